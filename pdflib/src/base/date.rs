@@ -0,0 +1,102 @@
+use std::fmt::{Display, Formatter};
+
+use super::*;
+
+/// A date as found in PDF text strings, e.g., `/CreationDate` and `/ModDate` in the document 
+/// information dictionary.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PdfDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// The offset from UT in minutes, or `None` if the time zone is not specified.
+    pub utc_offset: Option<i16>,
+}
+
+impl TryFrom<&[u8]> for PdfDate {
+    type Error = Error;
+
+    /// Parses a date in the format `D:YYYYMMDDHHmmSSOHH'mm'`. The `D:` prefix is optional, as are 
+    /// all the fields following the year. Missing fields default to the earliest possible value.
+    fn try_from(s: &[u8]) -> Result<PdfDate, Error> {
+        let err = || Error::Parse("malformed date");
+        let mut rest = s.strip_prefix(b"D:").unwrap_or(s);
+        let mut digits = |len: usize| -> Option<u16> {
+            let digits = rest.get(..len).filter(|d| d.iter().all(u8::is_ascii_digit))?;
+            rest = &rest[len..];
+            Some(digits.iter().fold(0, |acc, &d| acc * 10 + (d - b'0') as u16))
+        };
+        let year = digits(4).ok_or_else(err)?;
+        let mut fields = [1, 1, 0, 0, 0];
+        for field in &mut fields {
+            match digits(2) {
+                Some(value) => *field = value,
+                None => break
+            }
+        }
+        let [month, day, hour, minute, second] = fields;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 59 {
+            return Err(err());
+        }
+        let utc_offset = match rest.split_first() {
+            None => None,
+            Some((&sign @ (b'Z' | b'+' | b'-'), tz)) => {
+                rest = tz;
+                let mut digits = |max: u16| -> Result<u16, Error> {
+                    let Some(&[d1, d2]) = rest.get(..2) else { return Ok(0) };
+                    if !d1.is_ascii_digit() || !d2.is_ascii_digit() {
+                        return Err(err());
+                    }
+                    rest = rest[2..].strip_prefix(b"'").unwrap_or(&rest[2..]);
+                    let value = (d1 - b'0') as u16 * 10 + (d2 - b'0') as u16;
+                    if value <= max { Ok(value) } else { Err(err()) }
+                };
+                let offset = (digits(23)? * 60 + digits(59)?) as i16;
+                Some(if sign == b'-' { -offset } else { offset })
+            },
+            Some(_) => return Err(err())
+        };
+        if !rest.is_empty() {
+            return Err(err());
+        }
+        Ok(PdfDate { year, month: month as u8, day: day as u8, hour: hour as u8, minute: minute as u8,
+            second: second as u8, utc_offset })
+    }
+}
+
+impl Display for PdfDate {
+    /// Formats the date according to ISO 8601.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second)?;
+        match self.utc_offset {
+            None => Ok(()),
+            Some(0) => f.write_str("Z"),
+            Some(off) => write!(f, "{}{:02}:{:02}", if off < 0 { '-' } else { '+' },
+                off.unsigned_abs() / 60, off.unsigned_abs() % 60)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_date() {
+        let date = PdfDate::try_from(&b"D:20240131235959+01'30'"[..]).unwrap();
+        assert_eq!(date, PdfDate { year: 2024, month: 1, day: 31, hour: 23, minute: 59, second: 59,
+            utc_offset: Some(90) });
+        assert_eq!(date.to_string(), "2024-01-31T23:59:59+01:30");
+        assert_eq!(PdfDate::try_from(&b"D:199812231952-08'00"[..]).unwrap().to_string(), "1998-12-23T19:52:00-08:00");
+        assert_eq!(PdfDate::try_from(&b"2001Z"[..]).unwrap().to_string(), "2001-01-01T00:00:00Z");
+        assert_eq!(PdfDate::try_from(&b"D:2001"[..]).unwrap().utc_offset, None);
+        assert!(PdfDate::try_from(&b"D:20241301"[..]).is_err());
+        assert!(PdfDate::try_from(&b"D:20240101 garbage"[..]).is_err());
+        assert!(PdfDate::try_from(&b"Jan 1 2024"[..]).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt::{Display, Formatter};
+
+use super::name::Name;
+use super::object::ObjRef;
+use super::error::Error;
+use super::types::*;
+
+/// A recoverable problem encountered while reading a file.
+///
+/// Events are reported to the [`Diagnostics`] sink of the [`FileParser`](crate::parser::FileParser) 
+/// and the readers built on it.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Event {
+    /// The cross-reference section at the given offset could not be read. The chain of previous 
+    /// sections is not followed further.
+    BrokenXRef { offset: Offset, error: Error },
+    /// The `startxref` section pointing to the given offset does not lead to a readable 
+    /// cross-reference section. A previous `startxref` is tried instead, if present.
+    InvalidStartXRef(Offset),
+    /// The chain of cross-reference sections loops back to the section at the given offset.
+    BreakingXRefChain(Offset),
+    /// The `/XRefStm` entry of a hybrid-reference file points to a classical section, found at 
+    /// the given offset.
+    XRefStmNotStream(Offset),
+    /// The `/XRefStm` entry of a hybrid-reference file points to a stream which is not a 
+    /// cross-reference stream, found at the given offset. The entry is ignored.
+    XRefStmNotXRef(Offset),
+    /// The `/Prev` entry in a cross-reference stream pointed to by `/XRefStm`, found at the given 
+    /// offset, was ignored.
+    PrevInXRefStm(Offset),
+    /// A newer cross-reference section, found at the given offset, declares a smaller `/Size` than 
+    /// an older one it supersedes. Objects beyond the newer `/Size` may be hidden.
+    ShrinkingXRefSize { offset: Offset, size: ObjNum, prev_size: ObjNum },
+    /// The cross-reference stream with the given object number lacks `/Size`. The size is derived 
+    /// from its `/Index` instead.
+    MissingXRefSize(ObjRef),
+    /// The object number appears more than once in the same cross-reference section. The first 
+    /// occurrence is used.
+    DuplicateXRefEntry(ObjNum),
+    /// A stream has an invalid or missing `/Length` and is read until `endstream`. The offset is 
+    /// that of the stream data.
+    MissingLength(Offset),
+    /// A stream has a `/Length` which is a real number with a fractional part. It is read until 
+    /// `endstream` as if `/Length` was missing. The offset is that of the stream data.
+    NonIntegralLength { offset: Offset, length: f64 },
+    /// The `stream` keyword is followed by a lone carriage return instead of a proper end-of-line 
+    /// marker. The offset is that of the stream data. Only reported in the lenient mode, otherwise 
+    /// this is an error.
+    StreamKeywordCR(Offset),
+    /// The header of an object stream lists a different object number at the given index than 
+    /// requested. Only reported in the lenient mode, otherwise this is an error.
+    ObjStmMismatch { objstm: ObjNum, index: ObjIndex, found: ObjNum, expected: ObjRef },
+    /// An integer too large to fit into `i64` was read as a real number. Only reported in the 
+    /// lenient mode, otherwise this is an error.
+    IntegerOutOfRange(String),
+    /// A dictionary entry does not start with a name. The entry is skipped up to the next name. 
+    /// Only reported in the lenient mode, otherwise this is an error.
+    MalformedDictEntry,
+    /// An `R` keyword not preceded by an object number and a generation number was found within 
+    /// an array or a dictionary, and skipped. Only reported in the lenient mode, otherwise this is 
+    /// an error.
+    UnexpectedR,
+    /// A reference to the given object was found while materializing the object itself, see 
+    /// [`SimpleReader::materialize()`](crate::reader::SimpleReader::materialize). The reference is 
+    /// replaced by `null`.
+    ReferenceCycle(ObjRef),
+    /// The given object could not be read while traversing the document, and was skipped.
+    UnreadableObject { objref: ObjRef, error: Error },
+    /// The page tree node lacks `/Count`. The pages under it are counted by traversing its 
+    /// descendants.
+    MissingPageCount(ObjRef),
+    /// The entry of the document information dictionary with the given key is not a valid date. 
+    /// It is treated as absent.
+    MalformedDate(Name),
+    /// An image in an `/Indexed` color space contains indices beyond the size of its palette. 
+    /// These are clamped to the last entry.
+    PaletteIndexOutOfRange,
+    /// A content stream contains a `Q` without a matching `q`. It is ignored.
+    UnbalancedRestore,
+    /// A content stream ends with the given number of graphics states saved by `q` and not 
+    /// restored by `Q`.
+    UnclosedSave(usize),
+    /// The operands of the given content stream operator are malformed. The operation is skipped.
+    MalformedOperands { operator: Vec<u8>, error: Error },
+    /// The `/ToUnicode` CMap of a font could not be parsed. Text is decoded as if it was absent.
+    MalformedToUnicode(Error),
+    /// The font selected by `Tf` is not present in the page resources. Its glyphs are treated as 
+    /// having a default width.
+    FontNotFound(Name),
+    /// Decoding by the given filter is not implemented for the parameters of a stream. The data 
+    /// are returned undecoded.
+    UnimplementedFilter(Name),
+    /// `/DecodeParms` were given for the named filter, which takes no parameters. They are 
+    /// ignored.
+    IgnoredDecodeParms(Name),
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::BrokenXRef { offset, error } => write!(f, "Error reading xref at {offset}: {error}"),
+            Event::InvalidStartXRef(offset) => write!(f, "startxref points to an invalid xref at {offset}."),
+            Event::BreakingXRefChain(offset) => write!(f, "Breaking xref chain detected at {offset}."),
+            Event::XRefStmNotStream(_) => write!(f, "/XRefStm pointed to a classical section."),
+            Event::XRefStmNotXRef(offset) => write!(f, "/XRefStm pointed to a stream at {offset} which is not /Type /XRef."),
+            Event::PrevInXRefStm(_) => write!(f, "Ignoring /Prev in a /XRefStm."),
+            Event::ShrinkingXRefSize { offset, size, prev_size } =>
+                write!(f, "Xref at {offset} has /Size {size}, smaller than {prev_size} in a previous revision."),
+            Event::MissingXRefSize(oref) => write!(f, "Xref stream {oref} lacks /Size, using /Index."),
+            Event::DuplicateXRefEntry(num) => write!(f, "Duplicate object number {num} in xref section."),
+            Event::MissingLength(_) =>
+                write!(f, "Stream with invalid or missing /Length found, reading until endstream."),
+            Event::NonIntegralLength { offset, length } =>
+                write!(f, "Stream at {offset} has non-integral /Length {length}, reading until endstream."),
+            Event::StreamKeywordCR(offset) =>
+                write!(f, "stream keyword followed by a lone CR, data assumed to start at {offset}."),
+            Event::ObjStmMismatch { objstm, index, found, expected } =>
+                write!(f, "Object stream {objstm} has object {found} at index {index}, expected {expected}."),
+            Event::IntegerOutOfRange(num) => write!(f, "Integer {num} out of range, reading as real."),
+            Event::MalformedDictEntry => write!(f, "Malformed dictionary entry, skipping to next key."),
+            Event::UnexpectedR => write!(f, "Unexpected R, skipping."),
+            Event::ReferenceCycle(oref) => write!(f, "Cycle detected at {oref}, replacing by null."),
+            Event::UnreadableObject { objref, error } => write!(f, "Skipping {objref}: {error}"),
+            Event::MissingPageCount(oref) => write!(f, "Page tree node {oref} lacks /Count, counting its descendants."),
+            Event::MalformedDate(key) => write!(f, "Malformed date in {key}."),
+            Event::PaletteIndexOutOfRange => write!(f, "Palette index out of range in indexed image, clamping."),
+            Event::UnbalancedRestore => write!(f, "Unbalanced Q in content stream."),
+            Event::UnclosedSave(count) =>
+                write!(f, "Unbalanced q at the end of content stream ({count} not restored)."),
+            Event::MalformedOperands { operator, error } =>
+                write!(f, "Operator {}: {error}.", String::from_utf8_lossy(operator)),
+            Event::MalformedToUnicode(error) => write!(f, "Could not parse /ToUnicode: {error}."),
+            Event::FontNotFound(name) => write!(f, "Font {name} not found in resources."),
+            Event::UnimplementedFilter(name) => write!(f, "Decoding {name} not implemented, returning data undecoded."),
+            Event::IgnoredDecodeParms(name) => write!(f, "Ignoring /DecodeParms for {name}."),
+        }
+    }
+}
+
+/// A sink for [`Event`]s, allowing to collect diagnostics programmatically.
+///
+/// The default implementation is [`LogDiagnostics`].
+pub trait Diagnostics: Send + Sync {
+    fn report(&self, event: Event);
+}
+
+/// A [`Diagnostics`] implementation forwarding all events to the `log` crate. If the `log` feature 
+/// is disabled, the events are discarded.
+#[derive(Debug, Default)]
+pub struct LogDiagnostics;
+
+impl Diagnostics for LogDiagnostics {
+    fn report(&self, event: Event) {
+        match event {
+            Event::BrokenXRef { .. } => log_error!("{event}"),
+            _ => log_warn!("{event}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use crate::base::Record;
+    use crate::parser::FileParser;
+    use crate::utils::Capture;
+
+    #[test]
+    fn test_sink() {
+        let data = b"xref\n0 2\n0000000000 65535 f \n0000000000 65535 f \n1 1\n0000000015 00000 n \ntrailer\n<< /Size 2 >>";
+        let capture = Arc::new(Capture::default());
+        let mut parser = FileParser::new(Cursor::new(data));
+        parser.set_diagnostics(capture.clone());
+        let xref = parser.read_xref_at(0).unwrap();
+        assert!(matches!(xref.map.get(&1), Some(Record::Free { .. })));
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::DuplicateXRefEntry(1)]));
+
+        // Must not panic regardless of the `log` feature.
+        LogDiagnostics.report(Event::DuplicateXRefEntry(1));
+    }
+}
@@ -1,43 +1,96 @@
 use std::fmt::{Display, Formatter};
 
 use super::name::Name;
-use super::object::Object;
+use super::object::BaseObject;
+use super::stream::{self, Stream, StreamData};
 
 /// Dictionary objects (like `<< /Length 42 >>`).
-#[derive(Debug, PartialEq, Clone, Default)]
-pub struct Dict(Vec<(Name, Object)>);
+///
+/// Like [`BaseObject`], this comes in two flavours, differing in how the data of streams among 
+/// the values are stored. See the type aliases [`Dict`] and [`OwnedDict`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct BaseDict<Data: StreamData>(Vec<(Name, BaseObject<Data>)>);
+
+impl<Data: StreamData> BaseDict<Data> {
+    /// Creates an empty dictionary. Unlike [`BaseDict::default()`], this can be used in constants.
+    pub const fn new() -> Self {
+        BaseDict(Vec::new())
+    }
 
-impl Dict {
     /// Looks up for a value for a given [`Name`] key. If not present, returns a static reference 
-    /// to [`Object::Null`].
-    pub fn lookup(&self, key: &[u8]) -> &Object {
+    /// to [`BaseObject::Null`].
+    pub fn lookup(&self, key: &[u8]) -> &BaseObject<Data> {
         self.0.iter()
             .find(|(name, _obj)| *name == key)
             .map(|(_name, obj)| obj)
-            .unwrap_or(&Object::Null)
+            .unwrap_or(&BaseObject::Null)
+    }
+
+    /// Checks whether an entry with the given key is present. Note that unlike the PDF 
+    /// specification, this does not treat entries with a `null` value as absent.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.0.iter().any(|(name, _obj)| *name == key)
+    }
+
+    /// Iterates over the keys of the dictionary, in the order they appear.
+    pub fn keys(&self) -> impl Iterator<Item = &Name> {
+        self.0.iter().map(|(name, _obj)| name)
+    }
+
+    /// Checks whether the `/Type` entry is a name equal to `tpe`. Don't pass the initial `'/'`.
+    pub fn has_type(&self, tpe: &[u8]) -> bool {
+        matches!(self.lookup(b"Type"), BaseObject::Name(name) if name == tpe)
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &(Name, Object)> {
+    pub fn iter(&self) -> impl Iterator<Item = &(Name, BaseObject<Data>)> {
         self.0.iter()
     }
+
+    /// Writes a human-readable representation of the dictionary into `w`, one entry per line. See 
+    /// [`Object::print_indented()`](super::BaseObject::print_indented()).
+    pub fn print_indented(&self, indent: usize, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let ind = BaseObject::<Data>::INDENT.repeat(indent);
+        writeln!(w, "<<")?;
+        for (key, val) in &self.0 {
+            write!(w, "{ind}{}{key} ", BaseObject::<Data>::INDENT)?;
+            val.print_indented(indent + 1, w)?;
+        }
+        writeln!(w, "{ind}>>")
+    }
+
+    /// Converts the dictionary into another flavour by applying 
+    /// [`BaseObject::map_streams()`] to its values.
+    pub fn map_streams<D2: StreamData, E>(self, func: &mut impl FnMut(&Stream<Data>) -> Result<D2, E>)
+        -> Result<BaseDict<D2>, E>
+    {
+        Ok(BaseDict(self.0.into_iter()
+            .map(|(key, val)| Ok((key, val.map_streams(func)?)))
+            .collect::<Result<_, E>>()?))
+    }
 }
 
-impl From<Vec<(Name, Object)>> for Dict {
-    fn from(vec: Vec<(Name, Object)>) -> Dict {
-        Dict(vec)
+impl<Data: StreamData> Default for BaseDict<Data> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl IntoIterator for Dict {
-    type Item = (Name, Object);
-    type IntoIter = <Vec<(Name, Object)> as IntoIterator>::IntoIter;
+impl<Data: StreamData> From<Vec<(Name, BaseObject<Data>)>> for BaseDict<Data> {
+    fn from(vec: Vec<(Name, BaseObject<Data>)>) -> Self {
+        BaseDict(vec)
+    }
+}
+
+impl<Data: StreamData> IntoIterator for BaseDict<Data> {
+    type Item = (Name, BaseObject<Data>);
+    type IntoIter = <Vec<(Name, BaseObject<Data>)> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
     }
 }
 
-impl Display for Dict {
+impl<Data: StreamData> Display for BaseDict<Data> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str("<< ")?;
         for (key, val) in &self.0 {
@@ -47,6 +100,13 @@ impl Display for Dict {
     }
 }
 
+/// A shorthand for [`BaseDict<Offset>`](BaseDict), the dictionary type of [`Object`](super::Object).
+pub type Dict = BaseDict<stream::ByRef>;
+
+/// A shorthand for [`BaseDict<Vec<u8>>`](BaseDict), the dictionary type of 
+/// [`OwnedObject`](super::OwnedObject).
+pub type OwnedDict = BaseDict<stream::ByVal>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +122,29 @@ mod tests {
         assert_eq!(dict.lookup(b"IKey"), &Object::Number(Number::Int(10)));
         assert_eq!(dict.lookup(b"Missing"), &Object::Null);
     }
+
+    #[test]
+    fn test_keys() {
+        let dict = Dict::from(vec![
+            (Name::from(b"Filter"), Object::new_name(b"FlateDecode")),
+            (Name::from(b"DecodeParms"), Object::Null),
+        ]);
+        assert!(dict.contains_key(b"Filter"));
+        assert!(dict.contains_key(b"DecodeParms"));
+        assert!(!dict.contains_key(b"Length"));
+        assert!(!dict.contains_key(b"/Filter"));
+        assert_eq!(dict.keys().collect::<Vec<_>>(), [&Name::from(b"Filter"), &Name::from(b"DecodeParms")]);
+        assert_eq!(Dict::new().keys().count(), 0);
+    }
+
+    #[test]
+    fn test_has_type() {
+        let dict = Dict::from(vec![(Name::from(b"Type"), Object::new_name(b"Catalog"))]);
+        assert!(dict.has_type(b"Catalog"));
+        assert!(!dict.has_type(b"Pages"));
+        assert!(!dict.has_type(b"/Catalog"));
+        let dict = Dict::from(vec![(Name::from(b"Type"), Object::new_string(b"Catalog"))]);
+        assert!(!dict.has_type(b"Catalog"));
+        assert!(!Dict::default().has_type(b"Catalog"));
+    }
 }
@@ -4,16 +4,17 @@ use std::fmt::{Display, Formatter};
 ///
 /// Currently, this holds either a [`std::io::Error`] or a static string.
 ///
-/// The [`Error::IO`] case is held via a [`std::rc::Rc`] in order for instances to be clone-able.
+/// The [`Error::IO`] case is held via a [`std::sync::Arc`] in order for instances to be clone-able
+/// and sendable across threads.
 #[derive(Debug, Clone)]
 pub enum Error {
-    IO(std::rc::Rc<std::io::Error>),
+    IO(std::sync::Arc<std::io::Error>),
     Parse(&'static str)
 }
 
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
-        Error::IO(std::rc::Rc::new(err))
+        Error::IO(std::sync::Arc::new(err))
     }
 }
 
@@ -28,7 +28,7 @@ impl Locator for XRef {
     fn locate(&self, objref: &ObjRef) -> Option<Record> {
         if objref.num >= self.size {
             if self.map.contains_key(&objref.num) {
-                log::warn!("Ignoring object number {} ≥ /Size ({}) even though its record exists.",
+                log_warn!("Ignoring object number {} ≥ /Size ({}) even though its record exists.",
                     objref.num, self.size);
             }
             return Some(Record::default());
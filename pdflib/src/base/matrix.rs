@@ -0,0 +1,89 @@
+use super::*;
+
+/// A transformation matrix `[a b c d e f]`, like the current transformation matrix or the text 
+/// matrix.
+///
+/// As in the PDF specification, points are treated as row vectors, so `m1 * m2` is the 
+/// transformation applying `m1` first and `m2` second.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Matrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Matrix {
+    /// The identity matrix.
+    pub const IDENTITY: Matrix = Matrix { a: 1., b: 0., c: 0., d: 1., e: 0., f: 0. };
+
+    /// A translation by `(tx, ty)`.
+    pub fn translate(tx: f64, ty: f64) -> Matrix {
+        Matrix { e: tx, f: ty, ..Matrix::IDENTITY }
+    }
+
+    /// Transforms the point `(x, y)`.
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+}
+
+impl Default for Matrix {
+    fn default() -> Matrix {
+        Matrix::IDENTITY
+    }
+}
+
+impl std::ops::Mul for Matrix {
+    type Output = Matrix;
+
+    fn mul(self, rhs: Matrix) -> Matrix {
+        Matrix {
+            a: self.a * rhs.a + self.b * rhs.c,
+            b: self.a * rhs.b + self.b * rhs.d,
+            c: self.c * rhs.a + self.d * rhs.c,
+            d: self.c * rhs.b + self.d * rhs.d,
+            e: self.e * rhs.a + self.f * rhs.c + rhs.e,
+            f: self.e * rhs.b + self.f * rhs.d + rhs.f,
+        }
+    }
+}
+
+impl TryFrom<&[Object]> for Matrix {
+    type Error = Error;
+
+    /// Reads a `Matrix` from six numbers, as found in a `/Matrix` array or in the operands of 
+    /// `cm` or `Tm`.
+    fn try_from(objs: &[Object]) -> Result<Matrix, Error> {
+        let err = || Error::Parse("malformed matrix");
+        let coords = objs.iter()
+            .map(|obj| match obj {
+                Object::Number(num) => Ok(num.as_f64()),
+                _ => Err(err())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let [a, b, c, d, e, f] = coords[..] else { return Err(err()) };
+        Ok(Matrix { a, b, c, d, e, f })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix() {
+        let scale = Matrix { a: 2., d: 3., ..Matrix::IDENTITY };
+        let shift = Matrix::translate(10., 20.);
+        assert_eq!((scale * shift).apply(1., 1.), (12., 23.));
+        assert_eq!((shift * scale).apply(1., 1.), (22., 63.));
+        assert_eq!(Matrix::IDENTITY * scale, scale);
+
+        let objs = [1, 0, 0, 1, 50, 700].map(|x| Object::Number(Number::Int(x)));
+        assert_eq!(Matrix::try_from(&objs[..]).unwrap(), Matrix::translate(50., 700.));
+        assert!(Matrix::try_from(&objs[..5]).is_err());
+    }
+}
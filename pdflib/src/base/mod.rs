@@ -32,7 +32,17 @@ pub use object::*;
 mod stream;
 pub use stream::*;
 
+mod rectangle;
+pub use rectangle::*;
+
+mod matrix;
+pub use matrix::*;
+
 mod string;
+pub use string::decode_text_string;
+
+mod date;
+pub use date::*;
 
 mod xref;
 pub use xref::*;
@@ -46,5 +56,8 @@ pub use resolver::*;
 mod error;
 pub use error::*;
 
+mod diagnostics;
+pub use diagnostics::*;
+
 mod header;
 pub use header::*;
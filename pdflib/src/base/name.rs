@@ -1,5 +1,7 @@
 use std::fmt::{Display, Debug, Formatter};
 
+use super::error::Error;
+
 /// Name objects (e.g., `/Pages`).
 ///
 /// The leading `/` is not stored as part of the name.
@@ -7,6 +9,28 @@ use std::fmt::{Display, Debug, Formatter};
 pub struct Name(pub(crate) Vec<u8>);
 
 impl Name {
+    /// Creates a `Name` from arbitrary bytes, which are escaped as needed when written. Returns an 
+    /// error if `s` contains a null byte, which can not be represented in a name.
+    pub fn try_new(s: &[u8]) -> Result<Name, Error> {
+        if s.contains(&0) {
+            return Err(Error::Parse("null byte in name"));
+        }
+        Ok(Name(s.to_owned()))
+    }
+
+    /// The length of the name when written in PDF syntax, including the leading `/` and the `#` 
+    /// escapes.
+    pub fn escaped_len(&self) -> usize {
+        1 + self.0.iter()
+            .map(|&c| if Self::needs_escape(c) { 3 } else { 1 })
+            .sum::<usize>()
+    }
+
+    fn needs_escape(c: u8) -> bool {
+        use crate::parser::cc::CharClass;
+        !(0x21..=0x7E).contains(&c) || !matches!(CharClass::of(c), CharClass::Reg) || c == b'#'
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         &self.0
     }
@@ -30,13 +54,12 @@ impl<const N: usize> From<&[u8; N]> for Name {
 
 impl Display for Name {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        use crate::parser::cc::CharClass;
         f.write_str("/")?;
-        for c in &self.0 {
-            if (0x21..=0x7E).contains(c) && matches!(CharClass::of(*c), CharClass::Reg) && *c != b'#' {
-                write!(f, "{}", *c as char)?
-            } else {
+        for &c in &self.0 {
+            if Self::needs_escape(c) {
                 write!(f, "#{:02X}", c)?
+            } else {
+                write!(f, "{}", c as char)?
             }
         }
         Ok(())
@@ -56,3 +79,23 @@ impl<T: AsRef<[u8]> + ?Sized> PartialEq<T> for Name {
         self.0 == other.as_ref()
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::Object;
+    use crate::parser::ObjParser;
+
+    #[test]
+    fn test_try_new() {
+        let name = Name::try_new(b"A B#(x)/\xFF").unwrap();
+        assert_eq!(name.to_string(), "/A#20B#23#28x#29#2F#FF");
+        assert_eq!(name.escaped_len(), name.to_string().len());
+        let parsed = ObjParser::read_obj(&mut name.to_string().as_bytes()).unwrap();
+        assert_eq!(parsed, Object::Name(name));
+
+        assert_eq!(Name::try_new(b"Type").unwrap().escaped_len(), 5);
+        assert!(Name::try_new(b"A\0B").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt::{Display, Formatter};
+
 /// A PDF number, which can be integer or real.
 ///
 /// The specification does not require particular bit widths, so `i64` and `f64` were chosen,
@@ -10,3 +12,60 @@ pub enum Number {
     Int(i64),
     Real(f64)
 }
+
+impl Number {
+    /// The maximum number of decimal places written when formatting a [`Number::Real`].
+    pub const MAX_DECIMALS: usize = 10;
+
+    /// Returns the value as `f64`, converting from [`Number::Int`] if necessary.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::Int(x) => x as f64,
+            Number::Real(x) => x
+        }
+    }
+}
+
+/// Formats the number in PDF syntax.
+///
+/// Real numbers are always written in fixed-point notation, as PDF does not allow exponents, 
+/// rounded to [`Number::MAX_DECIMALS`] decimal places. Trailing zeros are trimmed. Non-finite 
+/// values have no PDF representation and are written as `0`.
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Number::Int(x) => write!(f, "{x}"),
+            Number::Real(x) if !x.is_finite() => f.write_str("0"),
+            Number::Real(x) => {
+                let s = format!("{x:.*}", Number::MAX_DECIMALS);
+                let s = s.trim_end_matches('0').trim_end_matches('.');
+                f.write_str(if s == "-0" { "0" } else { s })
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Number::Int(-42).to_string(), "-42");
+        assert_eq!(Number::Real(-1.).to_string(), "-1");
+        assert_eq!(Number::Real(0.5).to_string(), "0.5");
+        assert_eq!(Number::Real(1e-7).to_string(), "0.0000001");
+        assert_eq!(Number::Real(1e-20).to_string(), "0");
+        assert_eq!(Number::Real(-1e-20).to_string(), "0");
+        assert_eq!(Number::Real(1.23456789e-5).to_string(), "0.0000123457");
+        assert_eq!(Number::Real(1e20).to_string(), "100000000000000000000");
+        assert_eq!(Number::Real(-2.5e15).to_string(), "-2500000000000000");
+        assert_eq!(Number::Real(f64::NAN).to_string(), "0");
+        for x in [1e-20, 1e-7, 1e20, 1e300, -3.5e-12] {
+            let s = Number::Real(x).to_string();
+            assert!(!s.contains(['e', 'E']));
+            assert!(s.bytes().all(|c| c.is_ascii_digit() || c == b'.' || c == b'-'));
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 
 use super::name::Name;
-use super::dict::Dict;
+use super::dict::BaseDict;
 use super::number::Number;
 use super::string::format_string;
 use super::stream::{self, Stream, StreamData};
@@ -26,7 +27,7 @@ pub enum BaseObject<Data: StreamData> {
     /// Array (`[1 2 3]`)
     Array(Vec<Self>),
     /// Dictionary (`<< /Root 1 0 R >>`)
-    Dict(Dict),
+    Dict(BaseDict<Data>),
     /// Stream (`<< ... >> stream ... endstream`)
     Stream(Stream<Data>),
     /// Indirect object reference (`3 0 R`)
@@ -61,6 +62,13 @@ impl<Data: StreamData> BaseObject<Data> {
         }
     }
 
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Self::Number(val) => Some(val),
+            _ => None
+        }
+    }
+
     pub fn as_name(&self) -> Option<&Name> {
         match self {
             Self::Name(val) => Some(val),
@@ -75,7 +83,7 @@ impl<Data: StreamData> BaseObject<Data> {
         }
     }
 
-    pub fn as_dict(&self) -> Option<&Dict> {
+    pub fn as_dict(&self) -> Option<&BaseDict<Data>> {
         match self {
             Self::Dict(val) => Some(val),
             _ => None
@@ -103,6 +111,13 @@ impl<Data: StreamData> BaseObject<Data> {
         }
     }
 
+    pub fn into_number(self) -> Option<Number> {
+        match self {
+            Self::Number(val) => Some(val),
+            _ => None
+        }
+    }
+
     pub fn into_name(self) -> Option<Name> {
         match self {
             Self::Name(val) => Some(val),
@@ -117,7 +132,7 @@ impl<Data: StreamData> BaseObject<Data> {
         }
     }
 
-    pub fn into_dict(self) -> Option<Dict> {
+    pub fn into_dict(self) -> Option<BaseDict<Data>> {
         match self {
             Self::Dict(val) => Some(val),
             _ => None
@@ -149,13 +164,183 @@ impl<Data: StreamData> BaseObject<Data> {
     }
 }
 
+impl<Data: StreamData> BaseObject<Data> {
+    pub(super) const INDENT: &str = "  ";
+
+    /// Writes a human-readable representation of the object into `w`, with the contents of 
+    /// arrays and dictionaries on separate lines, indented according to their nesting level. 
+    /// `indent` gives the level of the object itself. Streams are represented by their 
+    /// dictionary followed by a `[stream]` marker.
+    ///
+    /// The first line is not indented, so that the output can continue a line started by the 
+    /// caller. The output is always terminated by a newline.
+    pub fn print_indented(&self, indent: usize, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let ind = Self::INDENT.repeat(indent);
+        match self {
+            Self::Array(arr) => {
+                writeln!(w, "[")?;
+                for (index, item) in arr.iter().enumerate() {
+                    write!(w, "{ind}{}[{}] ", Self::INDENT, index + 1)?;
+                    item.print_indented(indent + 1, w)?;
+                }
+                writeln!(w, "{ind}]")
+            },
+            Self::Dict(dict) => dict.print_indented(indent, w),
+            Self::Stream(stm) => {
+                stm.dict.print_indented(indent, w)?;
+                writeln!(w, "{ind}[stream]")
+            },
+            obj => writeln!(w, "{obj}")
+        }
+    }
+}
+
+impl<Data: StreamData> BaseObject<Data> {
+    /// Converts the object into another flavour by replacing the data of each stream with the 
+    /// result of `func`, at any depth, including streams within stream dictionaries. Other objects 
+    /// are unchanged.
+    pub fn map_streams<D2: StreamData, E>(self, func: &mut impl FnMut(&Stream<Data>) -> Result<D2, E>)
+        -> Result<BaseObject<D2>, E>
+    {
+        Ok(match self {
+            Self::Bool(val) => BaseObject::Bool(val),
+            Self::Number(num) => BaseObject::Number(num),
+            Self::String(s) => BaseObject::String(s),
+            Self::Name(name) => BaseObject::Name(name),
+            Self::Array(arr) => BaseObject::Array(arr.into_iter()
+                .map(|obj| obj.map_streams(func))
+                .collect::<Result<_, _>>()?),
+            Self::Dict(dict) => BaseObject::Dict(dict.map_streams(func)?),
+            Self::Stream(stm) => {
+                let data = func(&stm)?;
+                BaseObject::Stream(Stream { dict: stm.dict.map_streams(func)?, data })
+            },
+            Self::Ref(objref) => BaseObject::Ref(objref),
+            Self::Null => BaseObject::Null
+        })
+    }
+
+    /// Serializes the object in PDF syntax into `w`.
+    ///
+    /// For streams, the `/Length` entry of the dictionary is replaced by the actual length of the 
+    /// data. Streams referring to an input file (like those in [`Object`]) can not be serialized 
+    /// this way and result in an error of the kind [`std::io::ErrorKind::InvalidInput`].
+    pub fn write_to(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        match self {
+            Self::Stream(stm) => {
+                let data = stm.data.bytes()
+                    .ok_or(std::io::Error::new(std::io::ErrorKind::InvalidInput, "stream data not available"))?;
+                w.write_all(b"<< ")?;
+                for (key, val) in stm.dict.iter().filter(|(key, _)| key != b"Length") {
+                    write!(w, "{key} {val} ")?;
+                }
+                write!(w, "/Length {} >>\nstream\n", data.len())?;
+                w.write_all(data)?;
+                w.write_all(b"\nendstream")
+            },
+            obj => write!(w, "{obj}")
+        }
+    }
+
+    /// Encodes the object in a canonical binary form, suitable for comparing objects by contents.
+    ///
+    /// Unlike [`BaseObject::write_to()`], the result does not depend on the order of dictionary 
+    /// entries, and integers are distinguished from reals even if they print the same (`0` vs. 
+    /// `0.0`). For streams stored by value, the `/Length` entry is omitted in favour of the data. 
+    /// Streams whose data are stored by reference are encoded by their dictionary only.
+    ///
+    /// The encoding is not meant to be stored or parsed back.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_canonical(&mut buf);
+        buf
+    }
+
+    fn encode_canonical(&self, buf: &mut Vec<u8>) {
+        fn encode_len(len: usize, buf: &mut Vec<u8>) {
+            buf.extend_from_slice(&(len as u64).to_le_bytes());
+        }
+
+        fn encode_dict<'a, D: StreamData + 'a>(entries: impl Iterator<Item = &'a (Name, BaseObject<D>)>, buf: &mut Vec<u8>) {
+            let mut entries = entries.collect::<Vec<_>>();
+            entries.sort_by(|(k1, _), (k2, _)| k1.as_slice().cmp(k2.as_slice()));
+            encode_len(entries.len(), buf);
+            for (key, val) in entries {
+                encode_len(key.as_slice().len(), buf);
+                buf.extend_from_slice(key.as_slice());
+                val.encode_canonical(buf);
+            }
+        }
+
+        match self {
+            Self::Bool(val) => buf.extend_from_slice(if *val { b"T" } else { b"F" }),
+            Self::Number(Number::Int(val)) => {
+                buf.push(b'i');
+                buf.extend_from_slice(&val.to_le_bytes());
+            },
+            Self::Number(Number::Real(val)) => {
+                buf.push(b'r');
+                // 0.0 == -0.0 but their bit patterns differ
+                let val = if *val == 0.0 { 0.0 } else { *val };
+                buf.extend_from_slice(&val.to_bits().to_le_bytes());
+            },
+            Self::String(s) => {
+                buf.push(b's');
+                encode_len(s.len(), buf);
+                buf.extend_from_slice(s);
+            },
+            Self::Name(name) => {
+                buf.push(b'n');
+                encode_len(name.as_slice().len(), buf);
+                buf.extend_from_slice(name.as_slice());
+            },
+            Self::Array(arr) => {
+                buf.push(b'a');
+                encode_len(arr.len(), buf);
+                for obj in arr {
+                    obj.encode_canonical(buf);
+                }
+            },
+            Self::Dict(dict) => {
+                buf.push(b'd');
+                encode_dict(dict.iter(), buf);
+            },
+            Self::Stream(stm) => match stm.data.bytes() {
+                Some(data) => {
+                    buf.push(b'S');
+                    encode_dict(stm.dict.iter().filter(|(key, _)| key != b"Length"), buf);
+                    encode_len(data.len(), buf);
+                    buf.extend_from_slice(data);
+                },
+                None => {
+                    buf.push(b'X');
+                    encode_dict(stm.dict.iter(), buf);
+                }
+            },
+            Self::Ref(ObjRef{num, gen}) => {
+                buf.push(b'R');
+                buf.extend_from_slice(&num.to_le_bytes());
+                buf.extend_from_slice(&gen.to_le_bytes());
+            },
+            Self::Null => buf.push(b'N')
+        }
+    }
+}
+
+/// Hashes the canonical form of the object, as produced by [`BaseObject::canonical_bytes()`], 
+/// so that equal objects hash equally.
+impl<Data: StreamData> Hash for BaseObject<Data> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.canonical_bytes());
+    }
+}
+
 impl<Data: StreamData> Display for BaseObject<Data> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Bool(true) => f.write_str("true"),
             Self::Bool(false) => f.write_str("false"),
-            Self::Number(Number::Int(x)) => write!(f, "{x}"),
-            Self::Number(Number::Real(x)) => write!(f, "{x}"),
+            Self::Number(num) => write!(f, "{num}"),
             Self::String(s) => format_string(f, s),
             Self::Name(name) => write!(f, "{}", name),
             Self::Array(arr) => {
@@ -179,8 +364,14 @@ impl<Data: StreamData> Display for BaseObject<Data> {
 /// into the PDF file. This is the logical default for all read-only scenarios.
 pub type Object = BaseObject<stream::ByRef>;
 
+/// A shorthand for [`BaseObject<Vec<u8>>`].
+///
+/// In this variant, stream data are owned by the object. This is suitable for constructing new 
+/// objects, e.g. for writing.
+pub type OwnedObject = BaseObject<stream::ByVal>;
+
 /// An indirect object reference.
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
 pub struct ObjRef {
     pub num: ObjNum,
     pub gen: ObjGen
@@ -196,11 +387,12 @@ impl Display for ObjRef {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::base::{Dict, OwnedDict};
 
     #[test]
     fn test_display() {
         assert_eq!(format!("{}", Object::Number(Number::Real(-1.))), "-1");
-        assert_eq!(format!("{}", Object::Number(Number::Real(0.0000000000000001))), "0.0000000000000001");
+        assert_eq!(format!("{}", Object::Number(Number::Real(0.0000000001))), "0.0000000001");
         assert_eq!(format!("{}", Object::new_string(b"")), "()");
         assert_eq!(format!("{}", Object::new_string(b"\0\r\n\\")), "(\\000\\r\\n\\\\)");
         assert_eq!(format!("{}", Object::new_string(b"()")), "(\\(\\))");
@@ -233,4 +425,73 @@ mod tests {
         assert_eq!(format!("{}", Object::Dict(Dict::from(vec![
             (Name::from(b"Length"), Object::Ref(ObjRef{num: 8, gen: 0}))]))), "<< /Length 8 0 R >>");
     }
+
+    #[test]
+    fn test_as_number() {
+        use crate::parser::ObjParser;
+        let int = ObjParser::read_obj(&mut &b"1 "[..]).unwrap();
+        let real = ObjParser::read_obj(&mut &b"1.0 "[..]).unwrap();
+        assert_eq!(int.as_number(), Some(&Number::Int(1)));
+        assert_eq!(real.as_number(), Some(&Number::Real(1.)));
+        assert_ne!(int.as_number(), real.as_number());
+        assert_eq!(real.into_number(), Some(Number::Real(1.)));
+        assert_eq!(Object::new_name(b"1").as_number(), None);
+        assert_eq!(Object::new_string(b"1").into_number(), None);
+    }
+
+    #[test]
+    fn test_write_to() {
+        let obj = OwnedObject::Stream(Stream { dict: OwnedDict::from(vec![
+            (Name::from(b"Length"), OwnedObject::Ref(ObjRef{num: 8, gen: 0})),
+            (Name::from(b"Filter"), OwnedObject::new_name(b"ASCIIHexDecode")),
+        ]), data: b"414243>".to_vec() });
+        let mut out = Vec::new();
+        obj.write_to(&mut out).unwrap();
+        assert_eq!(out, b"<< /Filter /ASCIIHexDecode /Length 7 >>\nstream\n414243>\nendstream");
+
+        let mut out = Vec::new();
+        OwnedObject::Array(vec![OwnedObject::Null, OwnedObject::new_string(b"(")]).write_to(&mut out).unwrap();
+        assert_eq!(out, b"[ null (\\() ]");
+
+        let obj = Object::Stream(Stream { dict: Dict::default(), data: 42 });
+        assert!(obj.write_to(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_print_indented() {
+        let obj = Object::Dict(Dict::from(vec![
+            (Name::from(b"Type"), Object::new_name(b"Example")),
+            (Name::from(b"Kids"), Object::Array(vec![
+                Object::Ref(ObjRef{num: 3, gen: 0}),
+                Object::Array(vec![]),
+            ])),
+            (Name::from(b"Sub"), Object::Dict(Dict::from(vec![
+                (Name::from(b"Item"), Object::new_string(b"a string")),
+                (Name::from(b"Empty"), Object::Dict(Dict::default())),
+            ]))),
+            (Name::from(b"Content"), Object::Stream(Stream { dict: Dict::from(vec![
+                (Name::from(b"Length"), Object::Number(Number::Int(10)))]), data: 42 })),
+        ]));
+        let mut out = Vec::new();
+        obj.print_indented(0, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+<<
+  /Type /Example
+  /Kids [
+    [1] 3 0 R
+    [2] [
+    ]
+  ]
+  /Sub <<
+    /Item (a string)
+    /Empty <<
+    >>
+  >>
+  /Content <<
+    /Length 10
+  >>
+  [stream]
+>>
+");
+    }
 }
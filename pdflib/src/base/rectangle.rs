@@ -0,0 +1,48 @@
+use super::*;
+
+/// A rectangle, like the `/MediaBox` of a page or the `/Rect` of an annotation.
+///
+/// The coordinates are normalized so that `(llx, lly)` is the lower left corner and `(urx, ury)` 
+/// the upper right corner.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Rectangle {
+    pub llx: f64,
+    pub lly: f64,
+    pub urx: f64,
+    pub ury: f64,
+}
+
+impl TryFrom<&Object> for Rectangle {
+    type Error = Error;
+
+    /// Parses an array of four numbers into a `Rectangle`.
+    fn try_from(obj: &Object) -> Result<Rectangle, Error> {
+        let err = || Error::Parse("malformed rectangle");
+        let coords = obj.as_array().ok_or_else(err)?
+            .iter()
+            .map(|obj| match obj {
+                Object::Number(num) => Ok(num.as_f64()),
+                _ => Err(err())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let [x1, y1, x2, y2] = coords[..] else { return Err(err()) };
+        Ok(Rectangle { llx: x1.min(x2), lly: y1.min(y2), urx: x1.max(x2), ury: y1.max(y2) })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle() {
+        let arr = Object::Array(vec![
+            Object::Number(Number::Int(100)), Object::Number(Number::Real(20.5)),
+            Object::Number(Number::Int(10)), Object::Number(Number::Int(50)),
+        ]);
+        assert_eq!(Rectangle::try_from(&arr).unwrap(), Rectangle { llx: 10., lly: 20.5, urx: 100., ury: 50. });
+        assert!(Rectangle::try_from(&Object::Array(vec![Object::Number(Number::Int(1))])).is_err());
+        assert!(Rectangle::try_from(&Object::Null).is_err());
+    }
+}
@@ -8,13 +8,36 @@ use super::types::*;
 /// - `Vec<u8>`, for stream data stored as part of the object itself.
 ///
 /// These two types can then form the `data` field of a [`Stream`].
-pub trait StreamData {}
+pub trait StreamData {
+    /// The stream data, if stored by value.
+    fn bytes(&self) -> Option<&[u8]>;
+
+    /// The offset of the stream data, if stored by reference.
+    fn offset(&self) -> Option<Offset>;
+}
+
 pub(crate) type ByRef = Offset;
 pub(crate) type ByVal = Vec<u8>;
 
-impl StreamData for ByRef {}
+impl StreamData for ByRef {
+    fn bytes(&self) -> Option<&[u8]> {
+        None
+    }
 
-impl StreamData for ByVal {}
+    fn offset(&self) -> Option<Offset> {
+        Some(*self)
+    }
+}
+
+impl StreamData for ByVal {
+    fn bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+
+    fn offset(&self) -> Option<Offset> {
+        None
+    }
+}
 
 /// A PDF stream object.
 ///
@@ -23,15 +46,111 @@ impl StreamData for ByVal {}
 #[derive(Debug, PartialEq, Clone)]
 pub struct Stream<Data: StreamData> {
     /// The stream dictionary.
-    pub dict: Dict,
+    pub dict: BaseDict<Data>,
     /// The stream data, or its offset in the file (relative to `%PDF`).
     ///
     /// See [`StreamData`] for more details.
     pub data: Data
 }
 
+impl<Data: StreamData> Stream<Data> {
+    /// The offset of the stream data in the file (relative to `%PDF`), or `None` if the data are 
+    /// stored in a detached form.
+    pub fn data_offset(&self) -> Option<Offset> {
+        self.data.offset()
+    }
+
+    /// Checks whether this is an object stream (`/Type /ObjStm`).
+    pub fn is_objstm(&self) -> bool {
+        self.dict.has_type(b"ObjStm")
+    }
+
+    /// Checks whether this is a cross-reference stream (`/Type /XRef`).
+    pub fn is_xref(&self) -> bool {
+        self.dict.has_type(b"XRef")
+    }
+
+    /// Checks whether this is a form XObject (`/Subtype /Form`), i.e., a self-contained content 
+    /// stream with its own `/BBox` and `/Resources`.
+    ///
+    /// The `/Type` entry is optional for XObjects, so it is only checked if present.
+    pub fn is_form_xobject(&self) -> bool {
+        self.is_xobject(b"Form")
+    }
+
+    /// Checks whether this is an image XObject (`/Subtype /Image`).
+    ///
+    /// The `/Type` entry is optional for XObjects, so it is only checked if present.
+    pub fn is_image_xobject(&self) -> bool {
+        self.is_xobject(b"Image")
+    }
+
+    fn is_xobject(&self, subtype: &[u8]) -> bool {
+        (matches!(self.dict.lookup(b"Type"), BaseObject::Null) || self.dict.has_type(b"XObject"))
+            && matches!(self.dict.lookup(b"Subtype"), BaseObject::Name(name) if name.as_slice() == subtype)
+    }
+}
+
 /// A shorthand for [`Stream<Offset>`].
 pub type RefStream = Stream<ByRef>;
 
 /// A shorthand for [`Stream<Vec<u8>>`].
 pub type OwnedStream = Stream<ByVal>;
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_offset() {
+        let stm = RefStream { dict: Dict::default(), data: 74 };
+        assert_eq!(stm.data_offset(), Some(74));
+        let stm = OwnedStream { dict: OwnedDict::default(), data: vec![1, 2, 3] };
+        assert_eq!(stm.data_offset(), None);
+    }
+
+    #[test]
+    fn test_stream_type() {
+        let stm = RefStream { dict: Dict::from(vec![
+            (Name::from(b"Type"), Object::new_name(b"ObjStm")),
+            (Name::from(b"N"), Object::Number(Number::Int(3))),
+        ]), data: 0 };
+        assert!(stm.is_objstm());
+        assert!(!stm.is_xref());
+        let stm = RefStream { dict: Dict::from(vec![(Name::from(b"Type"), Object::new_name(b"XRef"))]), data: 0 };
+        assert!(!stm.is_objstm());
+        assert!(stm.is_xref());
+        let stm = RefStream { dict: Dict::default(), data: 0 };
+        assert!(!stm.is_objstm());
+        assert!(!stm.is_xref());
+    }
+
+    #[test]
+    fn test_xobject_type() {
+        let form = RefStream { dict: Dict::from(vec![
+            (Name::from(b"Type"), Object::new_name(b"XObject")),
+            (Name::from(b"Subtype"), Object::new_name(b"Form")),
+            (Name::from(b"BBox"), Object::Array(vec![Object::Number(Number::Int(0)); 4])),
+        ]), data: 0 };
+        assert!(form.is_form_xobject());
+        assert!(!form.is_image_xobject());
+        let image = RefStream { dict: Dict::from(vec![
+            (Name::from(b"Subtype"), Object::new_name(b"Image")),
+            (Name::from(b"Width"), Object::Number(Number::Int(1))),
+        ]), data: 0 };
+        assert!(!image.is_form_xobject());
+        assert!(image.is_image_xobject());
+        let other = RefStream { dict: Dict::from(vec![
+            (Name::from(b"Type"), Object::new_name(b"Metadata")),
+            (Name::from(b"Subtype"), Object::new_name(b"XML")),
+        ]), data: 0 };
+        assert!(!other.is_form_xobject());
+        assert!(!other.is_image_xobject());
+        let wrong_type = RefStream { dict: Dict::from(vec![
+            (Name::from(b"Type"), Object::new_name(b"Pattern")),
+            (Name::from(b"Subtype"), Object::new_name(b"Form")),
+        ]), data: 0 };
+        assert!(!wrong_type.is_form_xobject());
+    }
+}
@@ -20,3 +20,56 @@ pub(crate) fn format_string(f: &mut Formatter<'_>, s: &Vec<u8>) -> std::fmt::Res
     f.write_str(")")
 }
 
+/// Decodes a PDF text string (as used for document titles, outline entries etc.) into a Rust 
+/// `String`.
+///
+/// Text strings are either encoded in UTF-16BE or UTF-8, if starting with the respective byte order 
+/// mark, or in PDFDocEncoding otherwise. Invalid sequences and undefined codes are replaced by 
+/// U+FFFD.
+pub fn decode_text_string(s: &[u8]) -> String {
+    match s {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units = rest.chunks(2)
+                .map(|ch| u16::from_be_bytes([ch[0], *ch.get(1).unwrap_or(&0)]));
+            char::decode_utf16(units)
+                .map(|r| r.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect()
+        },
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => s.iter().map(|&c| pdfdoc_char(c)).collect()
+    }
+}
+
+fn pdfdoc_char(c: u8) -> char {
+    const HIGH: [char; 33] = [
+        '\u{2022}', '\u{2020}', '\u{2021}', '\u{2026}', '\u{2014}', '\u{2013}', '\u{0192}', '\u{2044}',
+        '\u{2039}', '\u{203A}', '\u{2212}', '\u{2030}', '\u{201E}', '\u{201C}', '\u{201D}', '\u{2018}',
+        '\u{2019}', '\u{201A}', '\u{2122}', '\u{FB01}', '\u{FB02}', '\u{0141}', '\u{0152}', '\u{0160}',
+        '\u{0178}', '\u{017D}', '\u{0131}', '\u{0142}', '\u{0153}', '\u{0161}', '\u{017E}', '\u{FFFD}',
+        '\u{20AC}'
+    ];
+    const LOW: [char; 8] = [
+        '\u{02D8}', '\u{02C7}', '\u{02C6}', '\u{02D9}', '\u{02DD}', '\u{02DB}', '\u{02DA}', '\u{02DC}'
+    ];
+    match c {
+        0x18..=0x1F => LOW[(c - 0x18) as usize],
+        0x7F | 0xAD => char::REPLACEMENT_CHARACTER,
+        0x80..=0xA0 => HIGH[(c - 0x80) as usize],
+        _ => c as char
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_text_string() {
+        assert_eq!(decode_text_string(b"Hello"), "Hello");
+        assert_eq!(decode_text_string(b"\x93rst \x84 \xA0\xE9"), "\u{FB01}rst \u{2014} \u{20AC}\u{E9}");
+        assert_eq!(decode_text_string(b"\xFE\xFF\x00H\x00i\xD8\x3D\xDE\x00"), "Hi\u{1F600}");
+        assert_eq!(decode_text_string(b"\xFE\xFF\xD8\x3D"), "\u{FFFD}");
+        assert_eq!(decode_text_string(b"\xEF\xBB\xBF\xC5\xBEena"), "\u{17E}ena");
+    }
+}
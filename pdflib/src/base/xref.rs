@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::io::Write;
 
 use super::*;
 use super::types::*;
@@ -37,6 +38,117 @@ impl XRef {
         prev.map.append(&mut self.map);
         self.map = prev.map;
     }
+
+    /// Serializes the records as a classical cross-reference table section, including the 
+    /// `trailer` keyword and the trailer dictionary given by `self.dict`. Consecutive object 
+    /// numbers are grouped into subsections.
+    ///
+    /// Compressed objects can not be represented in this format and result in an error of the 
+    /// kind [`std::io::ErrorKind::InvalidInput`].
+    pub fn write_table<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        w.write_all(b"xref\n")?;
+        let nums = self.map.keys().copied().collect::<Vec<_>>();
+        for group in nums.chunk_by(|a, b| *b == a + 1) {
+            writeln!(w, "{} {}", group[0], group.len())?;
+            for num in group {
+                match self.map[num] {
+                    Record::Used { gen, offset } => write!(w, "{offset:010} {gen:05} n\r\n")?,
+                    Record::Free { gen, next } => write!(w, "{next:010} {gen:05} f\r\n")?,
+                    Record::Compr { .. } => return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput,
+                        "compressed object in xref table"))
+                }
+            }
+        }
+        write!(w, "trailer\n{}\n", self.dict)
+    }
+
+    /// Serializes the records as a cross-reference stream object, including the `obj` and `endobj` 
+    /// keywords. The object number is taken from `self.tpe`, which needs to be 
+    /// [`XRefType::Stream`], otherwise an error of the kind [`std::io::ErrorKind::InvalidInput`] is 
+    /// returned.
+    ///
+    /// The entries of `self.dict` are copied to the stream dictionary, except for those describing 
+    /// the stream format, which are computed: `/W` uses the minimum field widths needed for the 
+    /// data, `/Index` is only included if the records do not form a single range starting at 0, 
+    /// and the data are compressed using `/FlateDecode` with the PNG Up predictor.
+    pub fn write_stream<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        let XRefType::Stream(objref) = self.tpe else {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "xref stream object number not known"));
+        };
+        let fields = |rec: &Record| -> (u64, u64, u64) {
+            match *rec {
+                Record::Free { gen, next } => (0, next, gen.into()),
+                Record::Used { gen, offset } => (1, offset, gen.into()),
+                Record::Compr { num_within, index } => (2, num_within, index.into()),
+            }
+        };
+        let width = |val: u64| (u64::BITS - val.leading_zeros()).div_ceil(8) as usize;
+        let (w2, w3) = self.map.values()
+            .map(fields)
+            .fold((1, 0), |(w2, w3), (_, f2, f3)|
+                (std::cmp::max(w2, width(f2)), std::cmp::max(w3, width(f3))));
+        let cols = 1 + w2 + w3;
+
+        let mut data = Vec::new();
+        let mut prev_row = vec![0u8; cols];
+        for rec in self.map.values() {
+            let (f1, f2, f3) = fields(rec);
+            let row = [&f1.to_be_bytes()[7..], &f2.to_be_bytes()[(8 - w2)..], &f3.to_be_bytes()[(8 - w3)..]].concat();
+            data.push(2);
+            data.extend(std::iter::zip(&row, &prev_row).map(|(new, old)| new.wrapping_sub(*old)));
+            prev_row = row;
+        }
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(&data)?;
+        let data = enc.finish()?;
+
+        let nums = self.map.keys().copied().collect::<Vec<_>>();
+        let groups = nums.chunk_by(|a, b| *b == a + 1).collect::<Vec<_>>();
+        let int = |val: usize| Object::Number(Number::Int(val as i64));
+        let mut dict = self.dict.iter()
+            .filter(|(key, _)| ![&b"Type"[..], b"Size", b"Index", b"W", b"Filter", b"DecodeParms", b"Length"]
+                .iter().any(|k| key == k))
+            .cloned()
+            .collect::<Vec<_>>();
+        dict.push((Name::from(b"Type"), Object::new_name(b"XRef")));
+        dict.push((Name::from(b"Size"), Object::Number(Number::Int(self.size as i64))));
+        if !matches!(groups[..], [] | [[0, ..]] if nums.len() as ObjNum == self.size) {
+            dict.push((Name::from(b"Index"), Object::Array(groups.iter()
+                .flat_map(|group| [Object::Number(Number::Int(group[0] as i64)), int(group.len())])
+                .collect())));
+        }
+        dict.push((Name::from(b"W"), Object::Array(vec![int(1), int(w2), int(w3)])));
+        dict.push((Name::from(b"Filter"), Object::new_name(b"FlateDecode")));
+        dict.push((Name::from(b"DecodeParms"), Object::Dict(Dict::from(vec![
+            (Name::from(b"Predictor"), int(12)),
+            (Name::from(b"Columns"), int(cols)),
+        ]))));
+
+        writeln!(w, "{} {} obj", objref.num, objref.gen)?;
+        let dict = Dict::from(dict).map_streams(&mut |_| Err(std::io::Error::new(std::io::ErrorKind::InvalidInput,
+            "stream within the trailer dictionary")))?;
+        OwnedObject::Stream(Stream { dict, data }).write_to(&mut w)?;
+        w.write_all(b"\nendobj\n")
+    }
+
+    /// Returns an object number suitable for a new object: the lowest number marked as free 
+    /// which can be reused (i.e., its generation number is less than 65535), or one past the 
+    /// highest number in use.
+    ///
+    /// In the former case, the new object must use the generation number given in the `Free` 
+    /// record.
+    pub fn next_free_number(&self) -> ObjNum {
+        self.map.iter()
+            .find(|(&num, rec)| num != 0 && matches!(rec, Record::Free{gen, ..} if *gen < 65535))
+            .map(|(&num, _)| num)
+            .unwrap_or_else(|| self.end())
+    }
+
+    /// One past the highest object number, either in the table or according to `/Size`.
+    pub(crate) fn end(&self) -> ObjNum {
+        let last = self.map.last_key_value().map(|(&num, _)| num + 1).unwrap_or(0);
+        std::cmp::max(last, self.size)
+    }
 }
 
 
@@ -73,3 +185,117 @@ impl Default for Record {
         Record::Free { gen: 65535, next: 0 }
     }
 }
+
+impl std::fmt::Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Record::Used { gen, offset } => write!(f, "used, gen {gen}, offset {offset}"),
+            Record::Free { gen, next } => write!(f, "free, gen {gen}, next {next}"),
+            Record::Compr { num_within, index } => write!(f, "compressed in {num_within} at index {index}"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::FileParser;
+
+    #[test]
+    fn test_write_table() {
+        let xref = XRef {
+            tpe: XRefType::Table,
+            map: BTreeMap::from([
+                (0, Record::Free { gen: 65535, next: 3 }),
+                (1, Record::Used { gen: 0, offset: 9 }),
+                (3, Record::Free { gen: 1, next: 0 }),
+                (4, Record::Used { gen: 2, offset: 1234567890 }),
+                (7, Record::Used { gen: 0, offset: 100 }),
+            ]),
+            dict: Dict::from(vec![
+                (Name::from(b"Size"), Object::Number(Number::Int(8))),
+                (Name::from(b"Root"), Object::Ref(ObjRef { num: 1, gen: 0 })),
+            ]),
+            size: 8
+        };
+        let mut out = Vec::new();
+        xref.write_table(&mut out).unwrap();
+        assert!(out.starts_with(b"xref\n0 2\n0000000003 65535 f\r\n0000000009 00000 n\r\n3 2\n"));
+
+        let fp = FileParser::new(std::io::Cursor::new(out));
+        let xref2 = fp.read_xref_at(0).unwrap();
+        assert!(matches!(xref2.tpe, XRefType::Table));
+        assert_eq!(xref2.map, xref.map);
+        assert_eq!(xref2.dict, xref.dict);
+        assert_eq!(xref2.size, 8);
+
+        assert_eq!(xref.map[&1].to_string(), "used, gen 0, offset 9");
+        assert_eq!(xref.map[&3].to_string(), "free, gen 1, next 0");
+
+        let mut xref = xref;
+        xref.map.insert(2, Record::Compr { num_within: 7, index: 0 });
+        assert_eq!(xref.map[&2].to_string(), "compressed in 7 at index 0");
+        assert!(xref.write_table(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_write_stream() {
+        let xref = XRef {
+            tpe: XRefType::Stream(ObjRef { num: 9, gen: 0 }),
+            map: BTreeMap::from([
+                (0, Record::Free { gen: 65535, next: 3 }),
+                (1, Record::Used { gen: 0, offset: 9 }),
+                (2, Record::Compr { num_within: 5, index: 1 }),
+                (3, Record::Free { gen: 1, next: 0 }),
+                (5, Record::Used { gen: 0, offset: 70000 }),
+                (9, Record::Used { gen: 0, offset: 100 }),
+            ]),
+            dict: Dict::from(vec![
+                (Name::from(b"Size"), Object::Number(Number::Int(10))),
+                (Name::from(b"Root"), Object::Ref(ObjRef { num: 1, gen: 0 })),
+                (Name::from(b"W"), Object::Array(vec![])),
+            ]),
+            size: 10
+        };
+        let mut out = Vec::new();
+        xref.write_stream(&mut out).unwrap();
+
+        let fp = FileParser::new(std::io::Cursor::new(out));
+        let xref2 = fp.read_xref_at(0).unwrap();
+        assert!(matches!(xref2.tpe, XRefType::Stream(ObjRef { num: 9, gen: 0 })));
+        assert_eq!(xref2.map, xref.map);
+        assert_eq!(xref2.size, 10);
+        assert_eq!(xref2.dict.lookup(b"Root"), &Object::Ref(ObjRef { num: 1, gen: 0 }));
+        assert_eq!(xref2.dict.lookup(b"W"), &Object::Array(vec![
+            Object::Number(Number::Int(1)), Object::Number(Number::Int(3)), Object::Number(Number::Int(2))]));
+        assert_eq!(xref2.dict.lookup(b"Index"), &Object::Array(vec![
+            Object::Number(Number::Int(0)), Object::Number(Number::Int(4)),
+            Object::Number(Number::Int(5)), Object::Number(Number::Int(1)),
+            Object::Number(Number::Int(9)), Object::Number(Number::Int(1))]));
+
+        let xref = XRef { tpe: XRefType::Table, ..xref };
+        assert!(xref.write_stream(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_next_free_number() {
+        let mut xref = XRef {
+            tpe: XRefType::Table,
+            map: BTreeMap::from([
+                (0, Record::Free { gen: 65535, next: 0 }),
+                (1, Record::Used { gen: 0, offset: 9 }),
+                (2, Record::Used { gen: 0, offset: 50 }),
+            ]),
+            dict: Dict::default(),
+            size: 3
+        };
+        assert_eq!(xref.next_free_number(), 3);
+        xref.size = 5;
+        assert_eq!(xref.next_free_number(), 5);
+        xref.map.insert(4, Record::Free { gen: 65535, next: 0 });
+        assert_eq!(xref.next_free_number(), 5);
+        xref.map.insert(3, Record::Free { gen: 1, next: 0 });
+        assert_eq!(xref.next_free_number(), 3);
+    }
+}
@@ -0,0 +1,187 @@
+//! Parsing of `/ToUnicode` CMaps.
+
+use std::collections::BTreeMap;
+
+use crate::base::*;
+use crate::content::ContentParser;
+
+/// Destination of a `bfrange` mapping.
+#[derive(Debug, Clone)]
+enum RangeTarget {
+    /// The UTF-16BE value of the first code, the following codes are mapped to values with the 
+    /// last code unit incremented.
+    Base(Vec<u16>),
+    /// An explicit value for each code in the range.
+    Array(Vec<String>),
+}
+
+/// A `/ToUnicode` CMap, mapping character codes of a font to Unicode text.
+#[derive(Debug, Clone, Default)]
+pub struct ToUnicode {
+    codespace: Vec<(Vec<u8>, Vec<u8>)>,
+    chars: BTreeMap<u32, String>,
+    ranges: Vec<(u32, u32, RangeTarget)>,
+    code_len: Option<usize>,
+}
+
+impl ToUnicode {
+    /// Parses the (decoded) data of a `/ToUnicode` stream.
+    ///
+    /// Only the `codespacerange`, `bfchar` and `bfrange` sections are interpreted, other 
+    /// operators are skipped.
+    pub fn parse(bytes: &[u8]) -> Result<ToUnicode, Error> {
+        let mut ret = ToUnicode::default();
+        for res in ContentParser::new(bytes) {
+            let (operands, operator) = res?;
+            match &operator[..] {
+                b"endcodespacerange" => {
+                    for pair in operands.chunks(2) {
+                        let [Object::String(lo), Object::String(hi)] = pair else {
+                            return Err(Error::Parse("malformed codespacerange"));
+                        };
+                        if lo.is_empty() || lo.len() > 4 || lo.len() != hi.len() {
+                            return Err(Error::Parse("malformed codespacerange"));
+                        }
+                        ret.codespace.push((lo.clone(), hi.clone()));
+                    }
+                },
+                b"endbfchar" => {
+                    for pair in operands.chunks(2) {
+                        let [Object::String(src), Object::String(dst)] = pair else {
+                            return Err(Error::Parse("malformed bfchar"));
+                        };
+                        let code = ret.source_code(src)?;
+                        ret.chars.insert(code, String::from_utf16_lossy(&utf16_units(dst)));
+                    }
+                },
+                b"endbfrange" => {
+                    for triple in operands.chunks(3) {
+                        let [Object::String(lo), Object::String(hi), dst] = triple else {
+                            return Err(Error::Parse("malformed bfrange"));
+                        };
+                        let (lo, hi) = (ret.source_code(lo)?, ret.source_code(hi)?);
+                        let target = match dst {
+                            Object::String(dst) => RangeTarget::Base(utf16_units(dst)),
+                            Object::Array(arr) => RangeTarget::Array(arr.iter()
+                                .map(|obj| obj.as_string()
+                                    .map(|s| String::from_utf16_lossy(&utf16_units(s)))
+                                    .ok_or(Error::Parse("malformed bfrange")))
+                                .collect::<Result<_, _>>()?),
+                            _ => return Err(Error::Parse("malformed bfrange"))
+                        };
+                        ret.ranges.push((lo, hi, target));
+                    }
+                },
+                _ => ()
+            }
+        }
+        Ok(ret)
+    }
+
+    fn source_code(&mut self, bytes: &[u8]) -> Result<u32, Error> {
+        if bytes.is_empty() || bytes.len() > 4 {
+            return Err(Error::Parse("malformed character code"));
+        }
+        self.code_len.get_or_insert(bytes.len());
+        Ok(bytes.iter().fold(0, |acc, &b| (acc << 8) | b as u32))
+    }
+
+    /// Maps a character code to Unicode text, if a mapping exists.
+    pub fn map(&self, code: u32) -> Option<String> {
+        if let Some(text) = self.chars.get(&code) {
+            return Some(text.clone());
+        }
+        let (lo, _, target) = self.ranges.iter().rev()
+            .find(|(lo, hi, _)| (*lo..=*hi).contains(&code))?;
+        let offset = code - lo;
+        match target {
+            RangeTarget::Base(units) => {
+                let mut units = units.clone();
+                let last = units.last_mut()?;
+                *last = last.checked_add(offset.try_into().ok()?)?;
+                Some(String::from_utf16_lossy(&units))
+            },
+            RangeTarget::Array(arr) => arr.get(offset as usize).cloned()
+        }
+    }
+
+    /// Splits a string into character codes according to the code space ranges of this CMap.
+    ///
+    /// Bytes not matching any code space range are taken as single-byte codes. If the CMap does 
+    /// not define any code space ranges, the length of the codes in its mappings is used.
+    pub fn codes(&self, bytes: &[u8]) -> Vec<u32> {
+        let mut ret = Vec::new();
+        let mut rest = bytes;
+        while !rest.is_empty() {
+            let len = if self.codespace.is_empty() {
+                self.code_len.unwrap_or(1).min(rest.len())
+            } else {
+                (1..=rest.len().min(4))
+                    .find(|&len| self.codespace.iter().any(|(lo, hi)| lo.len() == len
+                        && (0..len).all(|ix| (lo[ix]..=hi[ix]).contains(&rest[ix]))))
+                    .unwrap_or(1)
+            };
+            let (code, tail) = rest.split_at(len);
+            ret.push(code.iter().fold(0, |acc, &b| (acc << 8) | b as u32));
+            rest = tail;
+        }
+        ret
+    }
+}
+
+fn utf16_units(bytes: &[u8]) -> Vec<u16> {
+    bytes.chunks(2)
+        .map(|pair| match *pair {
+            [hi, lo] => u16::from_be_bytes([hi, lo]),
+            [lo] => lo as u16,
+            _ => unreachable!()
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_unicode() {
+        let data = b"/CIDInit /ProcSet findresource begin
+12 dict begin
+begincmap
+/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def
+/CMapName /Adobe-Identity-UCS def
+/CMapType 2 def
+1 begincodespacerange
+<0000> <FFFF>
+endcodespacerange
+2 beginbfchar
+<0003> <0020>
+<0024> <D835DC00>
+endbfchar
+2 beginbfrange
+<0010> <0019> <0030>
+<0050> <0052> [<0066006C> <00E9> <0041>]
+endbfrange
+endcmap
+CMapName currentdict /CMap defineresource pop
+end
+end";
+        let cmap = ToUnicode::parse(data).unwrap();
+        assert_eq!(cmap.map(0x03).as_deref(), Some(" "));
+        assert_eq!(cmap.map(0x24).as_deref(), Some("\u{1D400}"));
+        assert_eq!(cmap.map(0x10).as_deref(), Some("0"));
+        assert_eq!(cmap.map(0x19).as_deref(), Some("9"));
+        assert_eq!(cmap.map(0x50).as_deref(), Some("fl"));
+        assert_eq!(cmap.map(0x51).as_deref(), Some("é"));
+        assert_eq!(cmap.map(0x52).as_deref(), Some("A"));
+        assert_eq!(cmap.map(0x1A), None);
+        assert_eq!(cmap.codes(b"\x00\x10\x00\x03\x01"), vec![0x10, 0x03, 0x01]);
+
+        let cmap = ToUnicode::parse(b"1 beginbfchar <41> <0042> endbfchar").unwrap();
+        assert_eq!(cmap.codes(b"AB"), vec![0x41, 0x42]);
+        assert_eq!(cmap.map(0x41).as_deref(), Some("B"));
+
+        assert!(ToUnicode::parse(b"1 beginbfchar <41> endbfchar").is_err());
+    }
+}
@@ -9,6 +9,8 @@ pub struct Ascii85Decoder<R: BufRead> {
     reader: R,
     buf: Vec<u8>,
     index: usize,
+    started: bool,
+    error: Option<ErrorKind>,
 }
 
 impl<R: BufRead> Ascii85Decoder<R> {
@@ -16,7 +18,9 @@ impl<R: BufRead> Ascii85Decoder<R> {
         Ascii85Decoder {
             reader: input,
             buf: Vec::with_capacity(4),
-            index: 0
+            index: 0,
+            started: false,
+            error: None
         }
     }
 
@@ -38,25 +42,27 @@ impl<R: BufRead> Ascii85Decoder<R> {
             }
         }
     }
-}
 
-impl<R: BufRead> BufRead for Ascii85Decoder<R> {
-    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
-        if self.index < self.buf.len() {
-            return Ok(&self.buf[self.index..]);
+    fn read_group(&mut self) -> std::io::Result<()> {
+        if !self.started {
+            self.started = true;
+            // Skip the opening delimiter used outside PDF
+            if self.reader.fill_buf()?.starts_with(b"<~") {
+                self.reader.consume(2);
+            }
         }
+        self.buf.clear();
+        self.index = 0;
         match self.next_in()? {
-            None => Ok(&[]),
-            Some(b'z') => {
-                self.buf.clear();
-                self.buf.resize(4, 0u8);
-                Ok(&self.buf[..])
-            },
+            None => (),
+            Some(b'z') => self.buf.resize(4, 0u8),
             Some(c1) => {
                 let mut val = to_digit(c1)?;
                 let mut count = 1;
                 for _ in 1..5 {
                     let next = match self.next_in()? {
+                        // 'z' is only valid in place of a complete group
+                        Some(b'z') => return Err(std::io::Error::from(std::io::ErrorKind::InvalidData)),
                         Some(c) => {
                             count += 1;
                             to_digit(c)?
@@ -65,15 +71,34 @@ impl<R: BufRead> BufRead for Ascii85Decoder<R> {
                     };
                     val = val * 85 + next;
                 }
+                // A final partial group must encode at least one byte
+                if count == 1 {
+                    return Err(std::io::Error::from(std::io::ErrorKind::InvalidData));
+                }
                 let val: u32 = val.try_into()
                     .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?;
-                self.buf.clear();
                 self.buf.extend_from_slice(&val.to_be_bytes());
                 self.buf.truncate(count - 1);
-                self.index = 0;
-                Ok(&self.buf[..])
             }
         }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> BufRead for Ascii85Decoder<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.index < self.buf.len() {
+            return Ok(&self.buf[self.index..]);
+        }
+        // Errors are repeated on subsequent calls, as the input has already been consumed.
+        if let Some(kind) = self.error {
+            return Err(std::io::Error::from(kind));
+        }
+        if let Err(err) = self.read_group() {
+            self.error = Some(err.kind());
+            return Err(err);
+        }
+        Ok(&self.buf[..])
     }
 
     fn consume(&mut self, amt: usize) {
@@ -110,3 +135,53 @@ impl<R: BufRead> Read for Ascii85Decoder<R> {
         Ok(out_len)
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(input: &str) -> std::io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        decode(input.as_bytes()).read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn test_ascii85() {
+        assert_eq!(decode_all("87cURD]i,\"Ebo80~>").unwrap(), b"Hello World!");
+        assert_eq!(decode_all("<~87cURD]i,\"Ebo80~>").unwrap(), b"Hello World!");
+        assert_eq!(decode_all("~>").unwrap(), b"");
+    }
+
+    #[test]
+    fn test_ascii85_z() {
+        assert_eq!(decode_all("z~>").unwrap(), [0, 0, 0, 0]);
+        assert_eq!(decode_all("zz~>").unwrap(), [0; 8]);
+        assert_eq!(decode_all("<~87cz~>").unwrap_err().kind(), ErrorKind::InvalidData);
+        let mut dec = Ascii85Decoder::new("z 87cz~>".as_bytes());
+        assert_eq!(dec.fill_buf().unwrap(), [0; 4]);
+        dec.consume(4);
+        assert_eq!(dec.fill_buf().unwrap_err().kind(), ErrorKind::InvalidData);
+
+        // partial consumption of a 'z' group
+        let mut dec = Ascii85Decoder::new("zz~>".as_bytes());
+        assert_eq!(dec.fill_buf().unwrap(), [0; 4]);
+        dec.consume(3);
+        assert_eq!(dec.fill_buf().unwrap(), [0]);
+        dec.consume(1);
+        assert_eq!(dec.fill_buf().unwrap(), [0; 4]);
+    }
+
+    #[test]
+    fn test_ascii85_invalid_groups() {
+        assert_eq!(decode_all("87cURD]i,\"Ebo80~>").unwrap().len(), 12);
+        assert_eq!(decode_all("87cURD]i,\"Ebo8~>").unwrap().len(), 11);
+        assert_eq!(decode_all("87cURD]i,\"Ebo~>").unwrap().len(), 10);
+        assert_eq!(decode_all("87cURD]i,\"Eb~>").unwrap().len(), 9);
+        assert_eq!(decode_all("87cURD]i,\"E~>").unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(decode_all("s8W-!~>").unwrap(), [0xFF; 4]);
+        assert_eq!(decode_all("s8W-\"~>").unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(decode_all("uuuuu~>").unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+}
@@ -1,14 +1,34 @@
 use std::io::*;
-use crate::parser::Tokenizer;
+use std::fmt::{Display, Formatter};
+use crate::parser::cc::CharClass;
 use crate::utils;
 
 pub fn decode<R: BufRead>(input: R) -> BufReader<AsciiHexDecoder<R>> {
     BufReader::new(AsciiHexDecoder::new(input))
 }
 
+/// The error reported by `/ASCIIHexDecode` when encountering a character which is neither a 
+/// hexadecimal digit nor whitespace. It is wrapped in a [`std::io::Error`] of the kind 
+/// [`ErrorKind::InvalidData`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InvalidHexDigit {
+    /// The offending character.
+    pub byte: u8,
+    /// The position of the character within the encoded data.
+    pub offset: u64,
+}
+
+impl Display for InvalidHexDigit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid hex digit 0x{:02X} at offset {}", self.byte, self.offset)
+    }
+}
+
+impl std::error::Error for InvalidHexDigit { }
+
 pub struct AsciiHexDecoder<R: BufRead> {
     reader: R,
-    rem: Cursor<Vec<u8>>,
+    pos: u64,
     done: bool
 }
 
@@ -16,36 +36,42 @@ impl<R: BufRead> AsciiHexDecoder<R> {
     fn new(input: R) -> Self {
         AsciiHexDecoder {
             reader: input,
-            rem: Default::default(),
+            pos: 0,
             done: false
         }
     }
 
-    fn next_in(&mut self) -> std::io::Result<Option<u8>> {
-        if self.done { return Ok(None); }
-        let mut buf = [0];
-        if self.rem.read(&mut buf)? == 0 {
-            self.rem = match self.reader.read_token() {
-                Ok(tk) => Cursor::new(tk),
-                Err(err) if err.kind() == ErrorKind::UnexpectedEof
-                    => { self.done = true; return Ok(None); },
+    /// Returns the next non-whitespace character and its offset, or `None` at the end of data.
+    fn next_in(&mut self) -> std::io::Result<Option<(u8, u64)>> {
+        loop {
+            if self.done { return Ok(None); }
+            let c = match self.reader.fill_buf() {
+                Ok([]) => { self.done = true; return Ok(None); },
+                Ok(&[c, ..]) => c,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
                 Err(err) => return Err(err)
             };
-            assert!(self.rem.read(&mut buf)? == 1);
-        }
-        match buf[0] {
-            b'>' => { self.done = true; Ok(None) },
-            c => Ok(Some(c))
+            self.reader.consume(1);
+            self.pos += 1;
+            match c {
+                b'>' => self.done = true,
+                c if CharClass::of(c) == CharClass::Space => (),
+                c => return Ok(Some((c, self.pos - 1)))
+            }
         }
     }
 
     fn next_out(&mut self) -> std::io::Result<Option<u8>> {
+        let digit = |(byte, offset)| utils::hex_value(byte)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, InvalidHexDigit { byte, offset }));
         let msd = match self.next_in()? {
-            Some(msd) => utils::hex_value(msd).ok_or(Error::from(ErrorKind::InvalidData))?,
+            Some(c) => digit(c)?,
             None => return Ok(None),
         };
-        let lsd = utils::hex_value(self.next_in()?.unwrap_or(b'0'))
-            .ok_or(Error::from(ErrorKind::InvalidData))?;
+        let lsd = match self.next_in()? {
+            Some(c) => digit(c)?,
+            None => 0
+        };
         Ok(Some((msd << 4) | lsd))
     }
 }
@@ -64,3 +90,28 @@ impl<R: BufRead> Read for AsciiHexDecoder<R> {
         Ok(bytes)
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asciihex() {
+        let mut out = Vec::new();
+        decode(&b"74 65\n7374 3>"[..]).read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"test0");
+
+        let mut out = Vec::new();
+        decode(&b"7465"[..]).read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"te");
+    }
+
+    #[test]
+    fn test_error_offset() {
+        let err = decode(&b"0011 2233\n44x5>"[..]).read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let inner = err.get_ref().unwrap().downcast_ref::<InvalidHexDigit>().unwrap();
+        assert_eq!(inner, &InvalidHexDigit { byte: b'x', offset: 12 });
+    }
+}
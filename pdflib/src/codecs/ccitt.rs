@@ -0,0 +1,343 @@
+use std::io::{Read, BufRead};
+use crate::base::{Dict, Diagnostics, Event, Name};
+
+/// Returns `true` if the `/DecodeParms` describe data this module can decode, i.e., pure 
+/// two-dimensional Group 4 encoding (`/K` < 0).
+pub fn is_supported(params: &Dict) -> bool {
+    params.lookup(b"K").num_value::<i64>().unwrap_or(0) < 0
+}
+
+pub fn decode<'a, R: BufRead + 'a>(input: R, params: &Dict, diag: &dyn Diagnostics) -> Box<dyn BufRead + 'a> {
+    if !is_supported(params) {
+        diag.report(Event::UnimplementedFilter(Name::from(b"CCITTFaxDecode")));
+        return Box::new(input);
+    }
+    let bool_param = |key: &[u8], default| match params.lookup(key) {
+        crate::base::Object::Bool(val) => *val,
+        _ => default
+    };
+    Box::new(G4Decoder {
+        input: BitReader { input, byte: 0, bits: 0 },
+        cols: params.lookup(b"Columns").num_value().unwrap_or(1728),
+        rows: params.lookup(b"Rows").num_value().filter(|&rows| rows > 0),
+        black_is_1: bool_param(b"BlackIs1", false),
+        byte_align: bool_param(b"EncodedByteAlign", false),
+        ref_line: Vec::new(),
+        row: Vec::new(),
+        index: 0,
+        rows_done: 0,
+        done: false,
+    })
+}
+
+struct BitReader<R: BufRead> {
+    input: R,
+    byte: u8,
+    bits: u8,
+}
+
+impl<R: BufRead> BitReader<R> {
+    /// Returns the next bit, or `None` at the end of input.
+    fn bit(&mut self) -> std::io::Result<Option<bool>> {
+        if self.bits == 0 {
+            let mut buf = [0];
+            if self.input.read(&mut buf)? == 0 {
+                return Ok(None);
+            }
+            self.byte = buf[0];
+            self.bits = 8;
+        }
+        self.bits -= 1;
+        Ok(Some(self.byte & (1 << self.bits) != 0))
+    }
+
+    fn align(&mut self) {
+        self.bits = 0;
+    }
+}
+
+enum Mode {
+    Pass,
+    Horizontal,
+    Vertical(isize),
+    Extension,
+    Eol,
+}
+
+struct G4Decoder<R: BufRead> {
+    input: BitReader<R>,
+    cols: usize,
+    rows: Option<usize>,
+    black_is_1: bool,
+    byte_align: bool,
+    /// Changing elements of the reference line. Even indices are white-to-black transitions, odd 
+    /// indices black-to-white.
+    ref_line: Vec<usize>,
+    row: Vec<u8>,
+    index: usize,
+    rows_done: usize,
+    done: bool,
+}
+
+fn invalid_data() -> std::io::Error {
+    std::io::Error::from(std::io::ErrorKind::InvalidData)
+}
+
+impl<R: BufRead> G4Decoder<R> {
+    fn read_mode(&mut self) -> std::io::Result<Option<Mode>> {
+        let mut code = 0u16;
+        for len in 1..=12 {
+            let Some(bit) = self.input.bit()? else {
+                return if len == 1 { Ok(None) } else { Err(invalid_data()) };
+            };
+            code = (code << 1) | bit as u16;
+            let mode = match (len, code) {
+                (1, 0b1) => Mode::Vertical(0),
+                (3, 0b011) => Mode::Vertical(1),
+                (3, 0b010) => Mode::Vertical(-1),
+                (3, 0b001) => Mode::Horizontal,
+                (4, 0b0001) => Mode::Pass,
+                (6, 0b000011) => Mode::Vertical(2),
+                (6, 0b000010) => Mode::Vertical(-2),
+                (7, 0b0000011) => Mode::Vertical(3),
+                (7, 0b0000010) => Mode::Vertical(-3),
+                (7, 0b0000001) => Mode::Extension,
+                (12, 0b000000000001) => Mode::Eol,
+                _ => continue
+            };
+            return Ok(Some(mode));
+        }
+        Err(invalid_data())
+    }
+
+    fn read_run(&mut self, white: bool) -> std::io::Result<usize> {
+        let table = if white { WHITE_CODES } else { BLACK_CODES };
+        let mut total = 0;
+        'a: loop {
+            let mut code = 0u16;
+            for len in 1..=13 {
+                let bit = self.input.bit()?
+                    .ok_or(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+                code = (code << 1) | bit as u16;
+                if let Some(&(_, _, run)) = table.iter().find(|&&(l, c, _)| l == len && c == code) {
+                    total += run as usize;
+                    if run < 64 {
+                        return Ok(total);
+                    }
+                    continue 'a;
+                }
+            }
+            return Err(invalid_data());
+        }
+    }
+
+    /// Decodes a single row into a list of changing elements. Returns `None` if the end of data 
+    /// was reached instead.
+    fn read_changes(&mut self) -> std::io::Result<Option<Vec<usize>>> {
+        let cols = self.cols;
+        let mut changes = Vec::new();
+        let mut a0 = -1isize;
+        let mut white = true;
+        // Index of the first changing element on the reference line to the right of a0.
+        let mut ref_ix = 0;
+        while a0 < cols as isize {
+            let Some(mode) = self.read_mode()? else {
+                return match a0 {
+                    -1 => Ok(None),
+                    _ => Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))
+                };
+            };
+            while ref_ix < self.ref_line.len() && self.ref_line[ref_ix] as isize <= a0 {
+                ref_ix += 1;
+            }
+            let b1_ix = if (ref_ix % 2 == 0) == white { ref_ix } else { ref_ix + 1 };
+            let b1 = self.ref_line.get(b1_ix).copied().unwrap_or(cols);
+            let b2 = self.ref_line.get(b1_ix + 1).copied().unwrap_or(cols);
+            match mode {
+                Mode::Pass => a0 = b2 as isize,
+                Mode::Horizontal => {
+                    let start = std::cmp::max(a0, 0) as usize;
+                    let a1 = start + self.read_run(white)?;
+                    let a2 = a1 + self.read_run(!white)?;
+                    if a2 > cols {
+                        return Err(invalid_data());
+                    }
+                    changes.push(a1);
+                    changes.push(a2);
+                    a0 = a2 as isize;
+                },
+                Mode::Vertical(delta) => {
+                    let a1 = b1 as isize + delta;
+                    if a1 < 0 || a1 < a0 || a1 > cols as isize {
+                        return Err(invalid_data());
+                    }
+                    changes.push(a1 as usize);
+                    a0 = a1;
+                    white = !white;
+                },
+                Mode::Eol if a0 == -1 => return Ok(None),
+                Mode::Eol | Mode::Extension => return Err(invalid_data())
+            }
+        }
+        Ok(Some(changes))
+    }
+
+    fn read_row(&mut self) -> std::io::Result<&[u8]> {
+        self.index = 0;
+        self.row.clear();
+        if self.done || self.rows.is_some_and(|rows| self.rows_done >= rows) {
+            self.done = true;
+            return Ok(&[]);
+        }
+        if self.byte_align {
+            self.input.align();
+        }
+        let Some(changes) = self.read_changes()? else {
+            self.done = true;
+            return Ok(&[]);
+        };
+        let white_byte = if self.black_is_1 { 0x00 } else { 0xFF };
+        self.row.resize(self.cols.div_ceil(8), white_byte);
+        for span in changes.chunks(2) {
+            let end = span.get(1).copied().unwrap_or(self.cols);
+            for col in span[0]..end {
+                self.row[col / 8] ^= 0x80 >> (col % 8);
+            }
+        }
+        self.ref_line = changes;
+        self.rows_done += 1;
+        Ok(&self.row)
+    }
+}
+
+impl<R: BufRead> BufRead for G4Decoder<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.index < self.row.len() {
+            Ok(&self.row[self.index..])
+        } else {
+            self.read_row()
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.index += amt;
+    }
+}
+
+impl<R: BufRead> Read for G4Decoder<R> {
+    fn read(&mut self, out_buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut out_index = 0;
+        let out_len = out_buf.len();
+        while out_index < out_len {
+            let in_buf = match self.fill_buf() {
+                Ok([]) => return Ok(out_index),
+                Ok(buf) => buf,
+                Err(err) => match out_index {
+                    0 => return Err(err),
+                    read => return Ok(read)
+                }
+            };
+            let len = std::cmp::min(in_buf.len(), out_len - out_index);
+            out_buf[out_index..(out_index + len)].clone_from_slice(&in_buf[0..len]);
+            out_index += len;
+            self.consume(len);
+        }
+        Ok(out_len)
+    }
+}
+
+// Run length codes of ITU-T T.4, tables 2 and 3: (code length, code, run length).
+// Makeup codes (run length ≥ 64) are followed by a terminating code (run length < 64).
+const WHITE_CODES: &[(u8, u16, u16)] = &[
+    (4, 0b0111, 2), (4, 0b1000, 3), (4, 0b1011, 4), (4, 0b1100, 5),
+    (4, 0b1110, 6), (4, 0b1111, 7), (5, 0b10011, 8), (5, 0b10100, 9),
+    (5, 0b00111, 10), (5, 0b01000, 11), (5, 0b11011, 64), (5, 0b10010, 128),
+    (6, 0b000111, 1), (6, 0b001000, 12), (6, 0b000011, 13), (6, 0b110100, 14),
+    (6, 0b110101, 15), (6, 0b101010, 16), (6, 0b101011, 17), (6, 0b010111, 192),
+    (6, 0b011000, 1664), (7, 0b0100111, 18), (7, 0b0001100, 19), (7, 0b0001000, 20),
+    (7, 0b0010111, 21), (7, 0b0000011, 22), (7, 0b0000100, 23), (7, 0b0101000, 24),
+    (7, 0b0101011, 25), (7, 0b0010011, 26), (7, 0b0100100, 27), (7, 0b0011000, 28),
+    (7, 0b0110111, 256), (8, 0b00110101, 0), (8, 0b00000010, 29), (8, 0b00000011, 30),
+    (8, 0b00011010, 31), (8, 0b00011011, 32), (8, 0b00010010, 33), (8, 0b00010011, 34),
+    (8, 0b00010100, 35), (8, 0b00010101, 36), (8, 0b00010110, 37), (8, 0b00010111, 38),
+    (8, 0b00101000, 39), (8, 0b00101001, 40), (8, 0b00101010, 41), (8, 0b00101011, 42),
+    (8, 0b00101100, 43), (8, 0b00101101, 44), (8, 0b00000100, 45), (8, 0b00000101, 46),
+    (8, 0b00001010, 47), (8, 0b00001011, 48), (8, 0b01010010, 49), (8, 0b01010011, 50),
+    (8, 0b01010100, 51), (8, 0b01010101, 52), (8, 0b00100100, 53), (8, 0b00100101, 54),
+    (8, 0b01011000, 55), (8, 0b01011001, 56), (8, 0b01011010, 57), (8, 0b01011011, 58),
+    (8, 0b01001010, 59), (8, 0b01001011, 60), (8, 0b00110010, 61), (8, 0b00110011, 62),
+    (8, 0b00110100, 63), (8, 0b00110110, 320), (8, 0b00110111, 384), (8, 0b01100100, 448),
+    (8, 0b01100101, 512), (8, 0b01101000, 576), (8, 0b01100111, 640), (9, 0b011001100, 704),
+    (9, 0b011001101, 768), (9, 0b011010010, 832), (9, 0b011010011, 896), (9, 0b011010100, 960),
+    (9, 0b011010101, 1024), (9, 0b011010110, 1088), (9, 0b011010111, 1152), (9, 0b011011000, 1216),
+    (9, 0b011011001, 1280), (9, 0b011011010, 1344), (9, 0b011011011, 1408), (9, 0b010011000, 1472),
+    (9, 0b010011001, 1536), (9, 0b010011010, 1600), (9, 0b010011011, 1728), (11, 0b00000001000, 1792),
+    (11, 0b00000001100, 1856), (11, 0b00000001101, 1920), (12, 0b000000010010, 1984), (12, 0b000000010011, 2048),
+    (12, 0b000000010100, 2112), (12, 0b000000010101, 2176), (12, 0b000000010110, 2240), (12, 0b000000010111, 2304),
+    (12, 0b000000011100, 2368), (12, 0b000000011101, 2432), (12, 0b000000011110, 2496), (12, 0b000000011111, 2560),
+];
+
+const BLACK_CODES: &[(u8, u16, u16)] = &[
+    (2, 0b11, 2), (2, 0b10, 3), (3, 0b010, 1), (3, 0b011, 4),
+    (4, 0b0011, 5), (4, 0b0010, 6), (5, 0b00011, 7), (6, 0b000101, 8),
+    (6, 0b000100, 9), (7, 0b0000100, 10), (7, 0b0000101, 11), (7, 0b0000111, 12),
+    (8, 0b00000100, 13), (8, 0b00000111, 14), (9, 0b000011000, 15), (10, 0b0000110111, 0),
+    (10, 0b0000010111, 16), (10, 0b0000011000, 17), (10, 0b0000001000, 18), (10, 0b0000001111, 64),
+    (11, 0b00001100111, 19), (11, 0b00001101000, 20), (11, 0b00001101100, 21), (11, 0b00000110111, 22),
+    (11, 0b00000101000, 23), (11, 0b00000010111, 24), (11, 0b00000011000, 25), (11, 0b00000001000, 1792),
+    (11, 0b00000001100, 1856), (11, 0b00000001101, 1920), (12, 0b000011001010, 26), (12, 0b000011001011, 27),
+    (12, 0b000011001100, 28), (12, 0b000011001101, 29), (12, 0b000001101000, 30), (12, 0b000001101001, 31),
+    (12, 0b000001101010, 32), (12, 0b000001101011, 33), (12, 0b000011010010, 34), (12, 0b000011010011, 35),
+    (12, 0b000011010100, 36), (12, 0b000011010101, 37), (12, 0b000011010110, 38), (12, 0b000011010111, 39),
+    (12, 0b000001101100, 40), (12, 0b000001101101, 41), (12, 0b000011011010, 42), (12, 0b000011011011, 43),
+    (12, 0b000001010100, 44), (12, 0b000001010101, 45), (12, 0b000001010110, 46), (12, 0b000001010111, 47),
+    (12, 0b000001100100, 48), (12, 0b000001100101, 49), (12, 0b000001010010, 50), (12, 0b000001010011, 51),
+    (12, 0b000000100100, 52), (12, 0b000000110111, 53), (12, 0b000000111000, 54), (12, 0b000000100111, 55),
+    (12, 0b000000101000, 56), (12, 0b000001011000, 57), (12, 0b000001011001, 58), (12, 0b000000101011, 59),
+    (12, 0b000000101100, 60), (12, 0b000001011010, 61), (12, 0b000001100110, 62), (12, 0b000001100111, 63),
+    (12, 0b000011001000, 128), (12, 0b000011001001, 192), (12, 0b000001011011, 256), (12, 0b000000110011, 320),
+    (12, 0b000000110100, 384), (12, 0b000000110101, 448), (12, 0b000000010010, 1984), (12, 0b000000010011, 2048),
+    (12, 0b000000010100, 2112), (12, 0b000000010101, 2176), (12, 0b000000010110, 2240), (12, 0b000000010111, 2304),
+    (12, 0b000000011100, 2368), (12, 0b000000011101, 2432), (12, 0b000000011110, 2496), (12, 0b000000011111, 2560),
+    (13, 0b0000001101100, 512), (13, 0b0000001101101, 576), (13, 0b0000001001010, 640), (13, 0b0000001001011, 704),
+    (13, 0b0000001001100, 768), (13, 0b0000001001101, 832), (13, 0b0000001110010, 896), (13, 0b0000001110011, 960),
+    (13, 0b0000001110100, 1024), (13, 0b0000001110101, 1088), (13, 0b0000001110110, 1152), (13, 0b0000001110111, 1216),
+    (13, 0b0000001010010, 1280), (13, 0b0000001010011, 1344), (13, 0b0000001010100, 1408), (13, 0b0000001010101, 1472),
+    (13, 0b0000001011010, 1536), (13, 0b0000001011011, 1600), (13, 0b0000001100100, 1664), (13, 0b0000001100101, 1728),
+];
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::*;
+
+    #[test]
+    fn test_g4() {
+        // 8×6 image:
+        // ..####..
+        // ..####..
+        // ........
+        // .######.
+        // ..#####.
+        // #.......
+        let data: &[u8] = &[0x2E, 0xFC, 0x64, 0x72, 0xBC, 0x22, 0xBC, 0x00, 0x40, 0x04];
+        let mut params = Dict::from(vec![
+            (Name::from(b"K"), Object::Number(Number::Int(-1))),
+            (Name::from(b"Columns"), Object::Number(Number::Int(8))),
+        ]);
+        let mut out = Vec::new();
+        decode(data, &params, &LogDiagnostics).read_to_end(&mut out).unwrap();
+        assert_eq!(out, [0xC3, 0xC3, 0xFF, 0x81, 0xC1, 0x7F]);
+
+        params = Dict::from(vec![
+            (Name::from(b"K"), Object::Number(Number::Int(-1))),
+            (Name::from(b"Columns"), Object::Number(Number::Int(8))),
+            (Name::from(b"Rows"), Object::Number(Number::Int(2))),
+            (Name::from(b"BlackIs1"), Object::Bool(true)),
+        ]);
+        let mut out = Vec::new();
+        decode(data, &params, &LogDiagnostics).read_to_end(&mut out).unwrap();
+        assert_eq!(out, [0x3C, 0x3C]);
+    }
+}
@@ -1,18 +1,36 @@
 use std::io::{Read, BufRead, BufReader};
-use flate2::bufread::ZlibDecoder;
+use flate2::bufread::{ZlibDecoder, DeflateDecoder};
 use crate::base::Dict;
 
-pub fn decode<'a, R: BufRead + 'a>(input: R, params: &Dict) -> Box<dyn BufRead + 'a> {
+/// Decodes `/FlateDecode` data. Decoding stops at the end of the compressed stream, any data 
+/// following it (e.g. if `/Length` is too large) are ignored.
+pub fn decode<'a, R: BufRead + 'a>(mut input: R, params: &Dict) -> Box<dyn BufRead + 'a> {
+    let inflated: Box<dyn Read + 'a> = if has_zlib_header(&mut input) {
+        Box::new(ZlibDecoder::new(input))
+    } else {
+        log_warn!("/FlateDecode data without a zlib header, decoding as raw deflate.");
+        Box::new(DeflateDecoder::new(input))
+    };
     match params.lookup(b"Predictor").num_value() {
-        None => Box::new(BufReader::new(ZlibDecoder::new(input))),
+        None => Box::new(BufReader::new(inflated)),
         Some(10..=15) => Box::new(PNGDecode::new(
-            ZlibDecoder::new(input),
+            inflated,
             params.lookup(b"Columns").num_value().unwrap_or(1),
         )),
         _ => unimplemented!(),
     }
 }
 
+/// Checks whether the input starts with a valid zlib header (RFC 1950). Some producers omit it 
+/// and emit raw deflate data. If the header can not be inspected, it is assumed to be present, so 
+/// that any errors are reported by the zlib decoder.
+fn has_zlib_header(input: &mut impl BufRead) -> bool {
+    match input.fill_buf() {
+        Ok(&[cmf, flg, ..]) => cmf & 0x0F == 8 && (u16::from(cmf) << 8 | u16::from(flg)) % 31 == 0,
+        _ => true
+    }
+}
+
 struct PNGDecode<R: Read> {
     input: R,
     cols: usize,
@@ -96,3 +114,59 @@ impl<R: Read> Read for PNGDecode<R> {
         Ok(out_len)
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use crate::base::*;
+
+    #[test]
+    fn test_raw_deflate() {
+        let mut enc = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"raw deflate data").unwrap();
+        let data = enc.finish().unwrap();
+        let mut out = Vec::new();
+        decode(&data[..], &Dict::default()).read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"raw deflate data");
+
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"zlib data").unwrap();
+        let data = enc.finish().unwrap();
+        let mut out = Vec::new();
+        decode(&data[..], &Dict::default()).read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"zlib data");
+
+        // PNG Up predictor applies after the fallback
+        let mut enc = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(&[2, 1, 2, 3, 2, 1, 1, 1]).unwrap();
+        let data = enc.finish().unwrap();
+        let params = Dict::from(vec![
+            (Name::from(b"Predictor"), Object::Number(Number::Int(12))),
+            (Name::from(b"Columns"), Object::Number(Number::Int(3))),
+        ]);
+        let mut out = Vec::new();
+        decode(&data[..], &params).read_to_end(&mut out).unwrap();
+        assert_eq!(out, [1, 2, 3, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_trailing_garbage() {
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"compressed data").unwrap();
+        let mut data = enc.finish().unwrap();
+        data.extend_from_slice(b"\nendstream\nendobj\n");
+        let mut out = Vec::new();
+        decode(&data[..], &Dict::default()).read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"compressed data");
+
+        let mut enc = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"raw data").unwrap();
+        let mut data = enc.finish().unwrap();
+        data.extend_from_slice(b"\r\nendstream");
+        let mut out = Vec::new();
+        decode(&data[..], &Dict::default()).read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"raw data");
+    }
+}
@@ -1,10 +1,13 @@
 mod flate;
 mod asciihex;
 mod ascii85;
+mod ccitt;
 
 use crate::base::*;
 use std::io::BufRead;
 
+pub use asciihex::InvalidHexDigit;
+
 /// Supported PDF filters.
 #[derive(Debug, PartialEq)]
 pub enum Filter {
@@ -14,61 +17,179 @@ pub enum Filter {
     AsciiHex,
     /// `ASCII85Decode`
     Ascii85,
+    /// `/DCTDecode` (JPEG). Passed through undecoded, see [`ImageFormat`].
+    Dct(Dict),
+    /// `/JPXDecode` (JPEG 2000). Passed through undecoded, see [`ImageFormat`].
+    Jpx,
+    /// `/Crypt` with the `/Identity` crypt filter, which leaves the data unchanged.
+    Crypt,
+    /// `/CCITTFaxDecode` (supporting `/DecodeParms`). Only Group 4 encoding (`/K` < 0) is 
+    /// decoded, into one bit per pixel. Otherwise the data are passed through undecoded, see 
+    /// [`ImageFormat`].
+    CcittFax(Dict),
+}
+
+/// Image formats which can not be meaningfully decoded into a byte stream by this library.
+///
+/// Filters producing these are terminal: [`decode`] passes the data through unchanged, so that 
+/// the raw compressed bytes can be handed over to an external decoder.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ImageFormat {
+    /// JPEG (`/DCTDecode`).
+    Jpeg,
+    /// JPEG 2000 (`/JPXDecode`).
+    Jpeg2000,
+    /// CCITT Group 3 fax (`/CCITTFaxDecode` with `/K` ≥ 0).
+    CcittFax,
 }
 
 impl Filter {
-    fn try_from(name: &Name, params: Option<Dict>) -> Result<Filter, Error> {
+    fn try_from(name: &Name, params: Option<Dict>, diag: &dyn Diagnostics) -> Result<Filter, Error> {
+        let ignore_params = || if params.is_some() {
+            diag.report(Event::IgnoredDecodeParms(name.clone()));
+        };
         match name.as_slice() {
             b"FlateDecode" => Ok(Filter::Flate(params.unwrap_or_default())),
             b"ASCIIHexDecode" => {
-                if params.is_some() {
-                    log::warn!("Ingoring /DecodeParms for /ASCIIHexDecode.");
-                }
+                ignore_params();
                 Ok(Filter::AsciiHex)
             },
             b"ASCII85Decode" => {
-                if params.is_some() {
-                    log::warn!("Ingoring /DecodeParms for /ASCIIHexDecode.");
-                }
+                ignore_params();
                 Ok(Filter::Ascii85)
             },
+            b"Crypt" => {
+                match params.as_ref().map(|params| params.lookup(b"Name")) {
+                    None | Some(Object::Null) => Ok(Filter::Crypt),
+                    Some(Object::Name(name)) if name == b"Identity" => Ok(Filter::Crypt),
+                    _ => Err(Error::Parse("unimplemented crypt filter"))
+                }
+            },
+            b"DCTDecode" => Ok(Filter::Dct(params.unwrap_or_default())),
+            b"JPXDecode" => {
+                ignore_params();
+                Ok(Filter::Jpx)
+            },
+            b"CCITTFaxDecode" => Ok(Filter::CcittFax(params.unwrap_or_default())),
             _ => Err(Error::Parse("unimplemented filter"))
         }
     }
+
+    /// If this filter is passed through undecoded, returns the format of its encoded data.
+    pub fn image_format(&self) -> Option<ImageFormat> {
+        match self {
+            Filter::Dct(_) => Some(ImageFormat::Jpeg),
+            Filter::Jpx => Some(ImageFormat::Jpeg2000),
+            Filter::CcittFax(params) if !ccitt::is_supported(params) => Some(ImageFormat::CcittFax),
+            _ => None
+        }
+    }
+}
+
+/// Returns the format of the data produced by [`decode`] if the filter chain ends in an image 
+/// filter which is passed through undecoded, `None` if the output is fully decoded.
+pub fn image_format(filter: &[Filter]) -> Option<ImageFormat> {
+    filter.last()?.image_format()
 }
 
 /// Wraps a `BufRead` in an adapter decoding the data according to the provided filter chain.
+///
+/// Filters which can not be decoded are reported to [`LogDiagnostics`], see 
+/// [`decode_with_diagnostics`].
 pub fn decode<'a, R: BufRead + 'a>(input: R, filter: &[Filter]) -> Box<dyn BufRead + 'a> {
+    decode_with_diagnostics(input, filter, &LogDiagnostics)
+}
+
+/// Like [`decode`], but reports filters which can not be decoded, like CCITT Group 3 encoding, to 
+/// the given [`Diagnostics`] sink.
+pub fn decode_with_diagnostics<'a, R: BufRead + 'a>(input: R, filter: &[Filter], diag: &dyn Diagnostics)
+    -> Box<dyn BufRead + 'a>
+{
     match filter {
-        [] => Box::new(input),
+        [] | [Filter::Crypt] => Box::new(input),
         [Filter::Flate(params)] => flate::decode(input, params),
         [Filter::AsciiHex] => Box::new(asciihex::decode(input)),
         [Filter::Ascii85] => Box::new(ascii85::decode(input)),
-        [_, ..] => decode(decode(input, &filter[..1]), &filter[1..]),
+        [Filter::CcittFax(params)] => ccitt::decode(input, params, diag),
+        [Filter::Dct(_) | Filter::Jpx] => Box::new(input),
+        [_, ..] => decode_with_diagnostics(decode_with_diagnostics(input, &filter[..1], diag), &filter[1..], diag),
     }
 }
 
 /// Parse stream dictionary's `/Filter` and `/DecodeParms` entries into the form expected by 
-/// [`codecs::decode`](decode).
+/// [`codecs::decode`](decode). Indirect references in either entry, including array elements, are 
+/// resolved using `res`.
+///
+/// If `/DecodeParms` is absent, its abbreviation `/DP` is used instead. `/DecodeParms` given for 
+/// filters which take no parameters are ignored and reported to [`LogDiagnostics`], see 
+/// [`parse_filters_with_diagnostics`].
 pub fn parse_filters(dict: &Dict, res: &impl Resolver) -> Result<Vec<Filter>, Error> {
-    let filter = dict.lookup(b"Filter");
-    let params = dict.lookup(b"DecodeParms").to_owned();
-    let binding;
-    let filter_res = match filter {
-        Object::Ref(objref) => {
-            binding = res.resolve_ref(objref)?;
-            &binding
-        },
-        _ => filter
+    parse_filters_with_diagnostics(dict, res, &LogDiagnostics)
+}
+
+/// Like [`parse_filters`], but reports ignored `/DecodeParms` to the given [`Diagnostics`] sink.
+pub fn parse_filters_with_diagnostics(dict: &Dict, res: &impl Resolver, diag: &dyn Diagnostics)
+    -> Result<Vec<Filter>, Error>
+{
+    parse_filter_entries(dict.lookup(b"Filter"), decode_parms(dict), res, diag)
+}
+
+/// Like [`parse_filters`], but for the dictionary of an inline image, where `/F` and `/DP` can 
+/// stand for `/Filter` and `/DecodeParms`, and filter names can be abbreviated (like `/AHx` for 
+/// `/ASCIIHexDecode`).
+pub fn parse_inline_filters(dict: &Dict, res: &impl Resolver) -> Result<Vec<Filter>, Error> {
+    fn expand(name: &Name) -> Object {
+        let full: &[u8] = match name.as_slice() {
+            b"AHx" => b"ASCIIHexDecode",
+            b"A85" => b"ASCII85Decode",
+            b"LZW" => b"LZWDecode",
+            b"Fl" => b"FlateDecode",
+            b"RL" => b"RunLengthDecode",
+            b"CCF" => b"CCITTFaxDecode",
+            b"DCT" => b"DCTDecode",
+            other => other
+        };
+        Object::new_name(full)
+    }
+    let filter = match dict.lookup(b"Filter") {
+        Object::Null => dict.lookup(b"F"),
+        obj => obj
     };
-    match filter_res {
+    let filter = match filter {
+        Object::Name(name) => expand(name),
+        Object::Array(arr) => Object::Array(arr.iter()
+            .map(|obj| match obj {
+                Object::Name(name) => expand(name),
+                obj => obj.to_owned()
+            })
+            .collect()),
+        obj => obj.to_owned()
+    };
+    parse_filter_entries(&filter, decode_parms(dict), res, &LogDiagnostics)
+}
+
+fn decode_parms(dict: &Dict) -> &Object {
+    match dict.lookup(b"DecodeParms") {
+        Object::Null => dict.lookup(b"DP"),
+        obj => obj
+    }
+}
+
+fn parse_filter_entries(filter: &Object, params: &Object, res: &impl Resolver, diag: &dyn Diagnostics)
+    -> Result<Vec<Filter>, Error>
+{
+    let params = res.resolve_deep(params.to_owned())?;
+    // Resolves both an indirect /Filter and indirect names within an array, even if the array 
+    // itself is given indirectly.
+    let filter = res.resolve_deep(filter.to_owned())?;
+    match &filter {
         Object::Name(name) => {
             let params = match params {
                 Object::Dict(dict) => Some(dict),
                 Object::Null => None,
                 _ => return Err(Error::Parse("malformed /DecodeParms"))
             };
-            Ok(vec![Filter::try_from(name, params)?])
+            Ok(vec![Filter::try_from(name, params, diag)?])
         },
         Object::Array(filters) => {
             let params = match params {
@@ -84,23 +205,18 @@ pub fn parse_filters(dict: &Dict, res: &impl Resolver) -> Result<Vec<Filter>, Er
             let mut ret = Vec::new();
             let mut params_iter = params.map(IntoIterator::into_iter);
             for item in filters {
-                let binding;
-                let item_res = match item {
-                    Object::Ref(objref) => {
-                        binding = res.resolve_ref(objref)?;
-                        &binding
-                    },
-                    _ => item
-                };
                 let params = match params_iter.as_mut().and_then(Iterator::next) {
                     Some(Object::Dict(dict)) => Some(dict),
                     None | Some(Object::Null) => None,
                     _ => return Err(Error::Parse("malformed /DecodeParms"))
                 };
-                let filter = Filter::try_from(item_res.as_name()
-                    .ok_or(Error::Parse("malformed /Filter"))?, params)?;
+                let filter = Filter::try_from(item.as_name()
+                    .ok_or(Error::Parse("malformed /Filter"))?, params, diag)?;
                 ret.push(filter);
             }
+            if ret.iter().rev().skip(1).any(|filter| filter.image_format().is_some()) {
+                return Err(Error::Parse("image filter not last in /Filter chain"));
+            }
             Ok(ret)
         },
         Object::Null => Ok(vec![]),
@@ -111,7 +227,7 @@ pub fn parse_filters(dict: &Dict, res: &impl Resolver) -> Result<Vec<Filter>, Er
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Cursor;
+    use std::io::{Cursor, Read};
 
     #[test]
     fn test_filter_chaining() {
@@ -122,4 +238,110 @@ mod tests {
         output.read_to_string(&mut data_out).unwrap();
         assert_eq!(data_out, "test");
     }
+
+    #[test]
+    fn test_identity_crypt() {
+        let dict = Dict::from(vec![
+            (Name::from(b"Filter"), Object::Array(vec![Object::new_name(b"Crypt"), Object::new_name(b"ASCIIHexDecode")])),
+            (Name::from(b"DecodeParms"), Object::Array(vec![
+                Object::Dict(Dict::from(vec![(Name::from(b"Name"), Object::new_name(b"Identity"))])),
+                Object::Null
+            ])),
+        ]);
+        let filters = parse_filters(&dict, &()).unwrap();
+        assert_eq!(filters, vec![Filter::Crypt, Filter::AsciiHex]);
+        let mut data_out = String::new();
+        decode(Cursor::new("74657374>"), &filters).read_to_string(&mut data_out).unwrap();
+        assert_eq!(data_out, "test");
+
+        let dict = Dict::from(vec![
+            (Name::from(b"Filter"), Object::new_name(b"Crypt")),
+            (Name::from(b"DecodeParms"), Object::Dict(Dict::from(vec![(Name::from(b"Name"), Object::new_name(b"StdCF"))]))),
+        ]);
+        assert!(parse_filters(&dict, &()).is_err());
+    }
+
+    #[test]
+    fn test_indirect_filters() {
+        use crate::reader::SimpleReader;
+        use crate::utils;
+
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Filter 3 0 R /Length 9 >>\nstream\n74657374>\nendstream",
+            "[4 0 R]",
+            "/ASCIIHexDecode",
+            "<< /Filter 6 0 R /DecodeParms [null 7 0 R] /Length 0 >>\nstream\n\nendstream",
+            "[/ASCIIHexDecode 8 0 R]",
+            "<< /Predictor 12 >>",
+            "/FlateDecode",
+        ]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 2, gen: 0 })).unwrap();
+        assert_eq!(rdr.stream_filters(&stm).unwrap(), vec![Filter::AsciiHex]);
+        assert_eq!(rdr.read_stream_vec(&stm).unwrap(), b"test");
+
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 5, gen: 0 })).unwrap();
+        assert!(matches!(&rdr.stream_filters(&stm).unwrap()[..],
+            [Filter::AsciiHex, Filter::Flate(params)] if params.lookup(b"Predictor") == &Object::Number(Number::Int(12))));
+    }
+
+    #[test]
+    fn test_abbreviations() {
+        let dict = Dict::from(vec![
+            (Name::from(b"Filter"), Object::new_name(b"Crypt")),
+            (Name::from(b"DP"), Object::Dict(Dict::from(vec![(Name::from(b"Name"), Object::new_name(b"StdCF"))]))),
+        ]);
+        assert!(parse_filters(&dict, &()).is_err());
+
+        let dict = Dict::from(vec![
+            (Name::from(b"Filter"), Object::Array(vec![Object::new_name(b"Crypt"), Object::new_name(b"ASCIIHexDecode")])),
+            (Name::from(b"DP"), Object::Array(vec![
+                Object::Dict(Dict::from(vec![(Name::from(b"Name"), Object::new_name(b"Identity"))])),
+                Object::Null
+            ])),
+        ]);
+        let mut data_out = String::new();
+        decode(Cursor::new("74657374>"), &parse_filters(&dict, &()).unwrap()).read_to_string(&mut data_out).unwrap();
+        assert_eq!(data_out, "test");
+
+        let dict = Dict::from(vec![
+            (Name::from(b"Filter"), Object::new_name(b"FlateDecode")),
+            (Name::from(b"DP"), Object::Dict(Dict::from(vec![(Name::from(b"Predictor"), Object::Number(Number::Int(12)))]))),
+        ]);
+        assert!(matches!(&parse_filters(&dict, &()).unwrap()[..],
+            [Filter::Flate(params)] if params.lookup(b"Predictor") == &Object::Number(Number::Int(12))));
+
+        let dict = Dict::from(vec![
+            (Name::from(b"F"), Object::Array(vec![Object::new_name(b"AHx"), Object::new_name(b"Fl")])),
+            (Name::from(b"DP"), Object::Array(vec![Object::Null, Object::Null])),
+        ]);
+        assert_eq!(parse_inline_filters(&dict, &()).unwrap(), vec![Filter::AsciiHex, Filter::Flate(Dict::default())]);
+        assert_eq!(parse_filters(&dict, &()).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        use std::sync::Arc;
+        use crate::reader::SimpleReader;
+        use crate::utils::{self, Capture};
+
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Filter /ASCIIHexDecode /DecodeParms << /Predictor 12 >> /Length 9 >>\nstream\n74657374>\nendstream",
+            "<< /Filter /CCITTFaxDecode /DecodeParms << /K 0 >> /Length 4 >>\nstream\ntest\nendstream",
+        ]);
+        let capture = Arc::new(Capture::default());
+        let rdr = SimpleReader::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 2, gen: 0 })).unwrap();
+        assert_eq!(rdr.read_stream_vec(&stm).unwrap(), b"test");
+        assert!(matches!(&capture.0.lock().unwrap()[..],
+            [Event::IgnoredDecodeParms(name)] if name == b"ASCIIHexDecode"));
+
+        capture.0.lock().unwrap().clear();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 3, gen: 0 })).unwrap();
+        assert_eq!(rdr.read_stream_vec(&stm).unwrap(), b"test");
+        assert!(matches!(&capture.0.lock().unwrap()[..],
+            [Event::UnimplementedFilter(name)] if name == b"CCITTFaxDecode"));
+    }
 }
@@ -0,0 +1,164 @@
+//! Color spaces of images and other graphics objects.
+
+use std::io::{BufRead, Read, Seek};
+
+use crate::base::*;
+use crate::reader::SimpleReader;
+
+/// A color space, as given by a `/ColorSpace` entry.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ColorSpace {
+    DeviceGray,
+    DeviceRGB,
+    DeviceCMYK,
+    /// A palette of colors in the `base` color space. Each entry takes as many bytes in `lookup` 
+    /// as `base` has components, and there are `hival + 1` of them.
+    Indexed {
+        base: Box<ColorSpace>,
+        hival: u8,
+        lookup: Vec<u8>,
+    },
+}
+
+impl ColorSpace {
+    /// Parses a color space given by a name or an array, resolving indirect references using 
+    /// `reader`.
+    ///
+    /// ICC-based color spaces are replaced by the device color space with the same number of 
+    /// components (`/N`), similarly the CIE-based `/CalGray` and `/CalRGB` by `/DeviceGray` and 
+    /// `/DeviceRGB`, respectively.
+    pub fn parse<T: BufRead + Seek>(obj: &Object, reader: &SimpleReader<T>) -> Result<ColorSpace, Error> {
+        Self::parse_inner(obj, reader, true)
+    }
+
+    /// The base of an indexed color space can not be indexed itself. This is checked before 
+    /// parsing the base, so that a self-referencing `/Indexed` array can not recurse.
+    fn parse_inner<T: BufRead + Seek>(obj: &Object, reader: &SimpleReader<T>, allow_indexed: bool) -> Result<ColorSpace, Error> {
+        let err = || Error::Parse("malformed color space");
+        match reader.resolve_obj(obj.to_owned())? {
+            Object::Name(name) => match name.as_slice() {
+                b"DeviceGray" => Ok(ColorSpace::DeviceGray),
+                b"DeviceRGB" => Ok(ColorSpace::DeviceRGB),
+                b"DeviceCMYK" => Ok(ColorSpace::DeviceCMYK),
+                _ => Err(Error::Parse("unsupported color space"))
+            },
+            Object::Array(arr) => {
+                let family = arr.first()
+                    .and_then(Object::as_name)
+                    .ok_or_else(err)?;
+                match (family.as_slice(), &arr[1..]) {
+                    (b"DeviceGray" | b"CalGray", _) => Ok(ColorSpace::DeviceGray),
+                    (b"DeviceRGB" | b"CalRGB", _) => Ok(ColorSpace::DeviceRGB),
+                    (b"DeviceCMYK", _) => Ok(ColorSpace::DeviceCMYK),
+                    (b"ICCBased", [stm]) => {
+                        let stm = reader.resolve_stream(stm)?;
+                        match reader.resolve_obj(stm.dict.lookup(b"N").to_owned())?.num_value() {
+                            Some(1) => Ok(ColorSpace::DeviceGray),
+                            Some(3) => Ok(ColorSpace::DeviceRGB),
+                            Some(4) => Ok(ColorSpace::DeviceCMYK),
+                            _ => Err(Error::Parse("malformed ICC-based color space (/N)"))
+                        }
+                    },
+                    (b"Indexed", _) if !allow_indexed => Err(err()),
+                    (b"Indexed", [base, hival, lookup]) => {
+                        let base = ColorSpace::parse_inner(base, reader, false)?;
+                        let hival = reader.resolve_obj(hival.to_owned())?
+                            .num_value()
+                            .ok_or_else(err)?;
+                        let lookup = match reader.resolve_obj(lookup.to_owned())? {
+                            Object::String(s) => s,
+                            Object::Stream(stm) => {
+                                let mut data = Vec::new();
+                                reader.read_stream_data(&stm)?.read_to_end(&mut data)?;
+                                data
+                            },
+                            _ => return Err(err())
+                        };
+                        if lookup.len() < base.component_count() * (hival as usize + 1) {
+                            return Err(Error::Parse("malformed indexed color space (lookup too short)"));
+                        }
+                        Ok(ColorSpace::Indexed { base: Box::new(base), hival, lookup })
+                    },
+                    _ => Err(Error::Parse("unsupported color space"))
+                }
+            },
+            _ => Err(err())
+        }
+    }
+
+    /// The number of color components in this color space.
+    pub fn component_count(&self) -> usize {
+        match self {
+            ColorSpace::DeviceGray | ColorSpace::Indexed { .. } => 1,
+            ColorSpace::DeviceRGB => 3,
+            ColorSpace::DeviceCMYK => 4,
+        }
+    }
+
+    /// Converts a color to RGB. The components are expected in the range 0 to 1, except for 
+    /// [`ColorSpace::Indexed`], where the single component is the index into the palette.
+    ///
+    /// Missing components are taken as 0, out-of-range values are clamped.
+    pub fn to_rgb(&self, comps: &[f64]) -> [f64; 3] {
+        let comp = |ix: usize| comps.get(ix).copied().unwrap_or(0.).clamp(0., 1.);
+        match self {
+            ColorSpace::DeviceGray => [comp(0); 3],
+            ColorSpace::DeviceRGB => [comp(0), comp(1), comp(2)],
+            ColorSpace::DeviceCMYK => {
+                let k = 1. - comp(3);
+                [(1. - comp(0)) * k, (1. - comp(1)) * k, (1. - comp(2)) * k]
+            },
+            ColorSpace::Indexed { base, hival, lookup } => {
+                let index = comps.first().copied().unwrap_or(0.).round().clamp(0., *hival as f64) as usize;
+                let n = base.component_count();
+                let entry = lookup[index * n..(index + 1) * n].iter()
+                    .map(|&b| b as f64 / 255.)
+                    .collect::<Vec<_>>();
+                base.to_rgb(&entry)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_indexed() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "[/Indexed /DeviceRGB 2 <FF000000FF00000000>]",
+            "[/Indexed [/ICCBased 4 0 R] 1 5 0 R]",
+            "<< /N 4 /Length 0 >>\nstream\n\nendstream",
+            "<< /Filter /ASCIIHexDecode /Length 17 >>\nstream\n00000000000000FF>\nendstream",
+            "[/Indexed 6 0 R 1 <0000>]",
+            "[/Indexed [/Indexed /DeviceGray 1 <00FF>] 1 <0001>]",
+        ]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let cs = ColorSpace::parse(&Object::Ref(ObjRef { num: 2, gen: 0 }), &rdr).unwrap();
+        assert!(matches!(&cs, ColorSpace::Indexed { base, hival: 2, .. } if **base == ColorSpace::DeviceRGB));
+        assert_eq!(cs.component_count(), 1);
+        assert_eq!(cs.to_rgb(&[0.]), [1., 0., 0.]);
+        assert_eq!(cs.to_rgb(&[1.]), [0., 1., 0.]);
+        assert_eq!(cs.to_rgb(&[2.]), [0., 0., 0.]);
+        assert_eq!(cs.to_rgb(&[7.]), [0., 0., 0.]);
+
+        let cs = ColorSpace::parse(&Object::Ref(ObjRef { num: 3, gen: 0 }), &rdr).unwrap();
+        assert!(matches!(&cs, ColorSpace::Indexed { base, .. } if **base == ColorSpace::DeviceCMYK));
+        assert_eq!(cs.to_rgb(&[0.]), [1., 1., 1.]);
+        assert_eq!(cs.to_rgb(&[1.]), [0., 0., 0.]);
+
+        assert_eq!(ColorSpace::parse(&Object::new_name(b"DeviceGray"), &rdr).unwrap().to_rgb(&[0.5]), [0.5; 3]);
+        assert!(ColorSpace::parse(&Object::new_name(b"Pattern"), &rdr).is_err());
+
+        // The base of an indexed color space can not be indexed, including itself.
+        assert!(matches!(ColorSpace::parse(&Object::Ref(ObjRef { num: 6, gen: 0 }), &rdr),
+            Err(Error::Parse("malformed color space"))));
+        assert!(matches!(ColorSpace::parse(&Object::Ref(ObjRef { num: 7, gen: 0 }), &rdr),
+            Err(Error::Parse("malformed color space"))));
+    }
+}
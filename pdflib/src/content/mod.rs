@@ -0,0 +1,128 @@
+//! Parsing of content streams.
+
+use std::io::BufRead;
+
+use crate::base::*;
+use crate::parser::{ObjParser, ContentToken};
+use crate::parser::bp::ByteProvider;
+use crate::parser::cc::CharClass;
+
+mod state;
+pub use state::*;
+
+/// An operation in a content stream: its operands and operator.
+pub type Operation = (Vec<Object>, Vec<u8>);
+
+/// Splits a content stream into operations, each given as its operands and operator.
+///
+/// Inline images are reported as a single operation with the operator `BI` and two operands: the 
+/// image dictionary and the raw image data as a string.
+///
+/// After the first error, the iterator returns `None`.
+pub struct ContentParser<T: BufRead> {
+    reader: T,
+    done: bool,
+}
+
+impl<T: BufRead> ContentParser<T> {
+    /// Creates a `ContentParser` reading the (decoded) content stream from `reader`.
+    pub fn new(reader: T) -> Self {
+        Self { reader, done: false }
+    }
+
+    fn read_operation(&mut self) -> Result<Option<Operation>, Error> {
+        let mut operands = Vec::new();
+        loop {
+            match ObjParser::read_content_token(&mut self.reader)? {
+                Some(ContentToken::Operand(obj)) => operands.push(obj),
+                Some(ContentToken::Operator(op)) if op == b"BI" => {
+                    if !operands.is_empty() {
+                        log_warn!("Operands before inline image ignored.");
+                    }
+                    return self.read_inline_image().map(Some);
+                },
+                Some(ContentToken::Operator(op)) => return Ok(Some((operands, op))),
+                None if operands.is_empty() => return Ok(None),
+                None => return Err(Error::Parse("operands without operator at end of content stream"))
+            }
+        }
+    }
+
+    fn read_inline_image(&mut self) -> Result<Operation, Error> {
+        let mut dict = Vec::new();
+        loop {
+            let key = match ObjParser::read_content_token(&mut self.reader)? {
+                Some(ContentToken::Operator(op)) if op == b"ID" => break,
+                Some(ContentToken::Operand(Object::Name(name))) => name,
+                _ => return Err(Error::Parse("malformed inline image"))
+            };
+            match ObjParser::read_content_token(&mut self.reader)? {
+                Some(ContentToken::Operand(obj)) => dict.push((key, obj)),
+                _ => return Err(Error::Parse("malformed inline image"))
+            }
+        }
+        self.reader.next_if(|c| CharClass::of(c) == CharClass::Space);
+        let mut data = Vec::new();
+        loop {
+            data.push(self.reader.next_or_eof()?);
+            if data.ends_with(b"EI")
+                && (data.len() == 2 || CharClass::of(data[data.len() - 3]) == CharClass::Space)
+                && self.reader.peek().is_none_or(|c| CharClass::of(c) != CharClass::Reg) {
+                data.truncate(data.len().saturating_sub(3));
+                break;
+            }
+        }
+        Ok((vec![Object::Dict(Dict::from(dict)), Object::String(data)], b"BI".to_vec()))
+    }
+}
+
+impl<T: BufRead> Iterator for ContentParser<T> {
+    type Item = Result<Operation, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_operation() {
+            Ok(Some(op)) => Some(Ok(op)),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_parser() {
+        let data = b"q 1 0 0 1 50 700 cm BT /F1 12 Tf [(A) -120 (B)] TJ ET Q % comment\n\
+            BI /W 2 /H 1 /BPC 8 /CS /G ID \x01EI EI\nT* 1 2";
+        let ops = ContentParser::new(&data[..]).collect::<Vec<_>>();
+        let num = |x| Object::Number(Number::Int(x));
+        assert_eq!(ops.len(), 10);
+        assert_eq!(ops[0].as_ref().unwrap(), &(vec![], b"q".to_vec()));
+        assert_eq!(ops[1].as_ref().unwrap(), &(vec![num(1), num(0), num(0), num(1), num(50), num(700)], b"cm".to_vec()));
+        assert_eq!(ops[3].as_ref().unwrap(), &(vec![Object::new_name(b"F1"), num(12)], b"Tf".to_vec()));
+        assert_eq!(ops[4].as_ref().unwrap(), &(vec![Object::Array(vec![
+            Object::new_string(b"A"), num(-120), Object::new_string(b"B")])], b"TJ".to_vec()));
+        let (operands, op) = ops[7].as_ref().unwrap();
+        assert_eq!(op, b"BI");
+        assert_eq!(operands[0].as_dict().unwrap().lookup(b"W"), &num(2));
+        assert_eq!(operands[1], Object::String(b"\x01EI".to_vec()));
+        assert_eq!(ops[8].as_ref().unwrap(), &(vec![], b"T*".to_vec()));
+        assert!(ops[9].is_err());
+
+        let mut parser = ContentParser::new(&b"1 ) 2 Td"[..]);
+        assert!(parser.next().unwrap().is_err());
+        assert!(parser.next().is_none());
+    }
+}
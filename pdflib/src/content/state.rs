@@ -0,0 +1,194 @@
+use std::io::BufRead;
+use std::sync::Arc;
+
+use crate::base::*;
+
+use super::ContentParser;
+
+/// The parts of the graphics and text state tracked by [`GraphicsStateIter`].
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct GraphicsState {
+    /// The current transformation matrix, set by `cm`.
+    pub ctm: Matrix,
+    /// The text matrix, set by `Tm` and the text positioning operators.
+    pub text_matrix: Matrix,
+    /// The text line matrix, i.e., the text matrix at the start of the current line.
+    pub line_matrix: Matrix,
+    /// The font resource name as given by `Tf`.
+    pub font: Option<Name>,
+    /// The font size as given by `Tf`.
+    pub font_size: f64,
+    /// The text leading, set by `TL` and `TD`.
+    pub leading: f64,
+}
+
+impl GraphicsState {
+    fn next_line(&mut self, tx: f64, ty: f64) {
+        self.line_matrix = Matrix::translate(tx, ty) * self.line_matrix;
+        self.text_matrix = self.line_matrix;
+    }
+
+    fn apply(&mut self, operands: &[Object], operator: &[u8]) -> Result<(), Error> {
+        let nums = || operands.iter()
+            .map(|obj| match obj {
+                Object::Number(num) => Ok(num.as_f64()),
+                _ => Err(Error::Parse("malformed operands"))
+            })
+            .collect::<Result<Vec<_>, _>>();
+        match operator {
+            b"cm" => self.ctm = Matrix::try_from(operands)? * self.ctm,
+            b"BT" => {
+                self.text_matrix = Matrix::IDENTITY;
+                self.line_matrix = Matrix::IDENTITY;
+            },
+            b"Tf" => {
+                let [Object::Name(name), Object::Number(size)] = operands else {
+                    return Err(Error::Parse("malformed operands"));
+                };
+                self.font = Some(name.clone());
+                self.font_size = size.as_f64();
+            },
+            b"TL" => {
+                let [leading] = nums()?[..] else { return Err(Error::Parse("malformed operands")) };
+                self.leading = leading;
+            },
+            b"Td" | b"TD" => {
+                let [tx, ty] = nums()?[..] else { return Err(Error::Parse("malformed operands")) };
+                if operator == b"TD" {
+                    self.leading = -ty;
+                }
+                self.next_line(tx, ty);
+            },
+            b"Tm" => {
+                self.text_matrix = Matrix::try_from(operands)?;
+                self.line_matrix = self.text_matrix;
+            },
+            b"T*" | b"'" | b"\"" => self.next_line(0., -self.leading),
+            _ => ()
+        }
+        Ok(())
+    }
+}
+
+/// Iterates over the operations of a content stream along with the graphics state.
+///
+/// Each item is the state after the operation has been applied, followed by its operands and 
+/// operator. Only positioning is tracked: the advance of the text matrix due to shown glyphs is 
+/// not taken into account.
+///
+/// Unbalanced `Q` and operators with malformed operands are reported to the [`Diagnostics`] sink 
+/// and leave the state unchanged. So is a content stream ending with a `q` not restored. The 
+/// sink defaults to [`LogDiagnostics`] and can be replaced using 
+/// [`set_diagnostics()`](Self::set_diagnostics).
+pub struct GraphicsStateIter<T: BufRead> {
+    parser: ContentParser<T>,
+    state: GraphicsState,
+    stack: Vec<GraphicsState>,
+    done: bool,
+    diagnostics: Arc<dyn Diagnostics>,
+}
+
+impl<T: BufRead> GraphicsStateIter<T> {
+    /// Creates a `GraphicsStateIter` starting from the default state.
+    pub fn new(parser: ContentParser<T>) -> Self {
+        Self::with_state(parser, GraphicsState::default())
+    }
+
+    /// Creates a `GraphicsStateIter` starting from the given state, e.g. with the CTM of an 
+    /// enclosing form XObject.
+    pub fn with_state(parser: ContentParser<T>, state: GraphicsState) -> Self {
+        Self { parser, state, stack: Vec::new(), done: false, diagnostics: Arc::new(LogDiagnostics) }
+    }
+
+    /// Sets the sink receiving reports of recoverable problems.
+    pub fn set_diagnostics(&mut self, diag: Arc<dyn Diagnostics>) {
+        self.diagnostics = diag;
+    }
+
+    /// The number of graphics states saved by `q` and not yet restored by `Q`.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+}
+
+impl<T: BufRead> Iterator for GraphicsStateIter<T> {
+    type Item = Result<(GraphicsState, Vec<Object>, Vec<u8>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (operands, operator) = match self.parser.next() {
+            Some(Ok(op)) => op,
+            Some(Err(err)) => return Some(Err(err)),
+            None => {
+                if !self.done && !self.stack.is_empty() {
+                    self.diagnostics.report(Event::UnclosedSave(self.stack.len()));
+                }
+                self.done = true;
+                return None;
+            }
+        };
+        match &operator[..] {
+            b"q" => self.stack.push(self.state.clone()),
+            b"Q" => match self.stack.pop() {
+                Some(state) => self.state = state,
+                None => self.diagnostics.report(Event::UnbalancedRestore)
+            },
+            _ => if let Err(err) = self.state.apply(&operands, &operator) {
+                self.diagnostics.report(Event::MalformedOperands { operator: operator.clone(), error: err });
+            }
+        }
+        Some(Ok((self.state.clone(), operands, operator)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Capture;
+
+    #[test]
+    fn test_text_matrix() {
+        let data = b"BT /F1 12 Tf 14 TL 1 0 0 1 72 700 Tm (Hello) Tj 0 -20 Td (World) Tj T* (!) Tj ET";
+        let states = GraphicsStateIter::new(ContentParser::new(&data[..]))
+            .map(Result::unwrap)
+            .filter(|(_, _, op)| op == b"Tj")
+            .map(|(state, _, _)| state)
+            .collect::<Vec<_>>();
+        assert_eq!(states.len(), 3);
+        assert_eq!(states[0].text_matrix, Matrix::translate(72., 700.));
+        assert_eq!(states[0].font, Some(Name::from(b"F1")));
+        assert_eq!(states[0].font_size, 12.);
+        assert_eq!(states[1].text_matrix, Matrix::translate(72., 680.));
+        assert_eq!(states[2].text_matrix, Matrix::translate(72., 666.));
+        assert_eq!(states[2].line_matrix, states[2].text_matrix);
+    }
+
+    #[test]
+    fn test_save_restore() {
+        let capture = Arc::new(Capture::default());
+        let data = b"Q q 2 0 0 2 0 0 cm q 1 0 0 1 10 10 cm Q Q Q (x) Tj";
+        let mut iter = GraphicsStateIter::new(ContentParser::new(&data[..]));
+        iter.set_diagnostics(capture.clone());
+        let states = iter
+            .map(|res| res.unwrap().0.ctm)
+            .collect::<Vec<_>>();
+        let scale = Matrix { a: 2., d: 2., ..Matrix::IDENTITY };
+        assert_eq!(states, vec![
+            Matrix::IDENTITY, Matrix::IDENTITY, scale,
+            scale, Matrix::translate(10., 10.) * scale, scale,
+            Matrix::IDENTITY, Matrix::IDENTITY, Matrix::IDENTITY,
+        ]);
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::UnbalancedRestore, Event::UnbalancedRestore]));
+
+        let capture = Arc::new(Capture::default());
+        let data = b"q 2 0 0 2 0 0 cm q Q q (x) Tj /F1 Tf";
+        let mut iter = GraphicsStateIter::new(ContentParser::new(&data[..]));
+        iter.set_diagnostics(capture.clone());
+        assert_eq!(iter.by_ref().count(), 7);
+        // The stream ends with two states not restored.
+        assert_eq!(iter.depth(), 2);
+        assert!(iter.next().is_none());
+        assert!(matches!(&capture.0.lock().unwrap()[..], [Event::MalformedOperands { operator, .. },
+            Event::UnclosedSave(2)] if operator == b"Tf"));
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{BufRead, Seek};
+
+use crate::base::*;
+
+use super::Document;
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Finds the page a destination refers to.
+    ///
+    /// `dest` can be an explicit destination (an array whose first element is the page), or a 
+    /// named destination, which is looked up in the `/Dests` dictionary of the document catalog 
+    /// (names) or the `/Dests` name tree (strings).
+    ///
+    /// Returns `None` if a named destination is not found or if the destination does not refer to 
+    /// a page object of this document (like in remote go-to actions).
+    pub fn resolve_dest(&self, dest: &Object) -> Result<Option<ObjRef>, Error> {
+        let explicit = match self.reader.resolve_obj(dest.to_owned())? {
+            Object::Array(arr) => arr,
+            Object::Name(name) => match self.catalog.lookup(b"Dests") {
+                Object::Null => return Ok(None),
+                obj => match self.reader.resolve_dict(obj)?.lookup(name.as_slice()) {
+                    Object::Null => return Ok(None),
+                    obj => self.dest_array(obj)?
+                }
+            },
+            Object::String(key) => {
+                let tree = match self.catalog.lookup(b"Names") {
+                    Object::Null => return Ok(None),
+                    obj => self.reader.resolve_dict(obj)?.lookup(b"Dests").to_owned()
+                };
+                if tree == Object::Null {
+                    return Ok(None);
+                }
+                match self.name_tree(&tree)?.into_iter().find(|(name, _)| name == &key) {
+                    Some((_, obj)) => self.dest_array(&obj)?,
+                    None => return Ok(None)
+                }
+            },
+            _ => return Err(Error::Parse("malformed destination"))
+        };
+        Ok(explicit.first().and_then(Object::as_objref).copied())
+    }
+
+    /// The value of a named destination can be either the array or a dictionary with a `/D` entry.
+    fn dest_array(&self, obj: &Object) -> Result<Vec<Object>, Error> {
+        match self.reader.resolve_obj(obj.to_owned())? {
+            Object::Array(arr) => Ok(arr),
+            Object::Dict(dict) => self.reader.resolve_array(dict.lookup(b"D")),
+            _ => Err(Error::Parse("malformed destination"))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_resolve_dest() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Dests << /Old [3 0 R /Fit] >> /Names << /Dests << /Names [(intro) 2 0 R (other) [4 0 R /XYZ 0 0 0]] >> >> >>",
+            "<< /D [3 0 R /FitH 700] >>",
+            "<< /Type /Page >>",
+            "<< /Type /Page >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let page3 = Some(ObjRef { num: 3, gen: 0 });
+        let page4 = Some(ObjRef { num: 4, gen: 0 });
+        assert_eq!(doc.resolve_dest(&Object::new_string(b"intro")).unwrap(), page3);
+        assert_eq!(doc.resolve_dest(&Object::new_string(b"other")).unwrap(), page4);
+        assert_eq!(doc.resolve_dest(&Object::new_string(b"missing")).unwrap(), None);
+        assert_eq!(doc.resolve_dest(&Object::new_name(b"Old")).unwrap(), page3);
+        assert_eq!(doc.resolve_dest(&Object::new_name(b"Missing")).unwrap(), None);
+        assert_eq!(doc.resolve_dest(&Object::Array(vec![Object::Ref(ObjRef { num: 4, gen: 0 }),
+            Object::new_name(b"Fit")])).unwrap(), page4);
+        assert_eq!(doc.resolve_dest(&Object::Array(vec![Object::Number(Number::Int(0)),
+            Object::new_name(b"Fit")])).unwrap(), None);
+        assert!(doc.resolve_dest(&Object::Bool(true)).is_err());
+    }
+}
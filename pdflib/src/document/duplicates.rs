@@ -0,0 +1,106 @@
+use std::io::{BufRead, Seek, Read};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hasher};
+
+use crate::base::*;
+
+use super::Document;
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Finds groups of objects with identical contents, which could be merged into one.
+    ///
+    /// Objects are compared by their canonical form (see [`Object::canonical_bytes()`]), so the 
+    /// order of dictionary entries does not matter but `0` and `0.0` are considered different. 
+    /// Streams are compared by their dictionary and decoded data. Objects which can not be read are 
+    /// skipped, reporting [`Event::UnreadableObject`].
+    ///
+    /// Only the hashes of the canonical forms are kept in memory. Objects with equal hashes are 
+    /// compared in full by reading the first member of each candidate group again.
+    ///
+    /// Only groups of two or more objects are returned, each in increasing object number, ordered 
+    /// by their first member.
+    pub fn duplicate_objects(&self) -> Vec<Vec<ObjRef>> {
+        let mut buckets = HashMap::<u64, Vec<Vec<ObjRef>>>::new();
+        for (objref, res) in self.reader.objects() {
+            let bytes = match res.and_then(|obj| self.canonical_bytes(obj)) {
+                Ok(bytes) => bytes,
+                Err(error) => {
+                    self.reader.parser().diagnostics().report(Event::UnreadableObject { objref, error });
+                    continue;
+                }
+            };
+            let mut hasher = DefaultHasher::new();
+            hasher.write(&bytes);
+            let groups = buckets.entry(hasher.finish()).or_default();
+            let same = |group: &&mut Vec<ObjRef>| self.reader.resolve_ref(&group[0])
+                .and_then(|obj| self.canonical_bytes(obj))
+                .is_ok_and(|other| other == bytes);
+            match groups.iter_mut().find(same) {
+                Some(group) => group.push(objref),
+                None => groups.push(vec![objref])
+            }
+        }
+        let mut ret = buckets.into_values()
+            .flatten()
+            .filter(|group| group.len() > 1)
+            .collect::<Vec<_>>();
+        ret.sort();
+        ret
+    }
+
+    fn canonical_bytes(&self, obj: Object) -> Result<Vec<u8>, Error> {
+        let obj = obj.map_streams(&mut |stm| -> Result<_, Error> {
+            let mut data = Vec::new();
+            self.reader.read_stream_data(stm)?.read_to_end(&mut data)?;
+            Ok(data)
+        })?;
+        Ok(obj.canonical_bytes())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::hash::Hash;
+    use std::sync::Arc;
+    use crate::base::types::*;
+    use crate::utils::{self, Capture};
+
+    #[test]
+    fn test_duplicate_objects() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Type /FontDescriptor /FontName /Helvetica /Flags 32 /ItalicAngle 0 >>",
+            "<< /Type /FontDescriptor /FontName /Courier /Flags 32 /ItalicAngle 0 >>",
+            "<< /Type /FontDescriptor /FontName /Helvetica /Flags 32 /ItalicAngle 0.0 >>",
+            "<< /Length 4 >>\nstream\nabcd\nendstream",
+            "<< /Length 7 0 R >>\nstream\nabcd\nendstream",
+            "4",
+            "<< /Length 5 >>\nstream\nabcde\nendstream",
+            "<< /ItalicAngle 0 /Flags 32 /Type /FontDescriptor /FontName /Helvetica >>",
+            "<< /Length 4 /Filter /FlateDecode >>\nstream\nabcd\nendstream",
+        ]);
+        let capture = Arc::new(Capture::default());
+        let doc = Document::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let refs = |nums: &[ObjNum]| nums.iter().map(|&num| ObjRef { num, gen: 0 }).collect::<Vec<_>>();
+        assert_eq!(doc.duplicate_objects(), vec![refs(&[2, 9]), refs(&[5, 6])]);
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::UnreadableObject { objref: ObjRef { num: 10, gen: 0 }, .. }]));
+
+        let hash = |obj: &Object| {
+            let mut hasher = DefaultHasher::new();
+            obj.hash(&mut hasher);
+            hasher.finish()
+        };
+        let font2 = doc.reader().resolve_ref(&ObjRef { num: 2, gen: 0 }).unwrap();
+        let font3 = doc.reader().resolve_ref(&ObjRef { num: 3, gen: 0 }).unwrap();
+        let font4 = doc.reader().resolve_ref(&ObjRef { num: 4, gen: 0 }).unwrap();
+        let font9 = doc.reader().resolve_ref(&ObjRef { num: 9, gen: 0 }).unwrap();
+        assert_eq!(hash(&font2), hash(&font9));
+        assert_ne!(hash(&font2), hash(&font3));
+        assert_ne!(hash(&font2), hash(&font4));
+        assert_eq!(hash(&Object::Number(Number::Real(0.0))), hash(&Object::Number(Number::Real(-0.0))));
+        assert_ne!(hash(&Object::Number(Number::Int(0))), hash(&Object::Number(Number::Real(0.0))));
+    }
+}
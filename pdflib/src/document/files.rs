@@ -0,0 +1,94 @@
+use std::io::{BufRead, Seek, Read};
+
+use crate::base::*;
+
+use super::Document;
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Extracts the embedded files (attachments) of the document, returning their file names and 
+    /// decoded contents.
+    ///
+    /// Both the `/EmbeddedFiles` name tree of the document catalog and file attachment annotations 
+    /// on individual pages are searched, in this order. File specifications not containing an 
+    /// embedded file stream are skipped. If the catalog has no `/Pages`, only the name tree is 
+    /// searched.
+    pub fn attachments(&self) -> Result<Vec<(String, Vec<u8>)>, Error> {
+        let mut ret = Vec::new();
+        if let names @ (Object::Dict(_) | Object::Ref(_)) = self.catalog.lookup(b"Names") {
+            let names = self.reader.resolve_dict(names)?;
+            if let tree @ (Object::Dict(_) | Object::Ref(_)) = names.lookup(b"EmbeddedFiles") {
+                for (_, filespec) in self.name_tree(tree)? {
+                    ret.extend(self.read_filespec(&filespec)?);
+                }
+            }
+        }
+        if self.catalog.lookup(b"Pages") == &Object::Null {
+            return Ok(ret);
+        }
+        for page in self.pages()? {
+            for annot in page.annotations()? {
+                if annot.lookup(b"Subtype") == &Object::new_name(b"FileAttachment") {
+                    ret.extend(self.read_filespec(annot.lookup(b"FS"))?);
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    fn read_filespec(&self, filespec: &Object) -> Result<Option<(String, Vec<u8>)>, Error> {
+        let dict = match self.reader.resolve_obj(filespec.to_owned())? {
+            Object::Dict(dict) => dict,
+            // A plain string file specification refers to an external file.
+            _ => return Ok(None)
+        };
+        let name = [b"UF".as_slice(), b"F"].into_iter()
+            .find_map(|key| self.reader.resolve_obj(dict.lookup(key).to_owned()).ok()?.into_string())
+            .map(|name| decode_text_string(&name))
+            .unwrap_or_default();
+        let ef = match dict.lookup(b"EF") {
+            Object::Null => return Ok(None),
+            obj => self.reader.resolve_dict(obj)?
+        };
+        let stm = match (ef.lookup(b"UF"), ef.lookup(b"F")) {
+            (Object::Null, Object::Null) => return Ok(None),
+            (Object::Null, obj) | (obj, _) => self.reader.resolve_stream(obj)?
+        };
+        let mut data = Vec::new();
+        self.reader.read_stream_data(&stm)?.read_to_end(&mut data)?;
+        Ok(Some((name, data)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_attachments() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R /Names << /EmbeddedFiles << /Names [(a) 4 0 R (b) (external.txt)] >> >> >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /Annots [<< /Type /Annot /Subtype /FileAttachment /FS 6 0 R >>] >>",
+            "<< /Type /Filespec /F (hello.txt) /EF << /F 5 0 R >> >>",
+            "<< /Type /EmbeddedFile /Length 5 >>\nstream\nhello\nendstream",
+            "<< /Type /Filespec /F (x) /UF <FEFF00DF002E007400780074> /EF << /F 7 0 R >> >>",
+            "<< /Length 10 /Filter /ASCIIHexDecode >>\nstream\n776F726C64\nendstream",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.attachments().unwrap(), vec![
+            ("hello.txt".into(), b"hello".to_vec()),
+            ("\u{DF}.txt".into(), b"world".to_vec()),
+        ]);
+
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Names << /EmbeddedFiles << /Names [(a) 2 0 R] >> >> >>",
+            "<< /Type /Filespec /F (hello.txt) /EF << /F 3 0 R >> >>",
+            "<< /Type /EmbeddedFile /Length 5 >>\nstream\nhello\nendstream",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.attachments().unwrap(), vec![("hello.txt".into(), b"hello".to_vec())]);
+    }
+}
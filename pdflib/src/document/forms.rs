@@ -0,0 +1,111 @@
+use std::io::{BufRead, Seek};
+use std::collections::BTreeSet;
+
+use crate::base::*;
+
+use super::Document;
+
+/// A terminal field of an interactive form (AcroForm).
+#[derive(Debug, PartialEq, Clone)]
+pub struct FormField {
+    /// The fully qualified field name, i.e., the partial names (`/T`) of the field and all its 
+    /// ancestors, joined by periods.
+    pub name: String,
+    /// The field type (`/FT`), like `/Tx` or `/Btn`, possibly inherited from an ancestor.
+    pub field_type: Option<Name>,
+    /// The resolved field value (`/V`), possibly inherited from an ancestor. [`Object::Null`] if 
+    /// not present.
+    pub value: Object,
+}
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Lists the terminal fields of the interactive form, in the order of the field hierarchy.
+    ///
+    /// Returns an empty list if the document contains no `/AcroForm`.
+    pub fn form_fields(&self) -> Result<Vec<FormField>, Error> {
+        let acroform = match self.catalog.lookup(b"AcroForm") {
+            Object::Null => return Ok(vec![]),
+            obj => self.reader.resolve_dict(obj)?
+        };
+        let mut ret = Vec::new();
+        let mut visited = BTreeSet::new();
+        let root = FormField { name: String::new(), field_type: None, value: Object::Null };
+        for field in self.reader.resolve_array(acroform.lookup(b"Fields"))? {
+            self.collect_fields(&field, &root, &mut visited, &mut ret)?;
+        }
+        Ok(ret)
+    }
+
+    fn collect_fields(&self, obj: &Object, parent: &FormField, visited: &mut BTreeSet<ObjRef>,
+        out: &mut Vec<FormField>) -> Result<(), Error>
+    {
+        if let Object::Ref(objref) = obj {
+            if !visited.insert(*objref) {
+                log_warn!("Cycle detected in form field hierarchy at {objref}.");
+                return Ok(());
+            }
+        }
+        let dict = self.reader.resolve_dict(obj)?;
+        let name = match dict.lookup(b"T") {
+            Object::String(part) if parent.name.is_empty() => decode_text_string(part),
+            Object::String(part) => format!("{}.{}", parent.name, decode_text_string(part)),
+            _ => parent.name.clone()
+        };
+        let field_type = match dict.lookup(b"FT") {
+            Object::Null => parent.field_type.clone(),
+            obj => Some(self.reader.resolve_name(obj)?)
+        };
+        let value = match dict.lookup(b"V") {
+            Object::Null => parent.value.clone(),
+            obj => self.reader.resolve_obj(obj.to_owned())?
+        };
+        let field = FormField { name, field_type, value };
+        // Kids without /T are widget annotations of this field, not fields in their own right.
+        let kids = match dict.lookup(b"Kids") {
+            Object::Null => vec![],
+            obj => self.reader.resolve_array(obj)?
+        };
+        let mut has_child_fields = false;
+        for kid in kids {
+            if self.reader.resolve_dict(&kid)?.lookup(b"T") != &Object::Null {
+                has_child_fields = true;
+                self.collect_fields(&kid, &field, visited, out)?;
+            }
+        }
+        if !has_child_fields {
+            out.push(field);
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_form_fields() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /AcroForm 2 0 R >>",
+            "<< /Fields [3 0 R 4 0 R 5 0 R] >>",
+            "<< /FT /Tx /T (name) /V (John) >>",
+            "<< /FT /Btn /T (agree) /V /Yes /Kids [7 0 R] >>",
+            "<< /FT /Tx /T (addr) /V (unknown) /Kids [6 0 R] >>",
+            "<< /T (city) /Parent 5 0 R /V (Prague) >>",
+            "<< /Type /Annot /Subtype /Widget /Parent 4 0 R >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.form_fields().unwrap(), vec![
+            FormField { name: "name".into(), field_type: Some(Name::from(b"Tx")), value: Object::new_string(b"John") },
+            FormField { name: "agree".into(), field_type: Some(Name::from(b"Btn")), value: Object::new_name(b"Yes") },
+            FormField { name: "addr.city".into(), field_type: Some(Name::from(b"Tx")), value: Object::new_string(b"Prague") },
+        ]);
+
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.form_fields().unwrap(), vec![]);
+    }
+}
@@ -0,0 +1,108 @@
+use std::io::{BufRead, Seek};
+
+use crate::base::*;
+
+use super::Document;
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Formats the label of a page given by its 0-based index, according to the `/PageLabels` 
+    /// number tree of the document catalog.
+    ///
+    /// If the document defines no page labels, or the index is not covered by any label range, 
+    /// the 1-based page number is returned in decimal.
+    pub fn page_label(&self, index: usize) -> Result<String, Error> {
+        let default = || (index + 1).to_string();
+        let tree = match self.catalog.lookup(b"PageLabels") {
+            Object::Null => return Ok(default()),
+            obj => obj
+        };
+        let Some((start, label)) = self.number_tree(tree)?
+            .into_iter()
+            .filter(|&(start, _)| start >= 0 && start as usize <= index)
+            .max_by_key(|&(start, _)| start) else {
+                return Ok(default());
+            };
+        let label = self.reader.resolve_dict(&label)?;
+        let prefix = match self.reader.resolve_obj(label.lookup(b"P").to_owned())? {
+            Object::String(prefix) => decode_text_string(&prefix),
+            _ => String::new()
+        };
+        let first = self.reader.resolve_obj(label.lookup(b"St").to_owned())?
+            .num_value::<usize>()
+            .filter(|&st| st >= 1)
+            .unwrap_or(1);
+        let value = first + (index - start as usize);
+        let number = match self.reader.resolve_obj(label.lookup(b"S").to_owned())? {
+            Object::Null => String::new(),
+            Object::Name(style) => match style.as_slice() {
+                b"D" => value.to_string(),
+                b"R" => roman(value),
+                b"r" => roman(value).to_lowercase(),
+                b"A" => alpha(value),
+                b"a" => alpha(value).to_lowercase(),
+                _ => return Err(Error::Parse("malformed page label (/S)"))
+            },
+            _ => return Err(Error::Parse("malformed page label (/S)"))
+        };
+        Ok(prefix + &number)
+    }
+}
+
+fn roman(mut value: usize) -> String {
+    const DIGITS: [(usize, &str); 13] = [
+        (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"),
+        (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I")
+    ];
+    let mut ret = String::new();
+    for (step, digit) in DIGITS {
+        while value >= step {
+            ret.push_str(digit);
+            value -= step;
+        }
+    }
+    ret
+}
+
+/// A to Z for the first 26 values, AA to ZZ for the next 26, and so on.
+fn alpha(value: usize) -> String {
+    let letter = (b'A' + ((value - 1) % 26) as u8) as char;
+    std::iter::repeat_n(letter, (value - 1) / 26 + 1).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_page_label() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /PageLabels << /Nums [0 << /S /r >> 4 2 0 R 10 << /S /A /P (A-) /St 3 >>] >> >>",
+            "<< /S /D >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.page_label(0).unwrap(), "i");
+        assert_eq!(doc.page_label(3).unwrap(), "iv");
+        assert_eq!(doc.page_label(4).unwrap(), "1");
+        assert_eq!(doc.page_label(9).unwrap(), "6");
+        assert_eq!(doc.page_label(10).unwrap(), "A-C");
+        assert_eq!(doc.page_label(36).unwrap(), "A-CC");
+
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.page_label(0).unwrap(), "1");
+    }
+
+    #[test]
+    fn test_roman() {
+        assert_eq!(roman(1), "I");
+        assert_eq!(roman(4), "IV");
+        assert_eq!(roman(1994), "MCMXCIV");
+        assert_eq!(alpha(1), "A");
+        assert_eq!(alpha(26), "Z");
+        assert_eq!(alpha(27), "AA");
+        assert_eq!(alpha(53), "AAA");
+    }
+}
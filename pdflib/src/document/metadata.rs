@@ -0,0 +1,133 @@
+use std::io::{BufRead, Seek, Read};
+
+use crate::base::*;
+
+use super::Document;
+
+/// The contents of the document information dictionary (`/Info`), decoded.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub subject: Option<String>,
+    pub keywords: Option<String>,
+    pub creator: Option<String>,
+    pub producer: Option<String>,
+    pub creation_date: Option<PdfDate>,
+    pub mod_date: Option<PdfDate>,
+}
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Returns the document information dictionary (the `/Info` entry of the trailer), or `None` 
+    /// if there is none.
+    pub fn info(&self) -> Result<Option<Dict>, Error> {
+        match self.reader.xref.dict.lookup(b"Info") {
+            Object::Null => Ok(None),
+            obj => self.reader.resolve_dict(obj).map(Some)
+        }
+    }
+
+    /// Reads the document information dictionary into a [`Metadata`] structure. Text strings are 
+    /// decoded using [`decode_text_string()`] and dates are parsed as [`PdfDate`].
+    ///
+    /// Missing entries, as well as entries of a wrong type or malformed dates, are left as `None`. 
+    /// Malformed dates are also reported as [`Event::MalformedDate`]. If the document has no 
+    /// `/Info`, all the fields are `None`.
+    pub fn metadata(&self) -> Result<Metadata, Error> {
+        let Some(info) = self.info()? else { return Ok(Metadata::default()) };
+        let string = |key: &[u8]| -> Result<Option<Vec<u8>>, Error> {
+            match self.reader.resolve_obj(info.lookup(key).to_owned())? {
+                Object::String(s) => Ok(Some(s)),
+                _ => Ok(None)
+            }
+        };
+        let text = |key: &[u8]| -> Result<Option<String>, Error> {
+            Ok(string(key)?.map(|s| decode_text_string(&s)))
+        };
+        let date = |key: &[u8]| -> Result<Option<PdfDate>, Error> {
+            Ok(string(key)?.and_then(|s| match PdfDate::try_from(&s[..]) {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    self.reader.parser().diagnostics().report(Event::MalformedDate(Name::from(key)));
+                    None
+                }
+            }))
+        };
+        Ok(Metadata {
+            title: text(b"Title")?,
+            author: text(b"Author")?,
+            subject: text(b"Subject")?,
+            keywords: text(b"Keywords")?,
+            creator: text(b"Creator")?,
+            producer: text(b"Producer")?,
+            creation_date: date(b"CreationDate")?,
+            mod_date: date(b"ModDate")?,
+        })
+    }
+
+    /// Returns the decoded contents of the XMP metadata stream referenced from the document 
+    /// catalog (`/Metadata`), or `None` if there is none.
+    ///
+    /// The XML packet is not parsed in any way.
+    pub fn xmp_metadata(&self) -> Result<Option<Vec<u8>>, Error> {
+        let stm = match self.catalog.lookup(b"Metadata") {
+            Object::Null => return Ok(None),
+            obj => self.reader.resolve_stream(obj)?
+        };
+        if stm.dict.lookup(b"Subtype") != &Object::new_name(b"XML") {
+            return Err(Error::Parse("malformed metadata stream (/Subtype)"));
+        }
+        let mut data = Vec::new();
+        self.reader.read_stream_data(&stm)?.read_to_end(&mut data)?;
+        Ok(Some(data))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use crate::utils::{self, Capture};
+
+    #[test]
+    fn test_xmp_metadata() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Metadata 2 0 R >>",
+            "<< /Type /Metadata /Subtype /XML /Length 23 >>\nstream\n<x:xmpmeta></x:xmpmeta>\nendstream",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.xmp_metadata().unwrap(), Some(b"<x:xmpmeta></x:xmpmeta>".to_vec()));
+
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.xmp_metadata().unwrap(), None);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let data = utils::make_pdf_with_trailer(&[
+            "<< /Type /Catalog >>",
+            "<< /Title <FEFF0054006900740072006500A7> /Author 3 0 R /Producer (pdflib) /Keywords 42 \
+                /CreationDate (D:20240131120000Z) /ModDate (yesterday) >>",
+            "(Jane Doe)",
+        ], "/Info 2 0 R");
+        let capture = Arc::new(Capture::default());
+        let doc = Document::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let meta = doc.metadata().unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Titre\u{A7}"));
+        assert_eq!(meta.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(meta.producer.as_deref(), Some("pdflib"));
+        assert_eq!(meta.subject, None);
+        assert_eq!(meta.keywords, None);
+        assert_eq!(meta.creation_date.unwrap().to_string(), "2024-01-31T12:00:00Z");
+        assert_eq!(meta.mod_date, None);
+        assert!(matches!(&capture.0.lock().unwrap()[..], [Event::MalformedDate(key)] if key == b"ModDate"));
+
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.info().unwrap(), None);
+        assert_eq!(doc.metadata().unwrap(), Metadata::default());
+    }
+}
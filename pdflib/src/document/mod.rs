@@ -0,0 +1,82 @@
+use std::io::{BufRead, Seek};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::base::*;
+use crate::reader::SimpleReader;
+
+mod forms;
+pub use forms::*;
+
+mod tree;
+
+mod pages;
+pub use pages::*;
+
+mod files;
+
+mod outline;
+pub use outline::*;
+
+mod dests;
+
+mod labels;
+
+mod metadata;
+pub use metadata::*;
+
+mod signatures;
+pub use signatures::*;
+
+mod structure;
+
+mod validate;
+pub use validate::*;
+
+mod duplicates;
+
+mod text;
+
+/// A high-level view of a PDF document.
+///
+/// `Document` wraps a [`SimpleReader`] and gives access to the document-level structures reachable 
+/// from the document catalog (the `/Root` entry of the trailer).
+pub struct Document<T: BufRead + Seek> {
+    reader: SimpleReader<T>,
+    catalog: Dict,
+    /// The number of pages under page tree nodes lacking `/Count`, as computed by 
+    /// [`Document::find_page()`].
+    page_counts: RefCell<BTreeMap<ObjRef, usize>>,
+}
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Creates a `Document` instance around a `BufRead + Seek` source.
+    ///
+    /// Returns with an error if the cross-reference table is not found or damaged, or if the 
+    /// document catalog can not be read.
+    pub fn new(source: T) -> Result<Self, Error> {
+        Self::with_reader(SimpleReader::new(source)?)
+    }
+
+    /// Like [`Document::new()`], but reports recoverable problems to the given [`Diagnostics`] 
+    /// sink instead of the `log` crate.
+    pub fn with_diagnostics(source: T, diag: Arc<dyn Diagnostics>) -> Result<Self, Error> {
+        Self::with_reader(SimpleReader::with_diagnostics(source, diag)?)
+    }
+
+    fn with_reader(reader: SimpleReader<T>) -> Result<Self, Error> {
+        let catalog = reader.resolve_dict(reader.xref.dict.lookup(b"Root"))?;
+        Ok(Self { reader, catalog, page_counts: Default::default() })
+    }
+
+    /// The underlying [`SimpleReader`].
+    pub fn reader(&self) -> &SimpleReader<T> {
+        &self.reader
+    }
+
+    /// The document catalog dictionary.
+    pub fn catalog(&self) -> &Dict {
+        &self.catalog
+    }
+}
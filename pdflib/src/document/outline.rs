@@ -0,0 +1,101 @@
+use std::io::{BufRead, Seek};
+use std::collections::BTreeSet;
+
+use crate::base::*;
+
+use super::Document;
+
+/// An item of the document outline (bookmark).
+#[derive(Debug, PartialEq, Clone)]
+pub struct OutlineItem {
+    /// The decoded `/Title`.
+    pub title: String,
+    /// The destination: the `/Dest` entry, or the `/D` entry of a `/GoTo` action (`/A`). This can 
+    /// be an explicit destination (an array) or a named destination (a name or a string). 
+    /// [`Object::Null`] if none is present.
+    pub dest: Object,
+    /// The child items.
+    pub children: Vec<OutlineItem>,
+}
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Builds the document outline tree from the `/Outlines` dictionary of the document catalog.
+    /// Returns the top-level items.
+    ///
+    /// Returns an empty list if the document has no outline. Malformed cyclic links are detected 
+    /// and broken with a warning.
+    pub fn outline(&self) -> Result<Vec<OutlineItem>, Error> {
+        let outlines = match self.catalog.lookup(b"Outlines") {
+            Object::Null => return Ok(vec![]),
+            obj => self.reader.resolve_dict(obj)?
+        };
+        self.read_outline_level(outlines.lookup(b"First"), &mut BTreeSet::new())
+    }
+
+    fn read_outline_level(&self, first: &Object, visited: &mut BTreeSet<ObjRef>) -> Result<Vec<OutlineItem>, Error> {
+        let mut ret = Vec::new();
+        let mut next = first.to_owned();
+        while let Object::Ref(objref) = next {
+            if !visited.insert(objref) {
+                log_warn!("Cycle detected in document outline at {objref}.");
+                break;
+            }
+            let dict = self.reader.resolve_dict(&next)?;
+            let title = self.reader.resolve_obj(dict.lookup(b"Title").to_owned())?
+                .into_string()
+                .map(|title| decode_text_string(&title))
+                .unwrap_or_default();
+            let dest = match (dict.lookup(b"Dest"), dict.lookup(b"A")) {
+                (Object::Null, Object::Null) => Object::Null,
+                (Object::Null, action) => {
+                    let action = self.reader.resolve_dict(action)?;
+                    if action.lookup(b"S") == &Object::new_name(b"GoTo") {
+                        self.reader.resolve_obj(action.lookup(b"D").to_owned())?
+                    } else {
+                        Object::Null
+                    }
+                },
+                (dest, _) => self.reader.resolve_obj(dest.to_owned())?
+            };
+            let children = self.read_outline_level(dict.lookup(b"First"), visited)?;
+            ret.push(OutlineItem { title, dest, children });
+            next = dict.lookup(b"Next").to_owned();
+        }
+        Ok(ret)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_outline() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Outlines 2 0 R >>",
+            "<< /Type /Outlines /First 3 0 R /Last 4 0 R /Count 3 >>",
+            "<< /Title (Chapter 1) /Parent 2 0 R /Next 4 0 R /First 5 0 R /Last 5 0 R /Dest (ch1) >>",
+            "<< /Title <FEFF0043006800200032> /Parent 2 0 R /Prev 3 0 R /A << /S /GoTo /D [6 0 R /Fit] >> >>",
+            "<< /Title (Section 1.1) /Parent 3 0 R /Next 3 0 R >>",
+            "<< /Type /Page >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.outline().unwrap(), vec![
+            OutlineItem {
+                title: "Chapter 1".into(),
+                dest: Object::new_string(b"ch1"),
+                children: vec![
+                    OutlineItem { title: "Section 1.1".into(), dest: Object::Null, children: vec![] }
+                ]
+            },
+            OutlineItem {
+                title: "Ch 2".into(),
+                dest: Object::Array(vec![Object::Ref(ObjRef { num: 6, gen: 0 }), Object::new_name(b"Fit")]),
+                children: vec![]
+            },
+        ]);
+    }
+}
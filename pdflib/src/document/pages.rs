@@ -0,0 +1,406 @@
+use std::io::{BufRead, Seek, Read};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::base::*;
+
+use super::Document;
+use super::validate::collect_refs;
+
+/// The maximum depth of the page tree descended when counting pages.
+pub(super) const MAX_TREE_DEPTH: usize = 256;
+
+/// A single page of a [`Document`].
+pub struct Page<'a, T: BufRead + Seek> {
+    pub(super) doc: &'a Document<T>,
+    /// The reference to the page object.
+    pub objref: ObjRef,
+    /// The page dictionary.
+    pub dict: Dict,
+}
+
+impl<T: BufRead + Seek> Page<'_, T> {
+    /// Lists the annotations of this page (the `/Annots` entry), each resolved into its 
+    /// dictionary.
+    pub fn annotations(&self) -> Result<Vec<Dict>, Error> {
+        match self.dict.lookup(b"Annots") {
+            Object::Null => Ok(vec![]),
+            obj => self.doc.reader.resolve_array(obj)?
+                .iter()
+                .map(|annot| self.doc.reader.resolve_dict(annot))
+                .collect()
+        }
+    }
+
+    /// Lists the external hyperlinks on this page: the `/Rect` of each `/Link` annotation whose 
+    /// action is a `/URI` action, together with the target URI.
+    ///
+    /// Links to destinations within the document (`/GoTo` actions or `/Dest` entries) are skipped.
+    pub fn links(&self) -> Result<Vec<(Rectangle, String)>, Error> {
+        let mut ret = Vec::new();
+        for annot in self.annotations()? {
+            if annot.lookup(b"Subtype") != &Object::new_name(b"Link") {
+                continue;
+            }
+            let action = match annot.lookup(b"A") {
+                Object::Null => continue,
+                obj => self.doc.reader.resolve_dict(obj)?
+            };
+            if action.lookup(b"S") != &Object::new_name(b"URI") {
+                continue;
+            }
+            let rect = Rectangle::try_from(&self.doc.reader.resolve_obj(annot.lookup(b"Rect").to_owned())?)?;
+            let uri = self.doc.reader.resolve_obj(action.lookup(b"URI").to_owned())?
+                .into_string()
+                .ok_or(Error::Parse("malformed URI action"))?;
+            ret.push((rect, String::from_utf8_lossy(&uri).into_owned()));
+        }
+        Ok(ret)
+    }
+
+    /// Reads the content of this page: the data of the `/Contents` stream, or of all the streams 
+    /// in the `/Contents` array joined by newlines, after decoding.
+    pub fn contents(&self) -> Result<Vec<u8>, Error> {
+        let streams = match self.doc.reader.resolve_obj(self.dict.lookup(b"Contents").to_owned())? {
+            Object::Null => vec![],
+            Object::Stream(stm) => vec![stm],
+            Object::Array(arr) => arr.iter()
+                .map(|obj| self.doc.reader.resolve_stream(obj))
+                .collect::<Result<_, _>>()?,
+            _ => return Err(Error::Parse("malformed /Contents"))
+        };
+        let mut data = Vec::new();
+        for stm in streams {
+            if !data.is_empty() {
+                data.push(b'\n');
+            }
+            self.doc.reader.read_stream_data(&stm)?.read_to_end(&mut data)?;
+        }
+        Ok(data)
+    }
+
+    /// The resource dictionary of this page, which may be inherited from an ancestor node in the 
+    /// page tree. If none is found, an empty dictionary is returned.
+    pub fn resources(&self) -> Result<Dict, Error> {
+        match self.resources_entry()? {
+            Object::Null => Ok(Dict::default()),
+            obj => self.doc.reader.resolve_dict(&obj)
+        }
+    }
+
+    /// The `/Resources` entry of this page or of its nearest ancestor which has one, unresolved.
+    fn resources_entry(&self) -> Result<Object, Error> {
+        let mut dict = self.dict.clone();
+        let mut visited = BTreeSet::from([self.objref]);
+        loop {
+            match dict.lookup(b"Resources") {
+                Object::Null => (),
+                obj => return Ok(obj.clone())
+            }
+            let Some(&parent) = dict.lookup(b"Parent").as_objref() else {
+                return Ok(Object::Null);
+            };
+            if !visited.insert(parent) {
+                log_warn!("Cycle detected in page tree at {parent}.");
+                return Ok(Object::Null);
+            }
+            dict = self.doc.reader.resolve_dict(&Object::Ref(parent))?;
+        }
+    }
+
+    /// Collects the indirect objects reachable from the page dictionary, like its contents, 
+    /// resources and annotations.
+    ///
+    /// The traversal is bounded to this page: the `/Parent` entry is not followed, and neither 
+    /// are references to other page objects or page tree nodes (e.g. from link destinations). 
+    /// Resources inherited from an ancestor node are included, though. References to missing 
+    /// objects are skipped, objects which can not be read are skipped and reported as 
+    /// [`Event::UnreadableObject`].
+    pub fn reachable_objects(&self) -> Result<BTreeSet<ObjRef>, Error> {
+        let mut queue = VecDeque::new();
+        for (key, obj) in self.dict.iter() {
+            if key != b"Parent" && key != b"Resources" {
+                collect_refs(obj, &mut queue);
+            }
+        }
+        collect_refs(&self.resources_entry()?, &mut queue);
+        let mut ret = BTreeSet::new();
+        let mut visited = BTreeSet::from([self.objref]);
+        while let Some(objref) = queue.pop_front() {
+            if !visited.insert(objref) {
+                continue;
+            }
+            let obj = match self.doc.reader.resolve_ref_opt(&objref) {
+                Ok(Some(obj)) => obj,
+                Ok(None) => continue,
+                Err(error) => {
+                    self.doc.reader.parser().diagnostics().report(Event::UnreadableObject { objref, error });
+                    continue;
+                }
+            };
+            if obj.as_dict().is_some_and(|dict| dict.has_type(b"Page") || dict.has_type(b"Pages")) {
+                continue;
+            }
+            ret.insert(objref);
+            collect_refs(&obj, &mut queue);
+        }
+        Ok(ret)
+    }
+}
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Lists all pages of the document, in order, by traversing the page tree.
+    ///
+    /// Nodes appearing more than once in the tree are skipped with a warning.
+    pub fn pages(&self) -> Result<Vec<Page<'_, T>>, Error> {
+        let root = self.catalog.lookup(b"Pages")
+            .as_objref()
+            .ok_or(Error::Parse("malformed page tree (/Pages)"))?;
+        let mut ret = Vec::new();
+        self.collect_pages(*root, &mut BTreeSet::new(), &mut ret)?;
+        Ok(ret)
+    }
+
+    /// Maps each object to the (zero-based) indices of the pages it is reachable from, as given 
+    /// by [`Page::reachable_objects()`]. Shared resources map to multiple pages.
+    pub fn object_to_pages(&self) -> Result<BTreeMap<ObjRef, Vec<usize>>, Error> {
+        let mut ret = BTreeMap::<ObjRef, Vec<usize>>::new();
+        for (index, page) in self.pages()?.iter().enumerate() {
+            for objref in page.reachable_objects()? {
+                ret.entry(objref).or_default().push(index);
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Finds the page with the given (zero-based) index by descending the page tree, skipping 
+    /// subtrees according to their `/Count`.
+    ///
+    /// Intermediate nodes lacking `/Count` are tolerated, reporting [`Event::MissingPageCount`]: 
+    /// the number of pages under them is computed by traversing their descendants, up to 256 
+    /// levels deep, and remembered for later calls.
+    pub fn find_page(&self, mut index: usize) -> Result<Page<'_, T>, Error> {
+        let mut objref = *self.catalog.lookup(b"Pages")
+            .as_objref()
+            .ok_or(Error::Parse("malformed page tree (/Pages)"))?;
+        let mut visited = BTreeSet::new();
+        'a: loop {
+            if !visited.insert(objref) {
+                return Err(Error::Parse("cycle in page tree"));
+            }
+            let dict = self.reader.resolve_dict(&Object::Ref(objref))?;
+            if dict.has_type(b"Page") {
+                if index != 0 {
+                    return Err(Error::Parse("page index out of range"));
+                }
+                return Ok(Page { doc: self, objref, dict });
+            } else if !dict.has_type(b"Pages") {
+                return Err(Error::Parse("malformed page tree (/Type)"));
+            }
+            for kid in self.reader.resolve_array(dict.lookup(b"Kids"))? {
+                let kid = *kid.as_objref().ok_or(Error::Parse("malformed page tree (/Kids)"))?;
+                let count = self.page_count(kid, &mut BTreeSet::new(), MAX_TREE_DEPTH)?;
+                if index < count {
+                    objref = kid;
+                    continue 'a;
+                }
+                index -= count;
+            }
+            return Err(Error::Parse("page index out of range"));
+        }
+    }
+
+    /// The number of pages under a page tree node: its `/Count`, or if missing, the sum over its 
+    /// kids.
+    fn page_count(&self, objref: ObjRef, visited: &mut BTreeSet<ObjRef>, depth: usize) -> Result<usize, Error> {
+        if let Some(&count) = self.page_counts.borrow().get(&objref) {
+            return Ok(count);
+        }
+        if !visited.insert(objref) {
+            return Err(Error::Parse("cycle in page tree"));
+        }
+        let dict = self.reader.resolve_dict(&Object::Ref(objref))?;
+        if dict.has_type(b"Page") {
+            return Ok(1);
+        } else if !dict.has_type(b"Pages") {
+            return Err(Error::Parse("malformed page tree (/Type)"));
+        }
+        match self.reader.resolve_obj(dict.lookup(b"Count").to_owned())? {
+            Object::Null => (),
+            obj => return obj.num_value().ok_or(Error::Parse("malformed page tree (/Count)"))
+        }
+        self.reader.parser().diagnostics().report(Event::MissingPageCount(objref));
+        let depth = depth.checked_sub(1).ok_or(Error::Parse("page tree too deep"))?;
+        let mut count = 0usize;
+        for kid in self.reader.resolve_array(dict.lookup(b"Kids"))? {
+            let kid = *kid.as_objref().ok_or(Error::Parse("malformed page tree (/Kids)"))?;
+            count = count.checked_add(self.page_count(kid, visited, depth)?)
+                .ok_or(Error::Parse("malformed page tree (/Count)"))?;
+        }
+        self.page_counts.borrow_mut().insert(objref, count);
+        Ok(count)
+    }
+
+    fn collect_pages<'a>(&'a self, objref: ObjRef, visited: &mut BTreeSet<ObjRef>,
+        out: &mut Vec<Page<'a, T>>) -> Result<(), Error>
+    {
+        if !visited.insert(objref) {
+            log_warn!("Cycle detected in page tree at {objref}.");
+            return Ok(());
+        }
+        let dict = self.reader.resolve_dict(&Object::Ref(objref))?;
+        if dict.has_type(b"Page") {
+            out.push(Page { doc: self, objref, dict });
+        } else if dict.has_type(b"Pages") {
+            for kid in self.reader.resolve_array(dict.lookup(b"Kids"))? {
+                let kid = kid.as_objref().ok_or(Error::Parse("malformed page tree (/Kids)"))?;
+                self.collect_pages(*kid, visited, out)?;
+            }
+        } else {
+            return Err(Error::Parse("malformed page tree (/Type)"));
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use crate::utils::{self, Capture};
+
+    #[test]
+    fn test_annotations() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>",
+            "<< /Type /Page /Parent 2 0 R /Annots 5 0 R >>",
+            "<< /Type /Page /Parent 2 0 R >>",
+            "[6 0 R << /Type /Annot /Subtype /Text >>]",
+            "<< /Type /Annot /Subtype /Link /Rect [0 0 10 10] >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let pages = doc.pages().unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].objref, ObjRef { num: 3, gen: 0 });
+        let annots = pages[0].annotations().unwrap();
+        assert_eq!(annots.len(), 2);
+        assert_eq!(annots[0].lookup(b"Subtype"), &Object::new_name(b"Link"));
+        assert_eq!(annots[1].lookup(b"Subtype"), &Object::new_name(b"Text"));
+        assert!(pages[1].annotations().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_links() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /Annots [4 0 R 5 0 R] >>",
+            "<< /Type /Annot /Subtype /Link /Rect [72 700 144 686] /A << /S /URI /URI (https://example.com/) >> >>",
+            "<< /Type /Annot /Subtype /Link /Rect [0 0 10 10] /A << /S /GoTo /D [3 0 R /Fit] >> >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let pages = doc.pages().unwrap();
+        assert_eq!(pages[0].links().unwrap(), vec![
+            (Rectangle { llx: 72., lly: 686., urx: 144., ury: 700. }, "https://example.com/".into())
+        ]);
+    }
+
+    #[test]
+    fn test_find_page() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R 4 0 R 7 0 R] /Count 4 >>",
+            "<< /Type /Page /Parent 2 0 R >>",
+            "<< /Type /Pages /Parent 2 0 R /Kids [5 0 R 6 0 R] >>",
+            "<< /Type /Page /Parent 4 0 R >>",
+            "<< /Type /Page /Parent 4 0 R >>",
+            "<< /Type /Page /Parent 2 0 R >>",
+        ]);
+        let capture = Arc::new(Capture::default());
+        let doc = Document::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let num = |index| doc.find_page(index).unwrap().objref.num;
+        assert_eq!(num(0), 3);
+        assert_eq!(num(1), 5);
+        assert_eq!(num(2), 6);
+        assert_eq!(num(3), 7);
+        assert_eq!(doc.page_counts.borrow().get(&ObjRef { num: 4, gen: 0 }), Some(&2));
+        assert!(doc.find_page(4).is_err());
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::MissingPageCount(ObjRef { num: 4, gen: 0 })]));
+    }
+
+    #[test]
+    fn test_find_page_deep() {
+        // A chain of nodes lacking /Count, deeper than MAX_TREE_DEPTH.
+        let mut objects = vec!["<< /Type /Catalog /Pages 2 0 R >>".to_owned()];
+        let len = MAX_TREE_DEPTH + 10;
+        for num in 2..len {
+            objects.push(format!("<< /Type /Pages /Kids [{} 0 R] >>", num + 1));
+        }
+        objects.push("<< /Type /Page >>".to_owned());
+        let data = utils::make_pdf(&objects.iter().map(String::as_str).collect::<Vec<_>>());
+        let capture = Arc::new(Capture::default());
+        let doc = Document::with_diagnostics(Cursor::new(data), capture).unwrap();
+        assert!(matches!(doc.find_page(0), Err(Error::Parse("page tree too deep"))));
+    }
+
+    #[test]
+    fn test_find_page_count_overflow() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R 5 0 R] /Count 1 >>",
+            "<< /Type /Pages /Kids [4 0 R 6 0 R 7 0 R] >>",
+            "<< /Type /Pages /Kids [] /Count 9223372036854775807 >>",
+            "<< /Type /Page >>",
+            "<< /Type /Pages /Kids [] /Count 9223372036854775807 >>",
+            "<< /Type /Pages /Kids [] /Count 9223372036854775807 >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert!(matches!(doc.find_page(0), Err(Error::Parse("malformed page tree (/Count)"))));
+    }
+
+    #[test]
+    fn test_object_to_pages() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 >>",
+            "<< /Type /Page /Parent 2 0 R /Resources << /Font << /F1 5 0 R >> >> /Contents 6 0 R >>",
+            "<< /Type /Page /Parent 2 0 R /Resources 7 0 R /Annots [8 0 R] >>",
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>",
+            "<< /Length 0 >>\nstream\n\nendstream",
+            "<< /Font << /F1 5 0 R >> >>",
+            "<< /Type /Annot /Subtype /Link /Rect [0 0 10 10] /Dest [3 0 R /Fit] /P 4 0 R >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let map = doc.object_to_pages().unwrap();
+        let oref = |num| ObjRef { num, gen: 0 };
+        assert_eq!(map, BTreeMap::from([
+            (oref(5), vec![0, 1]),
+            (oref(6), vec![0]),
+            (oref(7), vec![1]),
+            (oref(8), vec![1]),
+        ]));
+    }
+
+    #[test]
+    fn test_reachable_inherited() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 /Resources << /Font << /F1 5 0 R >> >> >>",
+            "<< /Type /Page /Parent 2 0 R /Contents [6 0 R 7 0 R] >>",
+            "<< /Type /Page /Parent 2 0 R /Resources << >> >>",
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>",
+            "<< /Length 0 >>\nstream\n\nendstream",
+            "<< /Length ]] >>",
+        ]);
+        let capture = Arc::new(Capture::default());
+        let doc = Document::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let oref = |num| ObjRef { num, gen: 0 };
+        let pages = doc.pages().unwrap();
+        assert_eq!(pages[0].reachable_objects().unwrap(), BTreeSet::from([oref(5), oref(6)]));
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::UnreadableObject { objref: ObjRef { num: 7, gen: 0 }, .. }]));
+        // The page's own /Resources take precedence.
+        assert!(pages[1].reachable_objects().unwrap().is_empty());
+    }
+}
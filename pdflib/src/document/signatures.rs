@@ -0,0 +1,104 @@
+use std::io::{BufRead, Seek};
+
+use crate::base::*;
+use crate::base::types::*;
+
+use super::Document;
+
+/// The byte ranges covered by a digital signature, as pairs of offset and length.
+pub type ByteRange = Vec<(Offset, usize)>;
+
+impl<T: BufRead + Seek> Document<T> {
+    /// For each signature field of the interactive form, returns the byte ranges covered by the 
+    /// signature (as pairs of file offsets and lengths, from the `/ByteRange` entry) and the raw 
+    /// contents of the signature (the `/Contents` entry, e.g., a DER-encoded PKCS#7 blob).
+    ///
+    /// The byte ranges are validated to cover the entire file except for the hex string holding 
+    /// the `/Contents`. No cryptographic verification is performed. Signature fields which have 
+    /// not been signed yet are skipped.
+    ///
+    /// NB that unlike most offsets in this library, these are absolute file offsets, not relative 
+    /// to `%PDF`.
+    pub fn signature_byte_ranges(&self) -> Result<Vec<(ByteRange, Vec<u8>)>, Error> {
+        let file_len = self.reader.parser().file_len()?;
+        let mut ret = Vec::new();
+        for field in self.form_fields()? {
+            if field.field_type.as_ref().is_none_or(|tpe| tpe != b"Sig") {
+                continue;
+            }
+            let sig = match field.value {
+                Object::Dict(dict) => dict,
+                Object::Null => continue,
+                _ => return Err(Error::Parse("malformed signature field (/V)"))
+            };
+            let err = || Error::Parse("malformed signature (/ByteRange)");
+            let ranges = self.reader.resolve_array(sig.lookup(b"ByteRange"))?
+                .iter()
+                .map(|obj| obj.num_value::<u64>().ok_or_else(err))
+                .collect::<Result<Vec<_>, _>>()?
+                .chunks(2)
+                .map(|pair| match *pair {
+                    [start, len] => Ok((start, len.try_into().map_err(|_| err())?)),
+                    _ => Err(err())
+                })
+                .collect::<Result<ByteRange, _>>()?;
+            let contents = self.reader.resolve_obj(sig.lookup(b"Contents").to_owned())?
+                .into_string()
+                .ok_or(Error::Parse("malformed signature (/Contents)"))?;
+            let mut pos = 0;
+            let mut hole = 0;
+            for &(start, len) in &ranges {
+                if start < pos || (pos > 0 && start == pos) {
+                    return Err(Error::Parse("signature /ByteRange not contiguous"));
+                }
+                hole += start - pos;
+                pos = start + len as u64;
+            }
+            if ranges.first().is_none_or(|&(start, _)| start != 0) || pos != file_len {
+                return Err(Error::Parse("signature /ByteRange does not cover the file"));
+            }
+            // The hole is the /Contents hex string, including the angle brackets.
+            if hole != 2 * contents.len() as u64 + 2 {
+                return Err(Error::Parse("signature /ByteRange does not match /Contents"));
+            }
+            ret.push((ranges, contents));
+        }
+        Ok(ret)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_signature_byte_ranges() {
+        const PLACEHOLDER: &[u8] = b"0000000000 0000000000 0000000000 0000000000";
+        let mut data = utils::make_pdf(&[
+            "<< /Type /Catalog /AcroForm << /Fields [2 0 R 4 0 R] /SigFlags 3 >> >>",
+            "<< /FT /Sig /T (Signature1) /V 3 0 R >>",
+            "<< /Type /Sig /Filter /Adobe.PPKLite /SubFilter /adbe.pkcs7.detached \
+                /ByteRange [0000000000 0000000000 0000000000 0000000000] /Contents <0102030400000000> >>",
+            "<< /FT /Sig /T (Signature2) >>",
+        ]);
+        let find = |data: &[u8], what: &[u8]| data.windows(what.len()).position(|w| w == what).unwrap();
+        let hole_start = find(&data, b"<01020304");
+        let hole_end = hole_start + 18;
+        let ranges = format!("{:010} {:010} {:010} {:010}", 0, hole_start, hole_end, data.len() - hole_end);
+        let pos = find(&data, PLACEHOLDER);
+        data[pos..(pos + PLACEHOLDER.len())].copy_from_slice(ranges.as_bytes());
+
+        let doc = Document::new(Cursor::new(data.clone())).unwrap();
+        assert_eq!(doc.signature_byte_ranges().unwrap(), vec![
+            (vec![(0, hole_start), (hole_end as Offset, data.len() - hole_end)], vec![1, 2, 3, 4, 0, 0, 0, 0])
+        ]);
+
+        // Appending data invalidates the coverage
+        data.extend_from_slice(b"\n");
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert!(doc.signature_byte_ranges().is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use std::io::{BufRead, Seek};
+
+use crate::base::*;
+
+use super::Document;
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Returns the root of the structure tree (`/StructTreeRoot` in the document catalog), or 
+    /// `None` if there is none.
+    pub fn struct_tree_root(&self) -> Result<Option<Dict>, Error> {
+        match self.catalog.lookup(b"StructTreeRoot") {
+            Object::Null => Ok(None),
+            obj => self.reader.resolve_dict(obj).map(Some)
+        }
+    }
+
+    /// Checks whether this is a tagged PDF, i.e., the document catalog contains a structure tree 
+    /// and its `/MarkInfo` dictionary has `/Marked true`.
+    pub fn is_tagged(&self) -> Result<bool, Error> {
+        let marked = match self.catalog.lookup(b"MarkInfo") {
+            Object::Null => false,
+            obj => {
+                let mark_info = self.reader.resolve_dict(obj)?;
+                self.reader.resolve_obj(mark_info.lookup(b"Marked").to_owned())? == Object::Bool(true)
+            }
+        };
+        Ok(marked && self.struct_tree_root()?.is_some())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_is_tagged() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /MarkInfo << /Marked true >> /StructTreeRoot 2 0 R >>",
+            "<< /Type /StructTreeRoot /K [] >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert!(doc.is_tagged().unwrap());
+        assert!(doc.struct_tree_root().unwrap().unwrap().has_type(b"StructTreeRoot"));
+
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert!(!doc.is_tagged().unwrap());
+        assert_eq!(doc.struct_tree_root().unwrap(), None);
+
+        // Structure tree present, but not marked
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /MarkInfo 3 0 R /StructTreeRoot 2 0 R >>",
+            "<< /Type /StructTreeRoot >>",
+            "<< /Marked false >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert!(!doc.is_tagged().unwrap());
+
+        // Marked, but no structure tree
+        let data = utils::make_pdf(&["<< /Type /Catalog /MarkInfo << /Marked true >> >>"]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert!(!doc.is_tagged().unwrap());
+    }
+}
@@ -0,0 +1,313 @@
+use std::io::{BufRead, Seek, Read};
+use std::collections::BTreeMap;
+
+use crate::base::*;
+use crate::content::{ContentParser, GraphicsStateIter};
+use crate::cmap::ToUnicode;
+use crate::fonts::Encoding;
+use crate::fonts::standard14::Standard14;
+
+use super::Page;
+
+/// Glyph width, in thousandths of text space units, assumed when the font does not provide one.
+const DEFAULT_WIDTH: f64 = 500.;
+
+/// The properties of a font needed for text extraction.
+struct FontInfo {
+    first_char: u32,
+    widths: Vec<f64>,
+    to_unicode: Option<ToUnicode>,
+}
+
+impl FontInfo {
+    fn new<T: BufRead + Seek>(page: &Page<'_, T>, dict: &Dict) -> Result<FontInfo, Error> {
+        let reader = &page.doc.reader;
+        let first_char = reader.resolve_obj(dict.lookup(b"FirstChar").to_owned())?
+            .num_value()
+            .unwrap_or(0);
+        let widths = match dict.lookup(b"Widths") {
+            Object::Null => return Self::new_standard14(page, dict),
+            obj => reader.resolve_array(obj)?
+                .into_iter()
+                .map(|obj| match reader.resolve_obj(obj)? {
+                    Object::Number(num) => Ok(num.as_f64()),
+                    _ => Err(Error::Parse("malformed /Widths"))
+                })
+                .collect::<Result<_, _>>()?
+        };
+        Ok(FontInfo { first_char, widths, to_unicode: Self::to_unicode(page, dict)? })
+    }
+
+    /// For fonts without `/Widths`, takes the widths from the metrics of the standard 14 fonts 
+    /// according to `/BaseFont`, and the encoding given by `/Encoding`.
+    fn new_standard14<T: BufRead + Seek>(page: &Page<'_, T>, dict: &Dict) -> Result<FontInfo, Error> {
+        let reader = &page.doc.reader;
+        let to_unicode = Self::to_unicode(page, dict)?;
+        let Some(font) = dict.lookup(b"BaseFont").as_name().and_then(|name| Standard14::from_name(name.as_slice())) else {
+            return Ok(FontInfo { first_char: 0, widths: vec![], to_unicode });
+        };
+        let (encoding, differences) = match reader.resolve_obj(dict.lookup(b"Encoding").to_owned())? {
+            Object::Name(name) => (Encoding::from_name(name.as_slice()), vec![]),
+            Object::Dict(enc) => {
+                let base = enc.lookup(b"BaseEncoding").as_name().and_then(|name| Encoding::from_name(name.as_slice()));
+                let differences = match enc.lookup(b"Differences") {
+                    Object::Null => vec![],
+                    obj => reader.resolve_array(obj)?
+                };
+                (base, differences)
+            },
+            _ => (None, vec![])
+        };
+        let mut widths = (0..=255)
+            .map(|code| font.code_width(code, encoding).map_or(DEFAULT_WIDTH, f64::from))
+            .collect::<Vec<_>>();
+        let mut code = 0;
+        for obj in differences {
+            match obj {
+                Object::Number(_) => code = obj.num_value().unwrap_or(usize::MAX),
+                Object::Name(name) => {
+                    if let Some(width) = widths.get_mut(code) {
+                        *width = std::str::from_utf8(name.as_slice()).ok()
+                            .and_then(|glyph| font.glyph_width(glyph))
+                            .map_or(DEFAULT_WIDTH, f64::from);
+                    }
+                    code = code.saturating_add(1);
+                },
+                _ => return Err(Error::Parse("malformed /Differences"))
+            }
+        }
+        Ok(FontInfo { first_char: 0, widths, to_unicode })
+    }
+
+    fn to_unicode<T: BufRead + Seek>(page: &Page<'_, T>, dict: &Dict) -> Result<Option<ToUnicode>, Error> {
+        let reader = &page.doc.reader;
+        Ok(match dict.lookup(b"ToUnicode") {
+            Object::Null => None,
+            obj => {
+                let stm = reader.resolve_stream(obj)?;
+                let mut data = Vec::new();
+                reader.read_stream_data(&stm)?.read_to_end(&mut data)?;
+                ToUnicode::parse(&data)
+                    .inspect_err(|err| reader.parser().diagnostics().report(Event::MalformedToUnicode(err.clone())))
+                    .ok()
+            }
+        })
+    }
+
+    /// Splits a string into character codes, each with its Unicode text.
+    fn decode(&self, bytes: &[u8]) -> Vec<(u32, String)> {
+        match &self.to_unicode {
+            Some(cmap) => cmap.codes(bytes).into_iter()
+                .map(|code| (code, cmap.map(code).unwrap_or_else(|| latin1(code))))
+                .collect(),
+            None => bytes.iter()
+                .map(|&c| (c as u32, latin1(c as u32)))
+                .collect()
+        }
+    }
+
+    /// The width of the glyph for `code`, in thousandths of text space units.
+    fn width(&self, code: u32) -> f64 {
+        code.checked_sub(self.first_char)
+            .and_then(|ix| self.widths.get(ix as usize))
+            .copied()
+            .unwrap_or(DEFAULT_WIDTH)
+    }
+}
+
+/// Interprets a single-byte code as Latin-1, longer codes become U+FFFD.
+fn latin1(code: u32) -> String {
+    u8::try_from(code).map_or(char::REPLACEMENT_CHARACTER, char::from).to_string()
+}
+
+/// The end of the last shown text.
+struct TextEnd {
+    text_matrix: Matrix,
+    ctm: Matrix,
+    /// The horizontal advance from the origin of `text_matrix`, in thousandths of text space units.
+    advance: f64,
+    x: f64,
+    y: f64,
+}
+
+impl<T: BufRead + Seek> Page<'_, T> {
+    /// Extracts the text shown on this page by the `Tj`, `TJ`, `'` and `"` operators, in content 
+    /// stream order.
+    ///
+    /// Strings are decoded using the `/ToUnicode` CMap of the font if present. Otherwise, and for 
+    /// codes the CMap does not map, they are decoded byte by byte as Latin-1. A space is inserted 
+    /// where the text jumps forward on the same line or where `TJ` adjusts by more than a fifth of 
+    /// an em, and a newline where it moves to a different line. Glyph advances are computed from 
+    /// the `/Widths` of the font or, if it has none, from the metrics of the standard 14 fonts. 
+    /// Glyphs whose width is not known are taken as half an em wide.
+    ///
+    /// Text in form XObjects and annotation appearances is not included.
+    pub fn extract_text(&self) -> Result<String, Error> {
+        let font_dicts = match self.resources()?.lookup(b"Font") {
+            Object::Null => Dict::default(),
+            obj => self.doc.reader.resolve_dict(obj)?
+        };
+        let mut fonts = BTreeMap::new();
+        let mut ret = String::new();
+        let mut last: Option<TextEnd> = None;
+        let data = self.contents()?;
+        let diag = self.doc.reader.parser().diagnostics();
+        let malformed = |operator: &[u8]| diag.report(Event::MalformedOperands {
+            operator: operator.to_vec(), error: Error::Parse("malformed operands") });
+        let mut iter = GraphicsStateIter::new(ContentParser::new(&data[..]));
+        iter.set_diagnostics(diag.clone());
+        for res in iter {
+            let (state, mut operands, operator) = res?;
+            if !matches!(&operator[..], b"Tj" | b"TJ" | b"'" | b"\"") {
+                continue;
+            }
+            let items = match operands.pop() {
+                Some(Object::Array(arr)) if operator == b"TJ" => arr,
+                Some(obj @ Object::String(_)) if operator != b"TJ" => vec![obj],
+                _ => {
+                    malformed(&operator);
+                    continue;
+                }
+            };
+            let key = state.font.as_ref().map(|name| name.as_slice().to_vec()).unwrap_or_default();
+            if !fonts.contains_key(&key) {
+                let font = match font_dicts.lookup(&key) {
+                    Object::Null => {
+                        diag.report(Event::FontNotFound(Name::from(&key[..])));
+                        FontInfo { first_char: 0, widths: vec![], to_unicode: None }
+                    },
+                    obj => FontInfo::new(self, &self.doc.reader.resolve_dict(obj)?)?
+                };
+                fonts.insert(key.clone(), font);
+            }
+            let font = &fonts[&key];
+            let trm = state.text_matrix * state.ctm;
+            let mut advance = 0.;
+            match &last {
+                Some(last) if last.text_matrix == state.text_matrix && last.ctm == state.ctm
+                    => advance = last.advance,
+                Some(last) => {
+                    let (x, y) = trm.apply(0., 0.);
+                    let size = state.font_size * trm.c.hypot(trm.d);
+                    if (y - last.y).abs() > size / 2. {
+                        ret.push('\n');
+                    } else if x - last.x > size / 5. && !ret.ends_with(' ') {
+                        ret.push(' ');
+                    }
+                },
+                None => ()
+            }
+            for item in items {
+                match item {
+                    Object::String(bytes) => for (code, text) in font.decode(&bytes) {
+                        ret += &text;
+                        advance += font.width(code);
+                    },
+                    Object::Number(num) => {
+                        let num = num.as_f64();
+                        if num < -200. && !ret.ends_with(' ') {
+                            ret.push(' ');
+                        }
+                        advance -= num;
+                    },
+                    _ => malformed(b"TJ")
+                }
+            }
+            let (x, y) = (Matrix::translate(advance / 1000. * state.font_size, 0.) * trm).apply(0., 0.);
+            last = Some(TextEnd { text_matrix: state.text_matrix, ctm: state.ctm, advance, x, y });
+        }
+        Ok(ret)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use crate::base::*;
+    use crate::utils::{self, Capture};
+    use crate::document::Document;
+
+    fn page_text(content: &str, font: &str) -> String {
+        let content = format!("<< /Length {} >>\nstream\n{content}\nendstream", content.len() + 1);
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 /Resources << /Font << /F1 5 0 R >> >> >>",
+            "<< /Type /Page /Parent 2 0 R /Contents [4 0 R] >>",
+            &content,
+            font,
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let pages = doc.pages().unwrap();
+        pages[0].extract_text().unwrap()
+    }
+
+    #[test]
+    fn test_extract_text() {
+        let font = "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>";
+        assert_eq!(page_text("BT /F1 12 Tf 72 700 Td (Hello, ) Tj (PDF 1.5!) Tj ET", font), "Hello, PDF 1.5!");
+        assert_eq!(page_text("BT /F1 12 Tf 72 700 Td [(Hello,) -250 (PDF)] TJ 0 -14 Td (1.5!) Tj ET", font),
+            "Hello, PDF\n1.5!");
+        assert_eq!(page_text("BT /F1 10 Tf 72 700 Td (ab) Tj 10 0 Td (cd) Tj 20 0 Td (ef) Tj ET", font),
+            "abcd ef");
+        assert_eq!(page_text("BT /F1 10 Tf (\\311t\\351) Tj ET", font), "Été");
+
+        let font = "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /FirstChar 97 /Widths [100 200] >>";
+        assert_eq!(page_text("BT /F1 10 Tf 72 700 Td (ab) Tj 3 0 Td (ab) Tj ET", font), "abab");
+        assert_eq!(page_text("BT /F1 10 Tf 72 700 Td (ab) Tj 6 0 Td (ab) Tj ET", font), "ab ab");
+    }
+
+    #[test]
+    fn test_extract_text_to_unicode() {
+        let cmap = "1 begincodespacerange <0000> <FFFF> endcodespacerange \
+            1 beginbfrange <0001> <001A> <0061> endbfrange 1 beginbfchar <0020> <00DF> endbfchar";
+        let content = "BT /F1 10 Tf 72 700 Td <0013000500130020> Tj ET";
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /Contents 4 0 R /Resources << /Font << /F1 5 0 R >> >> >>",
+            &format!("<< /Length {} >>\nstream\n{content}\nendstream", content.len()),
+            "<< /Type /Font /Subtype /Type0 /BaseFont /Foo /Encoding /Identity-H /ToUnicode 6 0 R >>",
+            &format!("<< /Length {} >>\nstream\n{cmap}\nendstream", cmap.len()),
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.pages().unwrap()[0].extract_text().unwrap(), "sesß");
+    }
+
+    #[test]
+    fn test_extract_text_standard14() {
+        // Helvetica: "l" is 222, "W" is 944 units wide
+        let font = "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>";
+        assert_eq!(page_text("BT /F1 10 Tf 0 0 Td (ll) Tj 4.5 0 Td (ll) Tj ET", font), "llll");
+        assert_eq!(page_text("BT /F1 10 Tf 0 0 Td (WW) Tj 18.9 0 Td (WW) Tj ET", font), "WWWW");
+        assert_eq!(page_text("BT /F1 10 Tf 0 0 Td (ll) Tj 7 0 Td (ll) Tj ET", font), "ll ll");
+
+        let font = "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding << /Differences [108 /W] >> >>";
+        assert_eq!(page_text("BT /F1 10 Tf 0 0 Td (ll) Tj 7 0 Td (ll) Tj ET", font), "llll");
+    }
+
+    #[test]
+    fn test_extract_text_diagnostics() {
+        let cmap = "1 beginbfchar <01> endbfchar";
+        let content = "BT /F2 10 Tf (a) Tj /F1 10 Tf 5 Tj [(b) /x] TJ ET";
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /Contents 4 0 R /Resources << /Font << /F1 5 0 R >> >> >>",
+            &format!("<< /Length {} >>\nstream\n{content}\nendstream", content.len()),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /ToUnicode 6 0 R >>",
+            &format!("<< /Length {} >>\nstream\n{cmap}\nendstream", cmap.len()),
+        ]);
+        let capture = Arc::new(Capture::default());
+        let doc = Document::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        assert_eq!(doc.pages().unwrap()[0].extract_text().unwrap(), "ab");
+        let events = capture.0.lock().unwrap();
+        assert!(matches!(&events[..], [
+            Event::FontNotFound(name),
+            Event::MalformedOperands { operator: op1, .. },
+            Event::MalformedToUnicode(_),
+            Event::MalformedOperands { operator: op2, .. },
+        ] if name == &Name::from(b"F2") && op1 == b"Tj" && op2 == b"TJ"), "{events:?}");
+    }
+}
@@ -0,0 +1,108 @@
+use std::io::{BufRead, Seek};
+use std::collections::BTreeSet;
+
+use crate::base::*;
+
+use super::Document;
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Collects all entries of a name tree, given its root node (or a reference to it).
+    ///
+    /// The entries are returned in the order of appearance, which for a well-formed tree is the 
+    /// order of keys. The values are returned as stored, i.e., not resolved. Nodes appearing more 
+    /// than once in the tree are skipped with a warning.
+    pub fn name_tree(&self, root: &Object) -> Result<Vec<(Vec<u8>, Object)>, Error> {
+        let mut ret = Vec::new();
+        self.collect_name_tree(root, &mut BTreeSet::new(), &mut ret)?;
+        Ok(ret)
+    }
+
+    /// Collects all entries of a number tree, given its root node (or a reference to it).
+    ///
+    /// The same conventions apply as for [`Document::name_tree()`].
+    pub fn number_tree(&self, root: &Object) -> Result<Vec<(i64, Object)>, Error> {
+        let mut ret = Vec::new();
+        self.collect_number_tree(root, &mut BTreeSet::new(), &mut ret)?;
+        Ok(ret)
+    }
+
+    fn collect_number_tree(&self, node: &Object, visited: &mut BTreeSet<ObjRef>,
+        out: &mut Vec<(i64, Object)>) -> Result<(), Error>
+    {
+        if let Object::Ref(objref) = node {
+            if !visited.insert(*objref) {
+                log_warn!("Cycle detected in number tree at {objref}.");
+                return Ok(());
+            }
+        }
+        let dict = self.reader.resolve_dict(node)?;
+        if let nums @ (Object::Array(_) | Object::Ref(_)) = dict.lookup(b"Nums") {
+            let nums = self.reader.resolve_array(nums)?;
+            for pair in nums.chunks_exact(2) {
+                let key = self.reader.resolve_obj(pair[0].to_owned())?
+                    .num_value()
+                    .ok_or(Error::Parse("malformed number tree (key)"))?;
+                out.push((key, pair[1].to_owned()));
+            }
+        }
+        if let kids @ (Object::Array(_) | Object::Ref(_)) = dict.lookup(b"Kids") {
+            for kid in self.reader.resolve_array(kids)? {
+                self.collect_number_tree(&kid, visited, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_name_tree(&self, node: &Object, visited: &mut BTreeSet<ObjRef>,
+        out: &mut Vec<(Vec<u8>, Object)>) -> Result<(), Error>
+    {
+        if let Object::Ref(objref) = node {
+            if !visited.insert(*objref) {
+                log_warn!("Cycle detected in name tree at {objref}.");
+                return Ok(());
+            }
+        }
+        let dict = self.reader.resolve_dict(node)?;
+        if let names @ (Object::Array(_) | Object::Ref(_)) = dict.lookup(b"Names") {
+            let names = self.reader.resolve_array(names)?;
+            for pair in names.chunks_exact(2) {
+                let key = self.reader.resolve_obj(pair[0].to_owned())?
+                    .into_string()
+                    .ok_or(Error::Parse("malformed name tree (key)"))?;
+                out.push((key, pair[1].to_owned()));
+            }
+        }
+        if let kids @ (Object::Array(_) | Object::Ref(_)) = dict.lookup(b"Kids") {
+            for kid in self.reader.resolve_array(kids)? {
+                self.collect_name_tree(&kid, visited, out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_name_tree() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Names << /Dests 2 0 R >> >>",
+            "<< /Kids [3 0 R 4 0 R] >>",
+            "<< /Limits [(a) (b)] /Names [(a) 1 (b) 5 0 R] >>",
+            "<< /Limits [(c) (c)] /Names [(c) 3] /Kids [2 0 R] >>",
+            "2",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let names = doc.catalog().lookup(b"Names").as_dict().unwrap();
+        assert_eq!(doc.name_tree(names.lookup(b"Dests")).unwrap(), vec![
+            (b"a".to_vec(), Object::Number(Number::Int(1))),
+            (b"b".to_vec(), Object::Ref(ObjRef { num: 5, gen: 0 })),
+            (b"c".to_vec(), Object::Number(Number::Int(3))),
+        ]);
+    }
+}
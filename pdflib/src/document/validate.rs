@@ -0,0 +1,223 @@
+use std::io::{BufRead, Seek};
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::base::*;
+
+use super::Document;
+use super::pages::MAX_TREE_DEPTH;
+
+/// A problem found by [`Document::validate()`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidationIssue {
+    /// The object in which the problem was found, or `None` for the trailer.
+    pub objref: Option<ObjRef>,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl<T: BufRead + Seek> Document<T> {
+    /// Checks the document for structural problems which readers may or may not tolerate.
+    ///
+    /// All objects reachable from the trailer are visited. Reported are references to objects 
+    /// which do not exist, objects which can not be read, streams whose `/Length` does not end at 
+    /// the `endstream` keyword, and a missing or wrong `/Type` on the catalog or page tree nodes. 
+    /// The `/Count` of each page tree node is also checked against the number of its leaves, up to 
+    /// 256 levels deep.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        self.validate_objects(&mut issues);
+        match self.reader.xref.dict.lookup(b"Root").as_objref() {
+            Some(&root) => {
+                if !self.catalog.has_type(b"Catalog") {
+                    issues.push(ValidationIssue { objref: Some(root), message: "missing or wrong /Type in catalog".into() });
+                }
+                match self.catalog.lookup(b"Pages").as_objref() {
+                    Some(&pages) => { self.validate_page_tree(pages, &mut BTreeSet::new(), MAX_TREE_DEPTH, &mut issues); },
+                    None => issues.push(ValidationIssue { objref: Some(root), message: "malformed /Pages".into() })
+                }
+            },
+            None => issues.push(ValidationIssue { objref: None, message: "/Root is not an indirect reference".into() })
+        }
+        issues
+    }
+
+    fn validate_objects(&self, issues: &mut Vec<ValidationIssue>) {
+        let mut queue = VecDeque::new();
+        collect_refs(&Object::Dict(self.reader.xref.dict.clone()), &mut queue);
+        let mut visited = BTreeSet::new();
+        while let Some(objref) = queue.pop_front() {
+            if !visited.insert(objref) {
+                continue;
+            }
+            let obj = match self.reader.resolve_ref_opt(&objref) {
+                Ok(Some(obj)) => obj,
+                Ok(None) => {
+                    issues.push(ValidationIssue { objref: Some(objref), message: "reference to a missing object".into() });
+                    continue;
+                },
+                Err(err) => {
+                    issues.push(ValidationIssue { objref: Some(objref), message: format!("object can not be read: {err}") });
+                    continue;
+                }
+            };
+            if let Object::Stream(stm) = &obj {
+                if let Some(message) = self.validate_length(stm) {
+                    issues.push(ValidationIssue { objref: Some(objref), message });
+                }
+            }
+            collect_refs(&obj, &mut queue);
+        }
+    }
+
+    fn validate_length(&self, stm: &RefStream) -> Option<String> {
+        let len = match self.reader.resolve_obj(stm.dict.lookup(b"Length").to_owned()) {
+            Ok(obj) => obj.num_value::<u64>(),
+            Err(err) => return Some(format!("/Length can not be read: {err}"))
+        };
+        let Some(len) = len else {
+            return Some("missing or malformed /Length".into());
+        };
+        match self.reader.parser().read_stream_end(stm.data + len) {
+            Ok(_) => None,
+            Err(_) => Some(format!("/Length {len} does not end at endstream"))
+        }
+    }
+
+    /// Returns the number of leaves under the given node.
+    fn validate_page_tree(&self, objref: ObjRef, visited: &mut BTreeSet<ObjRef>, depth: usize,
+        issues: &mut Vec<ValidationIssue>) -> u64
+    {
+        let Some(depth) = depth.checked_sub(1) else {
+            issues.push(ValidationIssue { objref: Some(objref), message: "page tree too deep".into() });
+            return 0;
+        };
+        if !visited.insert(objref) {
+            issues.push(ValidationIssue { objref: Some(objref), message: "page tree node appears more than once".into() });
+            return 0;
+        }
+        let dict = match self.reader.resolve_dict(&Object::Ref(objref)) {
+            Ok(dict) => dict,
+            Err(_) => {
+                issues.push(ValidationIssue { objref: Some(objref), message: "page tree node is not a dictionary".into() });
+                return 0;
+            }
+        };
+        let kids = match dict.lookup(b"Kids") {
+            Object::Null => None,
+            obj => match self.reader.resolve_array(obj) {
+                Ok(kids) => Some(kids),
+                Err(_) => {
+                    issues.push(ValidationIssue { objref: Some(objref), message: "malformed /Kids".into() });
+                    return 0;
+                }
+            }
+        };
+        let Some(kids) = kids else {
+            if !dict.has_type(b"Page") {
+                issues.push(ValidationIssue { objref: Some(objref), message: "missing or wrong /Type in page".into() });
+            }
+            return 1;
+        };
+        if !dict.has_type(b"Pages") {
+            issues.push(ValidationIssue { objref: Some(objref), message: "missing or wrong /Type in page tree node".into() });
+        }
+        let mut count = 0;
+        for kid in kids {
+            match kid.as_objref() {
+                Some(&kid) => count += self.validate_page_tree(kid, visited, depth, issues),
+                None => issues.push(ValidationIssue { objref: Some(objref), message: "direct object in /Kids".into() })
+            }
+        }
+        let declared = self.reader.resolve_obj(dict.lookup(b"Count").to_owned()).ok()
+            .and_then(|obj| obj.num_value::<u64>());
+        if declared != Some(count) {
+            issues.push(ValidationIssue { objref: Some(objref), message: format!("/Count does not match the number of pages ({count})") });
+        }
+        count
+    }
+}
+
+/// Appends all indirect references contained in `obj`, at any depth, to `out`.
+pub(super) fn collect_refs(obj: &Object, out: &mut VecDeque<ObjRef>) {
+    match obj {
+        Object::Ref(objref) => out.push_back(*objref),
+        Object::Array(arr) => arr.iter().for_each(|obj| collect_refs(obj, out)),
+        Object::Dict(dict) => dict.iter().for_each(|(_, obj)| collect_refs(obj, out)),
+        Object::Stream(stm) => stm.dict.iter().for_each(|(_, obj)| collect_refs(obj, out)),
+        _ => ()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::utils;
+
+    #[test]
+    fn test_validate() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /Contents 4 0 R >>",
+            "<< /Length 3 >>\nstream\nabc\nendstream",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.validate(), vec![]);
+
+        let data = utils::make_pdf(&[
+            "<< /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R 5 0 R] /Count 3 >>",
+            "<< /Type /Page /Parent 2 0 R /Contents 4 0 R /Annots [9 0 R] >>",
+            "<< /Length 2 >>\nstream\nabc\nendstream",
+            "<< /Parent 2 0 R >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let issue = |num, message: &str| ValidationIssue { objref: Some(ObjRef { num, gen: 0 }), message: message.into() };
+        assert_eq!(doc.validate(), vec![
+            issue(4, "/Length 2 does not end at endstream"),
+            issue(9, "reference to a missing object"),
+            issue(1, "missing or wrong /Type in catalog"),
+            issue(5, "missing or wrong /Type in page"),
+            issue(2, "/Count does not match the number of pages (2)"),
+        ]);
+
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R >>",
+            "<< /Type /Pages /Parent 2 0 R /Kids 5 /Count 0 >>",
+        ]);
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.validate(), vec![issue(4, "malformed /Kids")]);
+
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [] /Count 0 >>",
+        ]);
+        // Replace the reference in the trailer by a direct catalog.
+        let data = String::from_utf8(data).unwrap()
+            .replace("/Root 1 0 R", "/Root << /Type /Catalog /Pages 2 0 R >>");
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.validate(), vec![
+            ValidationIssue { objref: None, message: "/Root is not an indirect reference".into() }]);
+    }
+
+    #[test]
+    fn test_validate_deep() {
+        let mut objects = vec!["<< /Type /Catalog /Pages 2 0 R >>".to_owned()];
+        let len = MAX_TREE_DEPTH + 10;
+        for num in 2..len {
+            objects.push(format!("<< /Type /Pages /Kids [{} 0 R] /Count 1 >>", num + 1));
+        }
+        objects.push("<< /Type /Page >>".to_owned());
+        let data = utils::make_pdf(&objects.iter().map(String::as_str).collect::<Vec<_>>());
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        let issues = doc.validate();
+        assert!(issues.contains(&ValidationIssue {
+            objref: Some(ObjRef { num: MAX_TREE_DEPTH as u64 + 2, gen: 0 }),
+            message: "page tree too deep".into()
+        }));
+    }
+}
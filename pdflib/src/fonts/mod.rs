@@ -0,0 +1,121 @@
+//! Font metrics and encodings.
+
+pub mod standard14;
+
+/// A predefined simple-font encoding, mapping single-byte codes to glyph names.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Encoding {
+    /// `/StandardEncoding`, the built-in encoding of most Type 1 fonts.
+    Standard,
+    /// `/WinAnsiEncoding`.
+    WinAnsi,
+}
+
+/// Glyph names for codes 32 to 126 in `/WinAnsiEncoding`. `/StandardEncoding` differs in codes 39 
+/// and 96.
+const ASCII: [&str; 95] = [
+    "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand", "quotesingle",
+    "parenleft", "parenright", "asterisk", "plus", "comma", "hyphen", "period", "slash",
+    "zero", "one", "two", "three", "four", "five", "six", "seven",
+    "eight", "nine", "colon", "semicolon", "less", "equal", "greater", "question",
+    "at", "A", "B", "C", "D", "E", "F", "G",
+    "H", "I", "J", "K", "L", "M", "N", "O",
+    "P", "Q", "R", "S", "T", "U", "V", "W",
+    "X", "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum", "underscore",
+    "grave", "a", "b", "c", "d", "e", "f", "g",
+    "h", "i", "j", "k", "l", "m", "n", "o",
+    "p", "q", "r", "s", "t", "u", "v", "w",
+    "x", "y", "z", "braceleft", "bar", "braceright", "asciitilde",
+];
+
+impl Encoding {
+    /// Resolves an encoding name as used in the `/Encoding` entry of a font dictionary.
+    pub fn from_name(name: &[u8]) -> Option<Encoding> {
+        match name {
+            b"StandardEncoding" => Some(Encoding::Standard),
+            b"WinAnsiEncoding" => Some(Encoding::WinAnsi),
+            _ => None
+        }
+    }
+
+    /// The name of the glyph encoded by `code`, if any.
+    pub fn glyph_name(self, code: u8) -> Option<&'static str> {
+        match (self, code) {
+            (Encoding::Standard, 39) => return Some("quoteright"),
+            (Encoding::Standard, 96) => return Some("quoteleft"),
+            (_, 32..=126) => return Some(ASCII[code as usize - 32]),
+            _ => ()
+        }
+        let name = match self {
+            Encoding::Standard => match code {
+                161 => "exclamdown", 162 => "cent", 163 => "sterling", 164 => "fraction",
+                165 => "yen", 166 => "florin", 167 => "section", 168 => "currency",
+                169 => "quotesingle", 170 => "quotedblleft", 171 => "guillemotleft", 172 => "guilsinglleft",
+                173 => "guilsinglright", 174 => "fi", 175 => "fl", 177 => "endash",
+                178 => "dagger", 179 => "daggerdbl", 180 => "periodcentered", 182 => "paragraph",
+                183 => "bullet", 184 => "quotesinglbase", 185 => "quotedblbase", 186 => "quotedblright",
+                187 => "guillemotright", 188 => "ellipsis", 189 => "perthousand", 191 => "questiondown",
+                193 => "grave", 194 => "acute", 195 => "circumflex", 196 => "tilde",
+                197 => "macron", 198 => "breve", 199 => "dotaccent", 200 => "dieresis",
+                202 => "ring", 203 => "cedilla", 205 => "hungarumlaut", 206 => "ogonek",
+                207 => "caron", 208 => "emdash", 225 => "AE", 227 => "ordfeminine",
+                232 => "Lslash", 233 => "Oslash", 234 => "OE", 235 => "ordmasculine",
+                241 => "ae", 245 => "dotlessi", 248 => "lslash", 249 => "oslash",
+                250 => "oe", 251 => "germandbls",
+                _ => return None
+            },
+            Encoding::WinAnsi => match code {
+                128 => "Euro", 130 => "quotesinglbase", 131 => "florin", 132 => "quotedblbase",
+                133 => "ellipsis", 134 => "dagger", 135 => "daggerdbl", 136 => "circumflex",
+                137 => "perthousand", 138 => "Scaron", 139 => "guilsinglleft", 140 => "OE",
+                142 => "Zcaron", 145 => "quoteleft", 146 => "quoteright", 147 => "quotedblleft",
+                148 => "quotedblright", 149 => "bullet", 150 => "endash", 151 => "emdash",
+                152 => "tilde", 153 => "trademark", 154 => "scaron", 155 => "guilsinglright",
+                156 => "oe", 158 => "zcaron", 159 => "Ydieresis", 160 => "space",
+                161 => "exclamdown", 162 => "cent", 163 => "sterling", 164 => "currency",
+                165 => "yen", 166 => "brokenbar", 167 => "section", 168 => "dieresis",
+                169 => "copyright", 170 => "ordfeminine", 171 => "guillemotleft", 172 => "logicalnot",
+                173 => "hyphen", 174 => "registered", 175 => "macron", 176 => "degree",
+                177 => "plusminus", 178 => "twosuperior", 179 => "threesuperior", 180 => "acute",
+                181 => "mu", 182 => "paragraph", 183 => "periodcentered", 184 => "cedilla",
+                185 => "onesuperior", 186 => "ordmasculine", 187 => "guillemotright", 188 => "onequarter",
+                189 => "onehalf", 190 => "threequarters", 191 => "questiondown", 192 => "Agrave",
+                193 => "Aacute", 194 => "Acircumflex", 195 => "Atilde", 196 => "Adieresis",
+                197 => "Aring", 198 => "AE", 199 => "Ccedilla", 200 => "Egrave",
+                201 => "Eacute", 202 => "Ecircumflex", 203 => "Edieresis", 204 => "Igrave",
+                205 => "Iacute", 206 => "Icircumflex", 207 => "Idieresis", 208 => "Eth",
+                209 => "Ntilde", 210 => "Ograve", 211 => "Oacute", 212 => "Ocircumflex",
+                213 => "Otilde", 214 => "Odieresis", 215 => "multiply", 216 => "Oslash",
+                217 => "Ugrave", 218 => "Uacute", 219 => "Ucircumflex", 220 => "Udieresis",
+                221 => "Yacute", 222 => "Thorn", 223 => "germandbls", 224 => "agrave",
+                225 => "aacute", 226 => "acircumflex", 227 => "atilde", 228 => "adieresis",
+                229 => "aring", 230 => "ae", 231 => "ccedilla", 232 => "egrave",
+                233 => "eacute", 234 => "ecircumflex", 235 => "edieresis", 236 => "igrave",
+                237 => "iacute", 238 => "icircumflex", 239 => "idieresis", 240 => "eth",
+                241 => "ntilde", 242 => "ograve", 243 => "oacute", 244 => "ocircumflex",
+                245 => "otilde", 246 => "odieresis", 247 => "divide", 248 => "oslash",
+                249 => "ugrave", 250 => "uacute", 251 => "ucircumflex", 252 => "udieresis",
+                253 => "yacute", 254 => "thorn", 255 => "ydieresis",
+                _ => return None
+            }
+        };
+        Some(name)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(Encoding::Standard.glyph_name(b'A'), Some("A"));
+        assert_eq!(Encoding::Standard.glyph_name(b'\''), Some("quoteright"));
+        assert_eq!(Encoding::WinAnsi.glyph_name(b'\''), Some("quotesingle"));
+        assert_eq!(Encoding::Standard.glyph_name(0xE1), Some("AE"));
+        assert_eq!(Encoding::WinAnsi.glyph_name(0xE1), Some("aacute"));
+        assert_eq!(Encoding::WinAnsi.glyph_name(0x81), None);
+        assert_eq!(Encoding::Standard.glyph_name(0x0A), None);
+    }
+}
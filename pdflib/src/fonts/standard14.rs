@@ -0,0 +1,468 @@
+//! Glyph widths of the 14 standard Type 1 fonts, which PDF readers are required to provide and 
+//! which therefore may be used without embedding or `/Widths`.
+
+use super::Encoding;
+
+/// One of the standard 14 fonts.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Standard14 {
+    Courier,
+    CourierBold,
+    CourierOblique,
+    CourierBoldOblique,
+    Helvetica,
+    HelveticaBold,
+    HelveticaOblique,
+    HelveticaBoldOblique,
+    TimesRoman,
+    TimesBold,
+    TimesItalic,
+    TimesBoldItalic,
+    Symbol,
+    ZapfDingbats,
+}
+
+/// Suffixes of the names of accented letters. In the Latin standard fonts, accented letters mostly 
+/// have the same width as their base letter. The exceptions are listed in the width tables.
+const ACCENTS: [&str; 8] = ["acute", "grave", "circumflex", "dieresis", "tilde", "ring", "cedilla", "caron"];
+
+impl Standard14 {
+    /// Resolves the `/BaseFont` name of one of the standard 14 fonts.
+    pub fn from_name(name: &[u8]) -> Option<Standard14> {
+        Some(match name {
+            b"Courier" => Standard14::Courier,
+            b"Courier-Bold" => Standard14::CourierBold,
+            b"Courier-Oblique" => Standard14::CourierOblique,
+            b"Courier-BoldOblique" => Standard14::CourierBoldOblique,
+            b"Helvetica" => Standard14::Helvetica,
+            b"Helvetica-Bold" => Standard14::HelveticaBold,
+            b"Helvetica-Oblique" => Standard14::HelveticaOblique,
+            b"Helvetica-BoldOblique" => Standard14::HelveticaBoldOblique,
+            b"Times-Roman" => Standard14::TimesRoman,
+            b"Times-Bold" => Standard14::TimesBold,
+            b"Times-Italic" => Standard14::TimesItalic,
+            b"Times-BoldItalic" => Standard14::TimesBoldItalic,
+            b"Symbol" => Standard14::Symbol,
+            b"ZapfDingbats" => Standard14::ZapfDingbats,
+            _ => return None
+        })
+    }
+
+    /// Widths of the Latin fonts.
+    fn latin_widths(self) -> Option<&'static [(&'static str, u16)]> {
+        use Standard14::*;
+        match self {
+            Courier | CourierBold | CourierOblique | CourierBoldOblique => Some(COURIER),
+            Helvetica | HelveticaOblique => Some(HELVETICA),
+            HelveticaBold | HelveticaBoldOblique => Some(HELVETICA_BOLD),
+            TimesRoman => Some(TIMES_ROMAN),
+            TimesBold => Some(TIMES_BOLD),
+            TimesItalic => Some(TIMES_ITALIC),
+            TimesBoldItalic => Some(TIMES_BOLD_ITALIC),
+            Symbol | ZapfDingbats => None
+        }
+    }
+
+    /// Glyphs of the symbolic fonts along with their codes in the built-in encoding.
+    fn builtin_glyphs(self) -> Option<&'static [(u8, &'static str, u16)]> {
+        match self {
+            Standard14::Symbol => Some(SYMBOL),
+            Standard14::ZapfDingbats => Some(ZAPF_DINGBATS),
+            _ => None
+        }
+    }
+
+    /// The advance width of a glyph, given by its name, in thousandths of an em.
+    ///
+    /// Courier, being monospaced, gives a width of 600 even for glyphs not listed in its table.
+    pub fn glyph_width(self, glyph: &str) -> Option<u16> {
+        let Some(table) = self.latin_widths() else {
+            return self.builtin_glyphs()?.iter()
+                .find(|(_, name, _)| *name == glyph)
+                .map(|&(_, _, width)| width);
+        };
+        let lookup = |glyph| table.iter().find(|(name, _)| *name == glyph).map(|&(_, width)| width);
+        lookup(glyph).or_else(|| {
+            let base = ACCENTS.iter().find_map(|accent| glyph.strip_suffix(accent))?;
+            if base.len() == 1 && base.as_bytes()[0].is_ascii_alphabetic() { lookup(base) } else { None }
+        }).or_else(|| {
+            use Standard14::*;
+            matches!(self, Courier | CourierBold | CourierOblique | CourierBoldOblique).then_some(600)
+        })
+    }
+
+    /// The advance width of the glyph for `code` in thousandths of an em, using the given 
+    /// encoding or, if `None`, the built-in encoding of the font.
+    pub fn code_width(self, code: u8, encoding: Option<Encoding>) -> Option<u16> {
+        match (encoding, self.builtin_glyphs()) {
+            (None, Some(glyphs)) => glyphs.iter()
+                .find(|(c, _, _)| *c == code)
+                .map(|&(_, _, width)| width),
+            (encoding, _) => self.glyph_width(encoding.unwrap_or(Encoding::Standard).glyph_name(code)?)
+        }
+    }
+}
+
+/// Glyph widths of Courier, a monospaced font, shared by all its styles.
+const COURIER: &[(&str, u16)] = &[
+    ("space", 600), ("exclam", 600), ("quotedbl", 600), ("numbersign", 600), ("dollar", 600), ("percent", 600),
+    ("ampersand", 600), ("quoteright", 600), ("parenleft", 600), ("parenright", 600), ("asterisk", 600), ("plus", 600),
+    ("comma", 600), ("hyphen", 600), ("period", 600), ("slash", 600), ("zero", 600), ("one", 600),
+    ("two", 600), ("three", 600), ("four", 600), ("five", 600), ("six", 600), ("seven", 600),
+    ("eight", 600), ("nine", 600), ("colon", 600), ("semicolon", 600), ("less", 600), ("equal", 600),
+    ("greater", 600), ("question", 600), ("at", 600), ("A", 600), ("B", 600), ("C", 600),
+    ("D", 600), ("E", 600), ("F", 600), ("G", 600), ("H", 600), ("I", 600),
+    ("J", 600), ("K", 600), ("L", 600), ("M", 600), ("N", 600), ("O", 600),
+    ("P", 600), ("Q", 600), ("R", 600), ("S", 600), ("T", 600), ("U", 600),
+    ("V", 600), ("W", 600), ("X", 600), ("Y", 600), ("Z", 600), ("bracketleft", 600),
+    ("backslash", 600), ("bracketright", 600), ("asciicircum", 600), ("underscore", 600), ("quoteleft", 600), ("a", 600),
+    ("b", 600), ("c", 600), ("d", 600), ("e", 600), ("f", 600), ("g", 600),
+    ("h", 600), ("i", 600), ("j", 600), ("k", 600), ("l", 600), ("m", 600),
+    ("n", 600), ("o", 600), ("p", 600), ("q", 600), ("r", 600), ("s", 600),
+    ("t", 600), ("u", 600), ("v", 600), ("w", 600), ("x", 600), ("y", 600),
+    ("z", 600), ("braceleft", 600), ("bar", 600), ("braceright", 600), ("asciitilde", 600), ("exclamdown", 600),
+    ("cent", 600), ("sterling", 600), ("fraction", 600), ("yen", 600), ("florin", 600), ("section", 600),
+    ("currency", 600), ("quotesingle", 600), ("quotedblleft", 600), ("guillemotleft", 600), ("guilsinglleft", 600), ("guilsinglright", 600),
+    ("fi", 600), ("fl", 600), ("endash", 600), ("dagger", 600), ("daggerdbl", 600), ("periodcentered", 600),
+    ("paragraph", 600), ("bullet", 600), ("quotesinglbase", 600), ("quotedblbase", 600), ("quotedblright", 600), ("guillemotright", 600),
+    ("ellipsis", 600), ("perthousand", 600), ("questiondown", 600), ("grave", 600), ("acute", 600), ("circumflex", 600),
+    ("tilde", 600), ("macron", 600), ("breve", 600), ("dotaccent", 600), ("dieresis", 600), ("ring", 600),
+    ("cedilla", 600), ("hungarumlaut", 600), ("ogonek", 600), ("caron", 600), ("emdash", 600), ("AE", 600),
+    ("ordfeminine", 600), ("Lslash", 600), ("Oslash", 600), ("OE", 600), ("ordmasculine", 600), ("ae", 600),
+    ("dotlessi", 600), ("lslash", 600), ("oslash", 600), ("oe", 600), ("germandbls", 600), ("copyright", 600),
+    ("registered", 600), ("trademark", 600), ("degree", 600), ("plusminus", 600), ("multiply", 600), ("divide", 600),
+    ("onehalf", 600), ("onequarter", 600), ("threequarters", 600), ("onesuperior", 600), ("twosuperior", 600), ("threesuperior", 600),
+    ("mu", 600), ("brokenbar", 600), ("logicalnot", 600), ("Eth", 600), ("eth", 600), ("Thorn", 600),
+    ("thorn", 600), ("minus", 600),
+];
+
+/// Glyph widths of Helvetica.
+const HELVETICA: &[(&str, u16)] = &[
+    ("space", 278), ("exclam", 278), ("quotedbl", 355), ("numbersign", 556), ("dollar", 556), ("percent", 889),
+    ("ampersand", 667), ("quoteright", 222), ("parenleft", 333), ("parenright", 333), ("asterisk", 389), ("plus", 584),
+    ("comma", 278), ("hyphen", 333), ("period", 278), ("slash", 278), ("zero", 556), ("one", 556),
+    ("two", 556), ("three", 556), ("four", 556), ("five", 556), ("six", 556), ("seven", 556),
+    ("eight", 556), ("nine", 556), ("colon", 278), ("semicolon", 278), ("less", 584), ("equal", 584),
+    ("greater", 584), ("question", 556), ("at", 1015), ("A", 667), ("B", 667), ("C", 722),
+    ("D", 722), ("E", 667), ("F", 611), ("G", 778), ("H", 722), ("I", 278),
+    ("J", 500), ("K", 667), ("L", 556), ("M", 833), ("N", 722), ("O", 778),
+    ("P", 667), ("Q", 778), ("R", 722), ("S", 667), ("T", 611), ("U", 722),
+    ("V", 667), ("W", 944), ("X", 667), ("Y", 667), ("Z", 611), ("bracketleft", 278),
+    ("backslash", 278), ("bracketright", 278), ("asciicircum", 469), ("underscore", 556), ("quoteleft", 222), ("a", 556),
+    ("b", 556), ("c", 500), ("d", 556), ("e", 556), ("f", 278), ("g", 556),
+    ("h", 556), ("i", 222), ("j", 222), ("k", 500), ("l", 222), ("m", 833),
+    ("n", 556), ("o", 556), ("p", 556), ("q", 556), ("r", 333), ("s", 500),
+    ("t", 278), ("u", 556), ("v", 500), ("w", 722), ("x", 500), ("y", 500),
+    ("z", 500), ("braceleft", 334), ("bar", 260), ("braceright", 334), ("asciitilde", 584), ("exclamdown", 333),
+    ("cent", 556), ("sterling", 556), ("fraction", 167), ("yen", 556), ("florin", 556), ("section", 556),
+    ("currency", 556), ("quotesingle", 191), ("quotedblleft", 333), ("guillemotleft", 556), ("guilsinglleft", 333), ("guilsinglright", 333),
+    ("fi", 500), ("fl", 500), ("endash", 556), ("dagger", 556), ("daggerdbl", 556), ("periodcentered", 278),
+    ("paragraph", 537), ("bullet", 350), ("quotesinglbase", 222), ("quotedblbase", 333), ("quotedblright", 333), ("guillemotright", 556),
+    ("ellipsis", 1000), ("perthousand", 1000), ("questiondown", 611), ("grave", 333), ("acute", 333), ("circumflex", 333),
+    ("tilde", 333), ("macron", 333), ("breve", 333), ("dotaccent", 333), ("dieresis", 333), ("ring", 333),
+    ("cedilla", 333), ("hungarumlaut", 333), ("ogonek", 333), ("caron", 333), ("emdash", 1000), ("AE", 1000),
+    ("ordfeminine", 370), ("Lslash", 556), ("Oslash", 778), ("OE", 1000), ("ordmasculine", 365), ("ae", 889),
+    ("dotlessi", 278), ("lslash", 222), ("oslash", 611), ("oe", 944), ("germandbls", 611), ("copyright", 737),
+    ("registered", 737), ("trademark", 1000), ("degree", 400), ("plusminus", 584), ("multiply", 584), ("divide", 584),
+    ("onehalf", 834), ("onequarter", 834), ("threequarters", 834), ("onesuperior", 333), ("twosuperior", 333), ("threesuperior", 333),
+    ("mu", 556), ("brokenbar", 260), ("logicalnot", 584), ("Eth", 722), ("eth", 556), ("Thorn", 667),
+    ("thorn", 556), ("minus", 584),
+    // Accented letters whose width differs from that of the base letter
+    ("dcaron", 643), ("lcaron", 299), ("tcaron", 317), ("iacute", 278), ("igrave", 278), ("icircumflex", 278),
+    ("idieresis", 278),
+];
+
+/// Glyph widths of Helvetica-Bold.
+const HELVETICA_BOLD: &[(&str, u16)] = &[
+    ("space", 278), ("exclam", 333), ("quotedbl", 474), ("numbersign", 556), ("dollar", 556), ("percent", 889),
+    ("ampersand", 722), ("quoteright", 278), ("parenleft", 333), ("parenright", 333), ("asterisk", 389), ("plus", 584),
+    ("comma", 278), ("hyphen", 333), ("period", 278), ("slash", 278), ("zero", 556), ("one", 556),
+    ("two", 556), ("three", 556), ("four", 556), ("five", 556), ("six", 556), ("seven", 556),
+    ("eight", 556), ("nine", 556), ("colon", 333), ("semicolon", 333), ("less", 584), ("equal", 584),
+    ("greater", 584), ("question", 611), ("at", 975), ("A", 722), ("B", 722), ("C", 722),
+    ("D", 722), ("E", 667), ("F", 611), ("G", 778), ("H", 722), ("I", 278),
+    ("J", 556), ("K", 722), ("L", 611), ("M", 833), ("N", 722), ("O", 778),
+    ("P", 667), ("Q", 778), ("R", 722), ("S", 667), ("T", 611), ("U", 722),
+    ("V", 667), ("W", 944), ("X", 667), ("Y", 667), ("Z", 611), ("bracketleft", 333),
+    ("backslash", 278), ("bracketright", 333), ("asciicircum", 584), ("underscore", 556), ("quoteleft", 278), ("a", 556),
+    ("b", 611), ("c", 556), ("d", 611), ("e", 556), ("f", 333), ("g", 611),
+    ("h", 611), ("i", 278), ("j", 278), ("k", 556), ("l", 278), ("m", 889),
+    ("n", 611), ("o", 611), ("p", 611), ("q", 611), ("r", 389), ("s", 556),
+    ("t", 333), ("u", 611), ("v", 556), ("w", 778), ("x", 556), ("y", 556),
+    ("z", 500), ("braceleft", 389), ("bar", 280), ("braceright", 389), ("asciitilde", 584), ("exclamdown", 333),
+    ("cent", 556), ("sterling", 556), ("fraction", 167), ("yen", 556), ("florin", 556), ("section", 556),
+    ("currency", 556), ("quotesingle", 238), ("quotedblleft", 500), ("guillemotleft", 556), ("guilsinglleft", 333), ("guilsinglright", 333),
+    ("fi", 611), ("fl", 611), ("endash", 556), ("dagger", 556), ("daggerdbl", 556), ("periodcentered", 278),
+    ("paragraph", 556), ("bullet", 350), ("quotesinglbase", 278), ("quotedblbase", 500), ("quotedblright", 500), ("guillemotright", 556),
+    ("ellipsis", 1000), ("perthousand", 1000), ("questiondown", 611), ("grave", 333), ("acute", 333), ("circumflex", 333),
+    ("tilde", 333), ("macron", 333), ("breve", 333), ("dotaccent", 333), ("dieresis", 333), ("ring", 333),
+    ("cedilla", 333), ("hungarumlaut", 333), ("ogonek", 333), ("caron", 333), ("emdash", 1000), ("AE", 1000),
+    ("ordfeminine", 370), ("Lslash", 611), ("Oslash", 778), ("OE", 1000), ("ordmasculine", 365), ("ae", 889),
+    ("dotlessi", 278), ("lslash", 278), ("oslash", 611), ("oe", 944), ("germandbls", 611), ("copyright", 737),
+    ("registered", 737), ("trademark", 1000), ("degree", 400), ("plusminus", 584), ("multiply", 584), ("divide", 584),
+    ("onehalf", 834), ("onequarter", 834), ("threequarters", 834), ("onesuperior", 333), ("twosuperior", 333), ("threesuperior", 333),
+    ("mu", 611), ("brokenbar", 280), ("logicalnot", 584), ("Eth", 722), ("eth", 611), ("Thorn", 667),
+    ("thorn", 611), ("minus", 584),
+    // Accented letters whose width differs from that of the base letter
+    ("dcaron", 743), ("lcaron", 400), ("tcaron", 389),
+];
+
+/// Glyph widths of Times-Roman.
+const TIMES_ROMAN: &[(&str, u16)] = &[
+    ("space", 250), ("exclam", 333), ("quotedbl", 408), ("numbersign", 500), ("dollar", 500), ("percent", 833),
+    ("ampersand", 778), ("quoteright", 333), ("parenleft", 333), ("parenright", 333), ("asterisk", 500), ("plus", 564),
+    ("comma", 250), ("hyphen", 333), ("period", 250), ("slash", 278), ("zero", 500), ("one", 500),
+    ("two", 500), ("three", 500), ("four", 500), ("five", 500), ("six", 500), ("seven", 500),
+    ("eight", 500), ("nine", 500), ("colon", 278), ("semicolon", 278), ("less", 564), ("equal", 564),
+    ("greater", 564), ("question", 444), ("at", 921), ("A", 722), ("B", 667), ("C", 667),
+    ("D", 722), ("E", 611), ("F", 556), ("G", 722), ("H", 722), ("I", 333),
+    ("J", 389), ("K", 722), ("L", 611), ("M", 889), ("N", 722), ("O", 722),
+    ("P", 556), ("Q", 722), ("R", 667), ("S", 556), ("T", 611), ("U", 722),
+    ("V", 722), ("W", 944), ("X", 722), ("Y", 722), ("Z", 611), ("bracketleft", 333),
+    ("backslash", 278), ("bracketright", 333), ("asciicircum", 469), ("underscore", 500), ("quoteleft", 333), ("a", 444),
+    ("b", 500), ("c", 444), ("d", 500), ("e", 444), ("f", 333), ("g", 500),
+    ("h", 500), ("i", 278), ("j", 278), ("k", 500), ("l", 278), ("m", 778),
+    ("n", 500), ("o", 500), ("p", 500), ("q", 500), ("r", 333), ("s", 389),
+    ("t", 278), ("u", 500), ("v", 500), ("w", 722), ("x", 500), ("y", 500),
+    ("z", 444), ("braceleft", 480), ("bar", 200), ("braceright", 480), ("asciitilde", 541), ("exclamdown", 333),
+    ("cent", 500), ("sterling", 500), ("fraction", 167), ("yen", 500), ("florin", 500), ("section", 500),
+    ("currency", 500), ("quotesingle", 180), ("quotedblleft", 444), ("guillemotleft", 500), ("guilsinglleft", 333), ("guilsinglright", 333),
+    ("fi", 556), ("fl", 556), ("endash", 500), ("dagger", 500), ("daggerdbl", 500), ("periodcentered", 250),
+    ("paragraph", 453), ("bullet", 350), ("quotesinglbase", 333), ("quotedblbase", 444), ("quotedblright", 444), ("guillemotright", 500),
+    ("ellipsis", 1000), ("perthousand", 1000), ("questiondown", 444), ("grave", 333), ("acute", 333), ("circumflex", 333),
+    ("tilde", 333), ("macron", 333), ("breve", 333), ("dotaccent", 333), ("dieresis", 333), ("ring", 333),
+    ("cedilla", 333), ("hungarumlaut", 333), ("ogonek", 333), ("caron", 333), ("emdash", 1000), ("AE", 889),
+    ("ordfeminine", 276), ("Lslash", 611), ("Oslash", 722), ("OE", 889), ("ordmasculine", 310), ("ae", 667),
+    ("dotlessi", 278), ("lslash", 278), ("oslash", 500), ("oe", 722), ("germandbls", 500), ("copyright", 760),
+    ("registered", 760), ("trademark", 980), ("degree", 400), ("plusminus", 564), ("multiply", 564), ("divide", 564),
+    ("onehalf", 750), ("onequarter", 750), ("threequarters", 750), ("onesuperior", 300), ("twosuperior", 300), ("threesuperior", 300),
+    ("mu", 500), ("brokenbar", 200), ("logicalnot", 564), ("Eth", 722), ("eth", 500), ("Thorn", 556),
+    ("thorn", 500), ("minus", 564),
+    // Accented letters whose width differs from that of the base letter
+    ("dcaron", 588), ("lcaron", 344), ("tcaron", 326),
+];
+
+/// Glyph widths of Times-Bold.
+const TIMES_BOLD: &[(&str, u16)] = &[
+    ("space", 250), ("exclam", 333), ("quotedbl", 555), ("numbersign", 500), ("dollar", 500), ("percent", 1000),
+    ("ampersand", 833), ("quoteright", 333), ("parenleft", 333), ("parenright", 333), ("asterisk", 500), ("plus", 570),
+    ("comma", 250), ("hyphen", 333), ("period", 250), ("slash", 278), ("zero", 500), ("one", 500),
+    ("two", 500), ("three", 500), ("four", 500), ("five", 500), ("six", 500), ("seven", 500),
+    ("eight", 500), ("nine", 500), ("colon", 333), ("semicolon", 333), ("less", 570), ("equal", 570),
+    ("greater", 570), ("question", 500), ("at", 930), ("A", 722), ("B", 667), ("C", 722),
+    ("D", 722), ("E", 667), ("F", 611), ("G", 778), ("H", 778), ("I", 389),
+    ("J", 500), ("K", 778), ("L", 667), ("M", 944), ("N", 722), ("O", 778),
+    ("P", 611), ("Q", 778), ("R", 722), ("S", 556), ("T", 667), ("U", 722),
+    ("V", 722), ("W", 1000), ("X", 722), ("Y", 722), ("Z", 667), ("bracketleft", 333),
+    ("backslash", 278), ("bracketright", 333), ("asciicircum", 581), ("underscore", 500), ("quoteleft", 333), ("a", 500),
+    ("b", 556), ("c", 444), ("d", 556), ("e", 444), ("f", 333), ("g", 500),
+    ("h", 556), ("i", 278), ("j", 333), ("k", 556), ("l", 278), ("m", 833),
+    ("n", 556), ("o", 500), ("p", 556), ("q", 556), ("r", 444), ("s", 389),
+    ("t", 333), ("u", 556), ("v", 500), ("w", 722), ("x", 500), ("y", 500),
+    ("z", 444), ("braceleft", 394), ("bar", 220), ("braceright", 394), ("asciitilde", 520), ("exclamdown", 333),
+    ("cent", 500), ("sterling", 500), ("fraction", 167), ("yen", 500), ("florin", 500), ("section", 500),
+    ("currency", 500), ("quotesingle", 278), ("quotedblleft", 500), ("guillemotleft", 500), ("guilsinglleft", 333), ("guilsinglright", 333),
+    ("fi", 556), ("fl", 556), ("endash", 500), ("dagger", 500), ("daggerdbl", 500), ("periodcentered", 250),
+    ("paragraph", 540), ("bullet", 350), ("quotesinglbase", 333), ("quotedblbase", 500), ("quotedblright", 500), ("guillemotright", 500),
+    ("ellipsis", 1000), ("perthousand", 1000), ("questiondown", 500), ("grave", 333), ("acute", 333), ("circumflex", 333),
+    ("tilde", 333), ("macron", 333), ("breve", 333), ("dotaccent", 333), ("dieresis", 333), ("ring", 333),
+    ("cedilla", 333), ("hungarumlaut", 333), ("ogonek", 333), ("caron", 333), ("emdash", 1000), ("AE", 1000),
+    ("ordfeminine", 300), ("Lslash", 667), ("Oslash", 778), ("OE", 1000), ("ordmasculine", 330), ("ae", 722),
+    ("dotlessi", 278), ("lslash", 278), ("oslash", 500), ("oe", 722), ("germandbls", 556), ("copyright", 747),
+    ("registered", 747), ("trademark", 1000), ("degree", 400), ("plusminus", 570), ("multiply", 570), ("divide", 570),
+    ("onehalf", 750), ("onequarter", 750), ("threequarters", 750), ("onesuperior", 300), ("twosuperior", 300), ("threesuperior", 300),
+    ("mu", 556), ("brokenbar", 220), ("logicalnot", 570), ("Eth", 722), ("eth", 500), ("Thorn", 611),
+    ("thorn", 556), ("minus", 570),
+    // Accented letters whose width differs from that of the base letter
+    ("dcaron", 672), ("lcaron", 394), ("tcaron", 416),
+];
+
+/// Glyph widths of Times-Italic.
+const TIMES_ITALIC: &[(&str, u16)] = &[
+    ("space", 250), ("exclam", 333), ("quotedbl", 420), ("numbersign", 500), ("dollar", 500), ("percent", 833),
+    ("ampersand", 778), ("quoteright", 333), ("parenleft", 333), ("parenright", 333), ("asterisk", 500), ("plus", 675),
+    ("comma", 250), ("hyphen", 333), ("period", 250), ("slash", 278), ("zero", 500), ("one", 500),
+    ("two", 500), ("three", 500), ("four", 500), ("five", 500), ("six", 500), ("seven", 500),
+    ("eight", 500), ("nine", 500), ("colon", 333), ("semicolon", 333), ("less", 675), ("equal", 675),
+    ("greater", 675), ("question", 500), ("at", 920), ("A", 611), ("B", 611), ("C", 667),
+    ("D", 722), ("E", 611), ("F", 611), ("G", 722), ("H", 722), ("I", 333),
+    ("J", 444), ("K", 667), ("L", 556), ("M", 833), ("N", 667), ("O", 722),
+    ("P", 611), ("Q", 722), ("R", 611), ("S", 500), ("T", 556), ("U", 722),
+    ("V", 611), ("W", 833), ("X", 611), ("Y", 556), ("Z", 556), ("bracketleft", 389),
+    ("backslash", 278), ("bracketright", 389), ("asciicircum", 422), ("underscore", 500), ("quoteleft", 333), ("a", 500),
+    ("b", 500), ("c", 444), ("d", 500), ("e", 444), ("f", 278), ("g", 500),
+    ("h", 500), ("i", 278), ("j", 278), ("k", 444), ("l", 278), ("m", 722),
+    ("n", 500), ("o", 500), ("p", 500), ("q", 500), ("r", 389), ("s", 389),
+    ("t", 278), ("u", 500), ("v", 444), ("w", 667), ("x", 444), ("y", 444),
+    ("z", 389), ("braceleft", 400), ("bar", 275), ("braceright", 400), ("asciitilde", 541), ("exclamdown", 389),
+    ("cent", 500), ("sterling", 500), ("fraction", 167), ("yen", 500), ("florin", 500), ("section", 500),
+    ("currency", 500), ("quotesingle", 214), ("quotedblleft", 556), ("guillemotleft", 500), ("guilsinglleft", 333), ("guilsinglright", 333),
+    ("fi", 500), ("fl", 500), ("endash", 500), ("dagger", 500), ("daggerdbl", 500), ("periodcentered", 250),
+    ("paragraph", 523), ("bullet", 350), ("quotesinglbase", 333), ("quotedblbase", 556), ("quotedblright", 556), ("guillemotright", 500),
+    ("ellipsis", 889), ("perthousand", 1000), ("questiondown", 500), ("grave", 333), ("acute", 333), ("circumflex", 333),
+    ("tilde", 333), ("macron", 333), ("breve", 333), ("dotaccent", 333), ("dieresis", 333), ("ring", 333),
+    ("cedilla", 333), ("hungarumlaut", 333), ("ogonek", 333), ("caron", 333), ("emdash", 889), ("AE", 889),
+    ("ordfeminine", 276), ("Lslash", 556), ("Oslash", 722), ("OE", 944), ("ordmasculine", 310), ("ae", 667),
+    ("dotlessi", 278), ("lslash", 278), ("oslash", 500), ("oe", 667), ("germandbls", 500), ("copyright", 760),
+    ("registered", 760), ("trademark", 980), ("degree", 400), ("plusminus", 675), ("multiply", 675), ("divide", 675),
+    ("onehalf", 750), ("onequarter", 750), ("threequarters", 750), ("onesuperior", 300), ("twosuperior", 300), ("threesuperior", 300),
+    ("mu", 500), ("brokenbar", 275), ("logicalnot", 675), ("Eth", 722), ("eth", 500), ("Thorn", 611),
+    ("thorn", 500), ("minus", 675),
+    // Accented letters whose width differs from that of the base letter
+    ("dcaron", 544), ("lcaron", 300), ("tcaron", 300),
+];
+
+/// Glyph widths of Times-BoldItalic.
+const TIMES_BOLD_ITALIC: &[(&str, u16)] = &[
+    ("space", 250), ("exclam", 389), ("quotedbl", 555), ("numbersign", 500), ("dollar", 500), ("percent", 833),
+    ("ampersand", 778), ("quoteright", 333), ("parenleft", 333), ("parenright", 333), ("asterisk", 500), ("plus", 570),
+    ("comma", 250), ("hyphen", 333), ("period", 250), ("slash", 278), ("zero", 500), ("one", 500),
+    ("two", 500), ("three", 500), ("four", 500), ("five", 500), ("six", 500), ("seven", 500),
+    ("eight", 500), ("nine", 500), ("colon", 333), ("semicolon", 333), ("less", 570), ("equal", 570),
+    ("greater", 570), ("question", 500), ("at", 832), ("A", 667), ("B", 667), ("C", 667),
+    ("D", 722), ("E", 667), ("F", 667), ("G", 722), ("H", 778), ("I", 389),
+    ("J", 500), ("K", 667), ("L", 611), ("M", 889), ("N", 722), ("O", 722),
+    ("P", 611), ("Q", 722), ("R", 667), ("S", 556), ("T", 611), ("U", 722),
+    ("V", 667), ("W", 889), ("X", 667), ("Y", 611), ("Z", 611), ("bracketleft", 333),
+    ("backslash", 278), ("bracketright", 333), ("asciicircum", 570), ("underscore", 500), ("quoteleft", 333), ("a", 500),
+    ("b", 500), ("c", 444), ("d", 500), ("e", 444), ("f", 333), ("g", 500),
+    ("h", 556), ("i", 278), ("j", 278), ("k", 500), ("l", 278), ("m", 778),
+    ("n", 556), ("o", 500), ("p", 500), ("q", 500), ("r", 389), ("s", 389),
+    ("t", 278), ("u", 556), ("v", 444), ("w", 667), ("x", 500), ("y", 444),
+    ("z", 389), ("braceleft", 348), ("bar", 220), ("braceright", 348), ("asciitilde", 570), ("exclamdown", 389),
+    ("cent", 500), ("sterling", 500), ("fraction", 167), ("yen", 500), ("florin", 500), ("section", 500),
+    ("currency", 500), ("quotesingle", 278), ("quotedblleft", 500), ("guillemotleft", 500), ("guilsinglleft", 333), ("guilsinglright", 333),
+    ("fi", 556), ("fl", 556), ("endash", 500), ("dagger", 500), ("daggerdbl", 500), ("periodcentered", 250),
+    ("paragraph", 500), ("bullet", 350), ("quotesinglbase", 333), ("quotedblbase", 500), ("quotedblright", 500), ("guillemotright", 500),
+    ("ellipsis", 1000), ("perthousand", 1000), ("questiondown", 500), ("grave", 333), ("acute", 333), ("circumflex", 333),
+    ("tilde", 333), ("macron", 333), ("breve", 333), ("dotaccent", 333), ("dieresis", 333), ("ring", 333),
+    ("cedilla", 333), ("hungarumlaut", 333), ("ogonek", 333), ("caron", 333), ("emdash", 1000), ("AE", 944),
+    ("ordfeminine", 266), ("Lslash", 611), ("Oslash", 722), ("OE", 944), ("ordmasculine", 300), ("ae", 722),
+    ("dotlessi", 278), ("lslash", 278), ("oslash", 500), ("oe", 722), ("germandbls", 500), ("copyright", 747),
+    ("registered", 747), ("trademark", 1000), ("degree", 400), ("plusminus", 570), ("multiply", 570), ("divide", 570),
+    ("onehalf", 750), ("onequarter", 750), ("threequarters", 750), ("onesuperior", 300), ("twosuperior", 300), ("threesuperior", 300),
+    ("mu", 576), ("brokenbar", 220), ("logicalnot", 606), ("Eth", 722), ("eth", 500), ("Thorn", 611),
+    ("thorn", 500), ("minus", 606),
+    // Accented letters whose width differs from that of the base letter
+    ("dcaron", 608), ("lcaron", 382), ("tcaron", 366),
+];
+
+/// Codes, glyph names and widths of Symbol, in its built-in encoding.
+const SYMBOL: &[(u8, &str, u16)] = &[
+    (32, "space", 250), (33, "exclam", 333), (34, "universal", 713), (35, "numbersign", 500), (36, "existential", 549),
+    (37, "percent", 833), (38, "ampersand", 778), (39, "suchthat", 439), (40, "parenleft", 333), (41, "parenright", 333),
+    (42, "asteriskmath", 500), (43, "plus", 549), (44, "comma", 250), (45, "minus", 549), (46, "period", 250),
+    (47, "slash", 278), (48, "zero", 500), (49, "one", 500), (50, "two", 500), (51, "three", 500),
+    (52, "four", 500), (53, "five", 500), (54, "six", 500), (55, "seven", 500), (56, "eight", 500),
+    (57, "nine", 500), (58, "colon", 278), (59, "semicolon", 278), (60, "less", 549), (61, "equal", 549),
+    (62, "greater", 549), (63, "question", 444), (64, "congruent", 549), (65, "Alpha", 722), (66, "Beta", 667),
+    (67, "Chi", 722), (68, "Delta", 612), (69, "Epsilon", 611), (70, "Phi", 763), (71, "Gamma", 603),
+    (72, "Eta", 722), (73, "Iota", 333), (74, "theta1", 631), (75, "Kappa", 722), (76, "Lambda", 686),
+    (77, "Mu", 889), (78, "Nu", 722), (79, "Omicron", 722), (80, "Pi", 768), (81, "Theta", 741),
+    (82, "Rho", 556), (83, "Sigma", 592), (84, "Tau", 611), (85, "Upsilon", 690), (86, "sigma1", 439),
+    (87, "Omega", 768), (88, "Xi", 645), (89, "Psi", 795), (90, "Zeta", 611), (91, "bracketleft", 333),
+    (92, "therefore", 863), (93, "bracketright", 333), (94, "perpendicular", 658), (95, "underscore", 500), (96, "radicalex", 500),
+    (97, "alpha", 631), (98, "beta", 549), (99, "chi", 549), (100, "delta", 494), (101, "epsilon", 439),
+    (102, "phi", 521), (103, "gamma", 411), (104, "eta", 603), (105, "iota", 329), (106, "phi1", 603),
+    (107, "kappa", 549), (108, "lambda", 549), (109, "mu", 576), (110, "nu", 521), (111, "omicron", 549),
+    (112, "pi", 549), (113, "theta", 521), (114, "rho", 549), (115, "sigma", 603), (116, "tau", 439),
+    (117, "upsilon", 576), (118, "omega1", 713), (119, "omega", 686), (120, "xi", 493), (121, "psi", 686),
+    (122, "zeta", 494), (123, "braceleft", 480), (124, "bar", 200), (125, "braceright", 480), (126, "similar", 549),
+    (160, "Euro", 750), (161, "Upsilon1", 620), (162, "minute", 247), (163, "lessequal", 549), (164, "fraction", 167),
+    (165, "infinity", 713), (166, "florin", 500), (167, "club", 753), (168, "diamond", 753), (169, "heart", 753),
+    (170, "spade", 753), (171, "arrowboth", 1042), (172, "arrowleft", 987), (173, "arrowup", 603), (174, "arrowright", 987),
+    (175, "arrowdown", 603), (176, "degree", 400), (177, "plusminus", 549), (178, "second", 411), (179, "greaterequal", 549),
+    (180, "multiply", 549), (181, "proportional", 713), (182, "partialdiff", 494), (183, "bullet", 460), (184, "divide", 549),
+    (185, "notequal", 549), (186, "equivalence", 549), (187, "approxequal", 549), (188, "ellipsis", 1000), (189, "arrowvertex", 603),
+    (190, "arrowhorizex", 1000), (191, "carriagereturn", 658), (192, "aleph", 823), (193, "Ifraktur", 686), (194, "Rfraktur", 795),
+    (195, "weierstrass", 987), (196, "circlemultiply", 768), (197, "circleplus", 768), (198, "emptyset", 823), (199, "intersection", 768),
+    (200, "union", 768), (201, "propersuperset", 713), (202, "reflexsuperset", 713), (203, "notsubset", 713), (204, "propersubset", 713),
+    (205, "reflexsubset", 713), (206, "element", 713), (207, "notelement", 713), (208, "angle", 768), (209, "gradient", 713),
+    (210, "registerserif", 790), (211, "copyrightserif", 790), (212, "trademarkserif", 890), (213, "product", 823), (214, "radical", 549),
+    (215, "dotmath", 250), (216, "logicalnot", 713), (217, "logicaland", 603), (218, "logicalor", 603), (219, "arrowdblboth", 1042),
+    (220, "arrowdblleft", 987), (221, "arrowdblup", 603), (222, "arrowdblright", 987), (223, "arrowdbldown", 603), (224, "lozenge", 494),
+    (225, "angleleft", 329), (226, "registersans", 790), (227, "copyrightsans", 790), (228, "trademarksans", 786), (229, "summation", 713),
+    (230, "parenlefttp", 384), (231, "parenleftex", 384), (232, "parenleftbt", 384), (233, "bracketlefttp", 384), (234, "bracketleftex", 384),
+    (235, "bracketleftbt", 384), (236, "bracelefttp", 494), (237, "braceleftmid", 494), (238, "braceleftbt", 494), (239, "braceex", 494),
+    (241, "angleright", 329), (242, "integral", 274), (243, "integraltp", 686), (244, "integralex", 686), (245, "integralbt", 686),
+    (246, "parenrighttp", 384), (247, "parenrightex", 384), (248, "parenrightbt", 384), (249, "bracketrighttp", 384), (250, "bracketrightex", 384),
+    (251, "bracketrightbt", 384), (252, "bracerighttp", 494), (253, "bracerightmid", 494), (254, "bracerightbt", 494),
+];
+
+/// Codes, glyph names and widths of ZapfDingbats, in its built-in encoding.
+const ZAPF_DINGBATS: &[(u8, &str, u16)] = &[
+    (32, "space", 278), (33, "a1", 974), (34, "a2", 961), (35, "a202", 974), (36, "a3", 980),
+    (37, "a4", 719), (38, "a5", 789), (39, "a119", 790), (40, "a118", 791), (41, "a117", 690),
+    (42, "a11", 960), (43, "a12", 939), (44, "a13", 549), (45, "a14", 855), (46, "a15", 911),
+    (47, "a16", 933), (48, "a105", 911), (49, "a17", 945), (50, "a18", 974), (51, "a19", 755),
+    (52, "a20", 846), (53, "a21", 762), (54, "a22", 761), (55, "a23", 571), (56, "a24", 677),
+    (57, "a25", 763), (58, "a26", 760), (59, "a27", 759), (60, "a28", 754), (61, "a6", 494),
+    (62, "a7", 552), (63, "a8", 537), (64, "a9", 577), (65, "a10", 692), (66, "a29", 786),
+    (67, "a30", 788), (68, "a31", 788), (69, "a32", 790), (70, "a33", 793), (71, "a34", 794),
+    (72, "a35", 816), (73, "a36", 823), (74, "a37", 789), (75, "a38", 841), (76, "a39", 823),
+    (77, "a40", 833), (78, "a41", 816), (79, "a42", 831), (80, "a43", 923), (81, "a44", 744),
+    (82, "a45", 723), (83, "a46", 749), (84, "a47", 790), (85, "a48", 792), (86, "a49", 695),
+    (87, "a50", 776), (88, "a51", 768), (89, "a52", 792), (90, "a53", 759), (91, "a54", 707),
+    (92, "a55", 708), (93, "a56", 682), (94, "a57", 701), (95, "a58", 826), (96, "a59", 815),
+    (97, "a60", 789), (98, "a61", 789), (99, "a62", 707), (100, "a63", 687), (101, "a64", 696),
+    (102, "a65", 689), (103, "a66", 786), (104, "a67", 787), (105, "a68", 713), (106, "a69", 791),
+    (107, "a70", 785), (108, "a71", 791), (109, "a72", 873), (110, "a73", 761), (111, "a74", 762),
+    (112, "a203", 762), (113, "a75", 759), (114, "a204", 759), (115, "a76", 892), (116, "a77", 892),
+    (117, "a78", 788), (118, "a79", 784), (119, "a81", 438), (120, "a82", 138), (121, "a83", 277),
+    (122, "a84", 415), (123, "a97", 392), (124, "a98", 392), (125, "a99", 668), (126, "a100", 668),
+    (128, "a89", 390), (129, "a90", 390), (130, "a93", 317), (131, "a94", 317), (132, "a91", 276),
+    (133, "a92", 276), (134, "a205", 509), (135, "a85", 509), (136, "a206", 410), (137, "a86", 410),
+    (138, "a87", 234), (139, "a88", 234), (140, "a95", 334), (141, "a96", 334), (161, "a101", 732),
+    (162, "a102", 544), (163, "a103", 544), (164, "a104", 910), (165, "a106", 667), (166, "a107", 760),
+    (167, "a108", 760), (168, "a112", 776), (169, "a111", 595), (170, "a110", 694), (171, "a109", 626),
+    (172, "a120", 788), (173, "a121", 788), (174, "a122", 788), (175, "a123", 788), (176, "a124", 788),
+    (177, "a125", 788), (178, "a126", 788), (179, "a127", 788), (180, "a128", 788), (181, "a129", 788),
+    (182, "a130", 788), (183, "a131", 788), (184, "a132", 788), (185, "a133", 788), (186, "a134", 788),
+    (187, "a135", 788), (188, "a136", 788), (189, "a137", 788), (190, "a138", 788), (191, "a139", 788),
+    (192, "a140", 788), (193, "a141", 788), (194, "a142", 788), (195, "a143", 788), (196, "a144", 788),
+    (197, "a145", 788), (198, "a146", 788), (199, "a147", 788), (200, "a148", 788), (201, "a149", 788),
+    (202, "a150", 788), (203, "a151", 788), (204, "a152", 788), (205, "a153", 788), (206, "a154", 788),
+    (207, "a155", 788), (208, "a156", 788), (209, "a157", 788), (210, "a158", 788), (211, "a159", 788),
+    (212, "a160", 894), (213, "a161", 838), (214, "a163", 1016), (215, "a164", 458), (216, "a196", 748),
+    (217, "a165", 924), (218, "a192", 748), (219, "a166", 918), (220, "a167", 927), (221, "a168", 928),
+    (222, "a169", 928), (223, "a170", 834), (224, "a171", 873), (225, "a172", 828), (226, "a173", 924),
+    (227, "a162", 924), (228, "a174", 917), (229, "a175", 930), (230, "a176", 931), (231, "a177", 463),
+    (232, "a178", 883), (233, "a179", 836), (234, "a193", 836), (235, "a180", 867), (236, "a199", 867),
+    (237, "a181", 696), (238, "a200", 696), (239, "a182", 874), (241, "a201", 874), (242, "a183", 760),
+    (243, "a184", 946), (244, "a197", 771), (245, "a185", 865), (246, "a194", 771), (247, "a198", 888),
+    (248, "a186", 967), (249, "a195", 888), (250, "a187", 831), (251, "a188", 873), (252, "a189", 927),
+    (253, "a190", 970), (254, "a191", 918),
+];
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard14() {
+        let helv = Standard14::from_name(b"Helvetica").unwrap();
+        assert_eq!(helv.glyph_width("space"), Some(278));
+        assert_eq!(helv.glyph_width("A"), Some(667));
+        assert_eq!(helv.glyph_width("Aacute"), Some(667));
+        assert_eq!(helv.glyph_width("alpha"), None);
+        assert_eq!(helv.code_width(b' ', None), Some(278));
+        assert_eq!(helv.code_width(b'A', Some(Encoding::WinAnsi)), Some(667));
+        assert_eq!(helv.code_width(0xE9, Some(Encoding::WinAnsi)), Some(556));
+        let courier = Standard14::from_name(b"Courier-Bold").unwrap();
+        assert_eq!(courier.glyph_width("W"), Some(600));
+        assert_eq!(courier.glyph_width("i"), Some(600));
+        assert_eq!(courier.glyph_width("Eacute"), Some(600));
+        assert_eq!(courier.glyph_width("Euro"), Some(600));
+        assert!((0..=255).filter_map(|code| courier.code_width(code, Some(Encoding::WinAnsi))).all(|width| width == 600));
+        assert_eq!(helv.glyph_width("i"), Some(222));
+        assert_eq!(helv.glyph_width("iacute"), Some(278));
+        assert_eq!(helv.glyph_width("dcaron"), Some(643));
+        assert_eq!(helv.glyph_width("Dcaron"), Some(722));
+        assert_eq!(Standard14::from_name(b"Times-BoldItalic").unwrap().glyph_width("tcaron"), Some(366));
+        assert_eq!(Standard14::from_name(b"Times-Roman").unwrap().code_width(b'i', None), Some(278));
+        assert_eq!(Standard14::from_name(b"Symbol").unwrap().code_width(b'a', None), Some(631));
+        assert_eq!(Standard14::from_name(b"ZapfDingbats").unwrap().glyph_width("a1"), Some(974));
+        assert_eq!(Standard14::from_name(b"Arial"), None);
+    }
+}
@@ -0,0 +1,263 @@
+//! Decoding of image XObjects into RGB samples.
+
+use std::io::{BufRead, Read, Seek};
+
+use crate::base::*;
+use crate::codecs;
+use crate::colorspace::ColorSpace;
+use crate::reader::SimpleReader;
+
+/// A decoded image, converted to 8-bit RGB.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    /// The color samples, three bytes per pixel, row by row from the top.
+    pub rgb: Vec<u8>,
+    /// The opacity of each pixel, if the image has a soft mask (`/SMask`) or a mask (`/Mask`). 
+    /// Has the same dimensions as the image.
+    pub alpha: Option<Vec<u8>>,
+}
+
+impl Image {
+    /// Combines the color samples and the opacity into four bytes per pixel. Pixels are opaque if 
+    /// the image has no mask.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.rgb.chunks(3)
+            .enumerate()
+            .flat_map(|(ix, rgb)| rgb.iter().copied()
+                .chain([self.alpha.as_ref().map_or(255, |alpha| alpha[ix])]))
+            .collect()
+    }
+}
+
+/// Raw samples of an image or a mask, each widened to `u16`.
+struct Samples {
+    width: usize,
+    height: usize,
+    bpc: u32,
+    data: Vec<u16>,
+}
+
+impl Samples {
+    fn read<T: BufRead + Seek>(stm: &RefStream, reader: &SimpleReader<T>, comps: usize) -> Result<Samples, Error> {
+        if codecs::image_format(&reader.stream_filters(stm)?).is_some() {
+            return Err(Error::Parse("unsupported image compression"));
+        }
+        let num = |key: &[u8]| -> Result<Option<usize>, Error> {
+            Ok(reader.resolve_obj(stm.dict.lookup(key).to_owned())?.num_value())
+        };
+        let (Some(width @ 1..), Some(height @ 1..)) = (num(b"Width")?, num(b"Height")?) else {
+            return Err(Error::Parse("malformed image (/Width, /Height)"));
+        };
+        let bpc = match reader.resolve_obj(stm.dict.lookup(b"ImageMask").to_owned())? {
+            Object::Bool(true) => 1,
+            _ => match num(b"BitsPerComponent")? {
+                Some(bpc @ (1 | 2 | 4 | 8 | 16)) => bpc as u32,
+                _ => return Err(Error::Parse("malformed image (/BitsPerComponent)"))
+            }
+        };
+        let Some((row_len, data_len)) = width.checked_mul(comps)
+            .and_then(|n| n.checked_mul(bpc as usize))
+            .map(|bits| bits.div_ceil(8))
+            .and_then(|row_len| Some((row_len, row_len.checked_mul(height)?)))
+        else {
+            return Err(Error::Parse("malformed image (/Width, /Height)"));
+        };
+        let mut raw = Vec::new();
+        reader.read_stream_data(stm)?.read_to_end(&mut raw)?;
+        if raw.len() < data_len {
+            return Err(Error::Parse("image data too short"));
+        }
+        let mut data = Vec::with_capacity(width * height * comps);
+        for row in raw.chunks(row_len).take(height) {
+            data.extend((0..width * comps).map(|ix| {
+                let bit = ix * bpc as usize;
+                match bpc {
+                    16 => u16::from_be_bytes([row[bit / 8], row[bit / 8 + 1]]),
+                    8 => row[bit / 8] as u16,
+                    _ => ((row[bit / 8] >> (8 - bpc - (bit % 8) as u32)) & ((1 << bpc) - 1)) as u16
+                }
+            }));
+        }
+        Ok(Samples { width, height, bpc, data })
+    }
+
+    fn max(&self) -> f64 {
+        ((1u32 << self.bpc) - 1) as f64
+    }
+
+    /// Reads the `/Decode` array, or returns `default` if there is none.
+    fn decode<T: BufRead + Seek>(&self, stm: &RefStream, reader: &SimpleReader<T>, default: Vec<f64>) -> Result<Vec<f64>, Error> {
+        match reader.resolve_deep(stm.dict.lookup(b"Decode").to_owned())? {
+            Object::Null => Ok(default),
+            Object::Array(arr) if arr.len() == default.len() => arr.iter()
+                .map(|obj| match obj {
+                    Object::Number(num) => Ok(num.as_f64()),
+                    _ => Err(Error::Parse("malformed image (/Decode)"))
+                })
+                .collect(),
+            _ => Err(Error::Parse("malformed image (/Decode)"))
+        }
+    }
+
+    /// Reads a single-component mask, scaled to the dimensions of the base image by the nearest 
+    /// neighbour method. The decoded values are mapped through `map`.
+    fn resampled(&self, decode: &[f64], width: usize, height: usize, map: impl Fn(f64) -> u8) -> Vec<u8> {
+        let (dmin, dmax) = (decode[0], decode[1]);
+        let mut ret = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let sy = y * self.height / height;
+            for x in 0..width {
+                let sx = x * self.width / width;
+                let sample = self.data[sy * self.width + sx] as f64;
+                ret.push(map(dmin + sample * (dmax - dmin) / self.max()));
+            }
+        }
+        ret
+    }
+}
+
+/// Decodes an image XObject into 8-bit RGB samples.
+///
+/// If the image has a soft mask (`/SMask`), it is decoded as well and provides the opacity of 
+/// each pixel, resampled to the dimensions of the image if they differ. Otherwise, a `/Mask` is 
+/// taken into account: either an image mask stream, whose samples of 1 mark transparent pixels, 
+/// or an array of color key ranges, matched against the raw color samples.
+///
+/// In an `/Indexed` color space, each sample is an index into the palette, which is unpacked 
+/// for any `/BitsPerComponent` and mapped through the palette to the base color space. Indices 
+/// above `hival` are clamped, reporting [`Event::PaletteIndexOutOfRange`].
+///
+/// Images compressed by `/DCTDecode`, `/JPXDecode` or an unsupported variant of 
+/// `/CCITTFaxDecode` result in an error, as do image masks (`/ImageMask true`).
+pub fn decode_image<T: BufRead + Seek>(stm: &RefStream, reader: &SimpleReader<T>) -> Result<Image, Error> {
+    if reader.resolve_obj(stm.dict.lookup(b"ImageMask").to_owned())? == Object::Bool(true) {
+        return Err(Error::Parse("image masks have no color space"));
+    }
+    let cs = ColorSpace::parse(stm.dict.lookup(b"ColorSpace"), reader)?;
+    let comps = cs.component_count();
+    let samples = Samples::read(stm, reader, comps)?;
+    let default = match cs {
+        ColorSpace::Indexed { .. } => vec![0., samples.max()],
+        _ => [0., 1.].repeat(comps)
+    };
+    let decode = samples.decode(stm, reader, default)?;
+    let (width, height) = (samples.width, samples.height);
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    let mut color = vec![0.; comps];
+    let hival = match &cs {
+        ColorSpace::Indexed { hival, .. } => Some(*hival as f64),
+        _ => None
+    };
+    let mut out_of_range = false;
+    for pixel in samples.data.chunks(comps) {
+        for (ix, &sample) in pixel.iter().enumerate() {
+            let (dmin, dmax) = (decode[2 * ix], decode[2 * ix + 1]);
+            color[ix] = dmin + sample as f64 * (dmax - dmin) / samples.max();
+        }
+        out_of_range |= hival.is_some_and(|hival| color[0].round() > hival);
+        rgb.extend(cs.to_rgb(&color).map(|c| (c * 255.).round() as u8));
+    }
+    if out_of_range {
+        reader.parser().diagnostics().report(Event::PaletteIndexOutOfRange);
+    }
+    let alpha = match (stm.dict.lookup(b"SMask"), reader.resolve_obj(stm.dict.lookup(b"Mask").to_owned())?) {
+        (Object::Null, Object::Null) => None,
+        (Object::Null, Object::Array(ranges)) => {
+            let ranges = ranges.iter()
+                .map(|obj| reader.resolve_obj(obj.to_owned())?.num_value::<u16>()
+                    .ok_or(Error::Parse("malformed color key mask")))
+                .collect::<Result<Vec<_>, _>>()?;
+            if ranges.len() != 2 * comps {
+                return Err(Error::Parse("malformed color key mask"));
+            }
+            Some(samples.data.chunks(comps)
+                .map(|pixel| {
+                    let masked = pixel.iter()
+                        .zip(ranges.chunks(2))
+                        .all(|(&sample, range)| (range[0]..=range[1]).contains(&sample));
+                    if masked { 0 } else { 255 }
+                })
+                .collect())
+        },
+        (Object::Null, Object::Stream(mask)) => {
+            let mask_samples = Samples::read(&mask, reader, 1)?;
+            let decode = mask_samples.decode(&mask, reader, vec![0., 1.])?;
+            Some(mask_samples.resampled(&decode, width, height, |val| if val >= 0.5 { 0 } else { 255 }))
+        },
+        (Object::Null, _) => return Err(Error::Parse("malformed image (/Mask)")),
+        (smask, _) => {
+            let smask = reader.resolve_stream(smask)?;
+            let mask_samples = Samples::read(&smask, reader, 1)?;
+            let decode = mask_samples.decode(&smask, reader, vec![0., 1.])?;
+            Some(mask_samples.resampled(&decode, width, height, |val| (val.clamp(0., 1.) * 255.).round() as u8))
+        }
+    };
+    Ok(Image { width, height, rgb, alpha })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use crate::utils::{self, Capture};
+
+    #[test]
+    fn test_decode_image() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Type /XObject /Subtype /Image /Width 2 /Height 2 /ColorSpace /DeviceRGB /BitsPerComponent 8 \
+                /SMask 3 0 R /Filter /ASCIIHexDecode /Length 28 >>\nstream\nFF0000 00FF00 0000FF FFFFFF>\nendstream",
+            "<< /Type /XObject /Subtype /Image /Width 1 /Height 2 /ColorSpace /DeviceGray /BitsPerComponent 4 \
+                /Filter /ASCIIHexDecode /Length 6 >>\nstream\nF0 80>\nendstream",
+            "<< /Type /XObject /Subtype /Image /Width 3 /Height 1 /ColorSpace [/Indexed /DeviceGray 3 <00406080>] \
+                /BitsPerComponent 2 /Mask [1 2] /Filter /ASCIIHexDecode /Length 3 >>\nstream\n18>\nendstream",
+            "<< /Type /XObject /Subtype /Image /Width 3 /Height 2 /ColorSpace [/Indexed /DeviceRGB 2 <FF000000FF000000FF>] \
+                /BitsPerComponent 4 /Filter /ASCIIHexDecode /Length 9 >>\nstream\n0120 2F00>\nendstream",
+        ]);
+        let capture = Arc::new(Capture::default());
+        let rdr = SimpleReader::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 2, gen: 0 })).unwrap();
+        let img = decode_image(&stm, &rdr).unwrap();
+        assert_eq!((img.width, img.height), (2, 2));
+        assert_eq!(img.to_rgba(), vec![
+            255, 0, 0, 255, 0, 255, 0, 255,
+            0, 0, 255, 136, 255, 255, 255, 136]);
+
+        // 4-bit palette indices, the last one out of range
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 5, gen: 0 })).unwrap();
+        let img = decode_image(&stm, &rdr).unwrap();
+        assert_eq!((img.width, img.height), (3, 2));
+        assert_eq!(img.rgb, vec![
+            255, 0, 0, 0, 255, 0, 0, 0, 255,
+            0, 0, 255, 0, 0, 255, 255, 0, 0]);
+        assert_eq!(img.alpha, None);
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::PaletteIndexOutOfRange]));
+
+        // Color key mask
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 4, gen: 0 })).unwrap();
+        let img = decode_image(&stm, &rdr).unwrap();
+        assert_eq!(img.rgb, vec![0, 0, 0, 64, 64, 64, 96, 96, 96]);
+        assert_eq!(img.alpha, Some(vec![255, 0, 0]));
+        assert_eq!(capture.0.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_huge_dimensions() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Type /XObject /Subtype /Image /Width 4294967296 /Height 4294967296 /ColorSpace /DeviceRGB \
+                /BitsPerComponent 8 /Length 3 >>\nstream\nabc\nendstream",
+            "<< /Type /XObject /Subtype /Image /Width 9223372036854775807 /Height 1 /ColorSpace /DeviceGray \
+                /BitsPerComponent 16 /Length 3 >>\nstream\nabc\nendstream",
+        ]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        for num in [2, 3] {
+            let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num, gen: 0 })).unwrap();
+            assert!(matches!(decode_image(&stm, &rdr), Err(Error::Parse("malformed image (/Width, /Height)"))));
+        }
+    }
+}
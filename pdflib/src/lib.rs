@@ -1,7 +1,17 @@
+#[macro_use]
+mod macros;
+
 mod base;
 pub mod parser;
 pub mod codecs;
+pub mod content;
+pub mod cmap;
+pub mod fonts;
+pub mod colorspace;
+pub mod image;
 pub mod reader;
+pub mod document;
+pub mod writer;
 mod utils;
 
 pub use base::*;
@@ -0,0 +1,32 @@
+//! Internal logging macros. With the `log` feature enabled, these forward to the macros of the 
+//! `log` crate. Without it, the messages are discarded.
+
+#[cfg(feature = "log")]
+macro_rules! log_info {
+    ($($arg:tt)+) => { log::info!($($arg)+) }
+}
+
+#[cfg(feature = "log")]
+macro_rules! log_warn {
+    ($($arg:tt)+) => { log::warn!($($arg)+) }
+}
+
+#[cfg(feature = "log")]
+macro_rules! log_error {
+    ($($arg:tt)+) => { log::error!($($arg)+) }
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_info {
+    ($($arg:tt)+) => { { let _ = format_args!($($arg)+); } }
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_warn {
+    ($($arg:tt)+) => { { let _ = format_args!($($arg)+); } }
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_error {
+    ($($arg:tt)+) => { { let _ = format_args!($($arg)+); } }
+}
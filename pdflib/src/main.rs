@@ -4,6 +4,7 @@ use std::io::{BufReader, Read, Cursor, BufRead, Seek};
 use std::fs::File;
 
 fn main() -> Result<(), pdflib::Error> {
+    #[cfg(feature = "log")]
     stderrlog::new()
         .verbosity(log::Level::Trace)
         .init()
@@ -3,7 +3,7 @@ use std::io::BufRead;
 pub trait ByteProvider: BufRead {
     fn peek(&mut self) -> Option<u8> {
         match self.fill_buf() {
-            Ok(buf) => Some(buf[0]),
+            Ok(buf) => buf.first().copied(),
             _ => None
         }
     }
@@ -79,6 +79,7 @@ pub trait ByteProvider: BufRead {
         self.read_line_inner(false)
     }
 
+    #[allow(dead_code)]
     fn read_line_incl(&mut self) -> std::io::Result<Vec<u8>> {
         self.read_line_inner(true)
     }
@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
 use std::cell::RefCell;
 use std::ops::DerefMut;
+use std::sync::Arc;
 
 use crate::base::*;
 use crate::base::types::*;
@@ -10,13 +11,14 @@ use crate::utils;
 use crate::codecs;
 
 use super::bp::ByteProvider;
-use super::op::ObjParser;
+use super::op::{ObjParser, ParseOptions};
 use super::tk::Tokenizer;
 
 /// The main interface to a file-level PDF parsing.
 pub struct FileParser<T: BufRead + Seek> {
     reader: RefCell<T>,
     header: Result<Header, Error>,
+    opts: ParseOptions,
 }
 
 pub enum Structural {
@@ -27,21 +29,60 @@ pub enum Structural {
 impl<T: BufRead + Seek> FileParser<T> {
     /// Creates a `FileParser` instance with the provided `BufRead`.
     ///
-    /// Locates the PDF header, determining the PDF version and its byte offset within the stream.
+    /// Locates the PDF header, determining the PDF version and its byte offset within the stream. 
     /// This information, along with the possible errors) is later available through a call to 
     /// [`FileParser::header()`].
     pub fn new(mut reader: T) -> Self {
         let header = Self::find_header(&mut reader);
         match &header {
             Ok(Header { start, version }) => {
-                log::info!("PDF version {}.{}", version.0, version.1);
+                log_info!("PDF version {}.{}", version.0, version.1);
                 if *start != 0 {
-                    log::info!("Offset start @ {start}");
+                    log_info!("Offset start @ {start}");
                 }
             },
-            Err(err) => log::warn!("{}", err)
+            Err(err) => log_warn!("{}", err)
         }
-        Self { reader: RefCell::new(reader), header }
+        Self { reader: RefCell::new(reader), header, opts: ParseOptions::default() }
+    }
+
+    /// Replaces the [`Diagnostics`] sink receiving reports of recoverable problems. By default, 
+    /// these are forwarded to the `log` crate using [`LogDiagnostics`].
+    pub fn set_diagnostics(&mut self, diag: Arc<dyn Diagnostics>) {
+        self.opts.diagnostics = diag;
+    }
+
+    /// The [`Diagnostics`] sink of this `FileParser`.
+    pub fn diagnostics(&self) -> &Arc<dyn Diagnostics> {
+        &self.opts.diagnostics
+    }
+
+    /// Sets whether objects are parsed leniently, see [`ObjParser::read_obj_with()`]. This also 
+    /// allows a lone carriage return after the `stream` keyword. Defaults to `false`.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.opts.lenient = lenient;
+    }
+
+    /// Sets the maximum length of name tokens, see [`ParseOptions::max_name_len`]. Defaults to 
+    /// [`MAX_NAME_LEN`](super::MAX_NAME_LEN).
+    pub fn set_max_name_len(&mut self, len: usize) {
+        self.opts.max_name_len = len;
+    }
+
+    /// Sets the maximum nesting depth of arrays and dictionaries, see 
+    /// [`ParseOptions::max_depth`]. Defaults to [`MAX_DEPTH`](super::MAX_DEPTH).
+    pub fn set_max_depth(&mut self, depth: usize) {
+        self.opts.max_depth = depth;
+    }
+
+    /// The [`ParseOptions`] used for reading objects.
+    pub fn parse_options(&self) -> &ParseOptions {
+        &self.opts
+    }
+
+    /// Replaces all the [`ParseOptions`] at once.
+    pub fn set_parse_options(&mut self, opts: ParseOptions) {
+        self.opts = opts;
     }
 
     fn start(&self) -> Offset {
@@ -67,7 +108,7 @@ impl<T: BufRead + Seek> FileParser<T> {
     }
 
     fn find_header(reader: &mut T) -> Result<Header, Error> {
-        const BUF_SIZE: usize = 1024;
+        const BUF_SIZE: Offset = 1024;
         const HEADER_FIXED: &[u8] = b"%PDF-";
         const HEADER_FIXED_LEN: usize = HEADER_FIXED.len();
         const HEADER_FULL_LEN: usize = HEADER_FIXED_LEN + 3;
@@ -75,15 +116,15 @@ impl<T: BufRead + Seek> FileParser<T> {
 
         let mut data = vec![0u8; HEADER_FULL_LEN];
         let mut from = 0;
-        let mut to = data.len();
+        let mut to = HEADER_FULL_LEN as Offset;
         use std::ops::ControlFlow;
-        let try_find = |data: &[u8], from: usize| {
+        let try_find = |data: &[u8], from: Offset| {
             data.windows(HEADER_FULL_LEN)
                 .enumerate()
                 .filter(|(_, w)| w[0..HEADER_FIXED_LEN] == *HEADER_FIXED)
                 .try_fold((), |(), (ix, w)| match &w[HEADER_FIXED_LEN..] {
                     [maj @ b'0'..=b'9', b'.', min @ b'0'..=b'9'] => {
-                        let start = (from + ix).try_into().expect("Should fit into u64.");
+                        let start = from + ix as Offset;
                         let version = (maj - b'0', min - b'0');
                         ControlFlow::Break(Header { start, version })
                     },
@@ -92,8 +133,7 @@ impl<T: BufRead + Seek> FileParser<T> {
                 .break_value()
         };
 
-        let file_len = reader.seek(std::io::SeekFrom::End(0))?
-            .try_into().expect("File length should fit into usize.");
+        let file_len = reader.seek(std::io::SeekFrom::End(0))?;
         reader.seek(std::io::SeekFrom::Start(0))?;
 
         reader.read_exact(&mut data)?;
@@ -104,9 +144,10 @@ impl<T: BufRead + Seek> FileParser<T> {
         while to < file_len {
             let data_len = data.len();
             data.copy_within((data_len - OVERLAP).., 0);
-            from = to - OVERLAP;
+            from = to - OVERLAP as Offset;
             to = std::cmp::min(from + BUF_SIZE, file_len);
-            data.resize(to - from, 0u8);
+            // At most BUF_SIZE, so this fits into usize on all platforms.
+            data.resize((to - from) as usize, 0u8);
             reader.read_exact(&mut data[OVERLAP..])?;
             if let Some(header) = try_find(&data, from) {
                 return Ok(header);
@@ -122,32 +163,71 @@ impl<T: BufRead + Seek> FileParser<T> {
         &self.header
     }
 
+    /// Returns the total length of the input in bytes, including any data preceding `%PDF`.
+    pub fn file_len(&self) -> Result<Offset, Error> {
+        Ok(self.reader.borrow_mut().seek(std::io::SeekFrom::End(0))?)
+    }
+
     /// Tries to locate the cross-reference entry point (`startxref`).
     ///
-    /// The last 1024 bytes of the byte stream are inspected.
+    /// The last 1024 bytes of the byte stream are inspected. Incrementally updated files may 
+    /// contain several `startxref` sections, so the last one followed by a valid offset and 
+    /// `%%EOF` is used. If the cross-reference section it points to can not be read, the previous 
+    /// ones are tried.
     pub fn entrypoint(&self) -> Result<Offset, Error> {
+        let candidates = self.find_startxrefs()?;
+        let last = candidates[0];
+        for &offset in &candidates {
+            if self.read_xref_at(offset).is_ok() {
+                return Ok(offset);
+            }
+            self.opts.diagnostics.report(Event::InvalidStartXRef(offset));
+        }
+        Ok(last)
+    }
+
+    /// Finds all well-formed `startxref` sections in the last 1024 bytes of the byte stream and 
+    /// returns their offsets, starting from the last one.
+    fn find_startxrefs(&self) -> Result<Vec<Offset>, Error> {
         let mut reader = self.reader.borrow_mut();
         let len = reader.seek(std::io::SeekFrom::End(0))?;
         let buf_size = std::cmp::min(len, 1024);
 
         // Read last 1024 bytes
-        reader.seek(std::io::SeekFrom::End(-(buf_size as i64)))?;
+        reader.seek(std::io::SeekFrom::Start(len - buf_size))?;
         // FIXME: use read_buf_exact when stabilized
         let mut data = vec![0; buf_size as usize];
         reader.read_exact(&mut data)?;
 
-        // Find "startxref<EOL>number<EOL>"
+        // Find "startxref<EOL>number ... %%EOF"
         const SXREF: &[u8] = b"startxref";
-        let sxref = data.windows(SXREF.len())
-            .rposition(|w| w == b"startxref")
-            .ok_or(Error::Parse("startxref not found"))?;
-        let mut cur = Cursor::new(&data[(sxref+SXREF.len())..]);
-        cur.read_eol()?;
-        let sxref = utils::parse_num(&cur.read_line_excl()?).ok_or(Error::Parse("malformed startxref"))?;
-        Ok(sxref)
+        let parse = |pos: usize| -> Result<Offset, Error> {
+            let mut cur = Cursor::new(&data[(pos+SXREF.len())..]);
+            cur.read_eol()?;
+            let line = cur.read_line_excl()?;
+            let digits = line.iter().position(|c| !c.is_ascii_digit()).unwrap_or(line.len());
+            let sxref = utils::parse_num(&line[..digits]).ok_or(Error::Parse("malformed startxref"))?;
+            // Any junk following the number is ignored up to %%EOF. A missing %%EOF is tolerated 
+            // at the very end of the file.
+            let rest = &data[(pos+SXREF.len()+cur.position() as usize)..];
+            if !rest.iter().all(u8::is_ascii_whitespace) && !rest.windows(5).any(|w| w == b"%%EOF") {
+                return Err(Error::Parse("malformed startxref"));
+            }
+            Ok(sxref)
+        };
+        let candidates = data.windows(SXREF.len())
+            .enumerate()
+            .rev()
+            .filter(|(_, w)| w == &SXREF)
+            .filter_map(|(pos, _)| parse(pos).ok())
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Err(Error::Parse("startxref not found"));
+        }
+        Ok(candidates)
     }
 
-    fn read_at(&self, pos: Offset) -> Result<Structural, Error> {
+    pub(crate) fn read_at(&self, pos: Offset) -> Result<Structural, Error> {
         let mut reader = self.reader.borrow_mut();
         reader.seek(std::io::SeekFrom::Start(pos + self.start()))?;
         let tk = reader.read_token()?;
@@ -165,7 +245,7 @@ impl<T: BufRead + Seek> FileParser<T> {
         if reader.read_token()? != b"obj" {
             return Err(Error::Parse("unexpected token"));
         }
-        let obj = ObjParser::read_obj(&mut *reader)?;
+        let obj = ObjParser::read_obj_with(&mut *reader, &self.opts)?;
         match &reader.read_token()?[..] {
             b"endobj" =>
                 Ok(Structural::Object(oref, obj)),
@@ -175,8 +255,11 @@ impl<T: BufRead + Seek> FileParser<T> {
                 match reader.next_or_eof()? {
                     b'\n' => (),
                     b'\r' => {
-                        if reader.next_or_eof()? != b'\n' {
-                            return Err(Error::Parse("stream keyword not followed by proper EOL"));
+                        if reader.next_if(|c| c == b'\n').is_none() {
+                            if !self.opts.lenient {
+                                return Err(Error::Parse("stream keyword not followed by proper EOL"));
+                            }
+                            self.opts.diagnostics.report(Event::StreamKeywordCR(reader.stream_position()?));
                         }
                     },
                     _ => return Err(Error::Parse("stream keyword not followed by proper EOL"))
@@ -189,6 +272,42 @@ impl<T: BufRead + Seek> FileParser<T> {
         }
     }
 
+    /// Returns the current position of the underlying reader (relative to `%PDF`).
+    pub(crate) fn position(&self) -> Result<Offset, Error> {
+        Ok(self.reader.borrow_mut().stream_position()? - self.start())
+    }
+
+    /// Skips whitespace, comments and `startxref` sections from the specified location (relative 
+    /// to `%PDF`). Returns the location of the next token, or `None` if the end of input was 
+    /// reached.
+    pub(crate) fn skip_to_next(&self, pos: Offset) -> Result<Option<Offset>, Error> {
+        let mut reader = self.reader.borrow_mut();
+        reader.seek(std::io::SeekFrom::Start(pos + self.start()))?;
+        loop {
+            reader.skip_ws()?;
+            if reader.peek().is_none() {
+                return Ok(None);
+            }
+            let next = reader.stream_position()? - self.start();
+            if reader.read_token()? != b"startxref" {
+                return Ok(Some(next));
+            }
+            reader.read_token()?;
+        }
+    }
+
+    /// Reads the `endstream` and `endobj` keywords at the specified location, which should be the 
+    /// end of stream data. Like stream data offsets, `pos` is not adjusted by the start of `%PDF`. 
+    /// Returns the location following the keywords, relative to `%PDF`.
+    pub(crate) fn read_stream_end(&self, pos: Offset) -> Result<Offset, Error> {
+        let mut reader = self.reader.borrow_mut();
+        reader.seek(std::io::SeekFrom::Start(pos))?;
+        if reader.read_token()? != b"endstream" || reader.read_token()? != b"endobj" {
+            return Err(Error::Parse("endstream not found"));
+        }
+        Ok(reader.stream_position()? - self.start())
+    }
+
     /// Attempts to read an indirect object at the specified location (relative to `%PDF`).
     pub fn read_obj_at(&self, pos: Offset) -> Result<(ObjRef, Object), Error> {
         match self.read_at(pos)? {
@@ -197,6 +316,17 @@ impl<T: BufRead + Seek> FileParser<T> {
         }
     }
 
+    /// Like [`FileParser::read_obj_at()`], but fails if the object found at the specified location 
+    /// is not `oref`.
+    pub fn read_obj_at_checked(&self, pos: Offset, oref: &ObjRef) -> Result<Object, Error> {
+        let (oref_found, obj) = self.read_obj_at(pos)?;
+        if &oref_found == oref {
+            Ok(obj)
+        } else {
+            Err(Error::Parse("object number mismatch"))
+        }
+    }
+
     /// Attempts to read a cross-reference table section or a cross-reference stream object at the 
     /// specified location (relative to `%PDF`).
     pub fn read_xref_at(&self, pos: Offset) -> Result<XRef, Error> {
@@ -230,11 +360,11 @@ impl<T: BufRead + Seek> FileParser<T> {
                 };
                 match map.entry(num) {
                     Entry::Vacant(entry) => { entry.insert(rec); },
-                    Entry::Occupied(_) => log::warn!("Duplicate object number {num} in xref table")
+                    Entry::Occupied(_) => self.opts.diagnostics.report(Event::DuplicateXRefEntry(num))
                 };
             }
         }
-        let trailer = match ObjParser::read_obj(reader)? {
+        let trailer = match ObjParser::read_obj_with(reader, &self.opts)? {
             Object::Dict(dict) => dict,
             _ => return Err(Error::Parse("malformed trailer"))
         };
@@ -244,24 +374,39 @@ impl<T: BufRead + Seek> FileParser<T> {
         Ok(XRef { tpe: XRefType::Table, map, dict: trailer, size })
     }
 
-    fn read_xref_stream(&self, oref: ObjRef, obj: Object) -> Result<XRef, Error> {
+    pub(crate) fn read_xref_stream(&self, oref: ObjRef, obj: Object) -> Result<XRef, Error> {
         let mut reader = self.reader.borrow_mut();
-        let Object::Stream(RefStream{dict, data: offset}) = obj else {
-            return Err(Error::Parse("malfomed xref"))
+        let Object::Stream(stm) = obj else {
+            return Err(Error::Parse("object found instead of xref section"))
         };
-        if dict.lookup(b"Type") != &Object::new_name(b"XRef") {
-            return Err(Error::Parse("malfomed xref stream (/Type)"))
+        if !stm.is_xref() {
+            return Err(Error::Parse("stream found instead of xref section (not /Type /XRef)"))
         }
-        let size = dict.lookup(b"Size").num_value()
-            .ok_or(Error::Parse("malfomed xref stream (/Size)"))?;
-        let index = match dict.lookup(b"Index") {
-            Object::Array(arr) =>
+        let RefStream{dict, data: offset} = stm;
+        let size = match dict.lookup(b"Size") {
+            Object::Null => None,
+            obj => Some(obj.num_value().ok_or(Error::Parse("malfomed xref stream (/Size)"))?)
+        };
+        let index = match (dict.lookup(b"Index"), size) {
+            (Object::Array(arr), _) =>
                 arr.iter()
                     .map(|obj| obj.num_value().ok_or(Error::Parse("malfomed xref stream (/Index)")))
                     .collect::<Result<Vec<_>, _>>()?,
-            Object::Null => vec![0, size],
+            (Object::Null, Some(size)) => vec![0, size],
+            (Object::Null, None) => return Err(Error::Parse("malfomed xref stream (/Size)")),
             _ => return Err(Error::Parse("malfomed xref stream (/Index)"))
         };
+        // Without /Size, the range covered by /Index is taken as the size.
+        let size = match size {
+            Some(size) => size,
+            None => {
+                self.opts.diagnostics.report(Event::MissingXRefSize(oref));
+                index.chunks_exact(2)
+                    .map(|ch: &[ObjNum]| ch[0].saturating_add(ch[1]))
+                    .max()
+                    .unwrap_or(0)
+            }
+        };
 
         let [w1, w2, w3] = match dict.lookup(b"W") {
             Object::Array(arr) =>
@@ -273,17 +418,21 @@ impl<T: BufRead + Seek> FileParser<T> {
                     .collect::<Result<Vec<_>, _>>()?,
             _ => return Err(Error::Parse("malfomed xref stream (/W)"))
         }.try_into().map_err(|_| Error::Parse("malfomed xref stream (/W)"))?;
+        // A zero width means the field is omitted and takes its default value: type 1 for the 
+        // first field and zero for the third (generation number or index within an object 
+        // stream). The second field has no meaningful default and must be present.
         if w2 == 0 {
-            return Err(Error::Parse("malfomed xref stream (/W)"))
+            return Err(Error::Parse("malfomed xref stream (/W: second field can not be omitted)"))
         }
 
         assert_eq!(reader.stream_position()?, offset);
         let len = dict.lookup(b"Length")
             .num_value()
             .ok_or(Error::Parse("malfomed xref stream (/Length)"))?;
-        let filters = codecs::parse_filters(&dict, &())?;
+        let diag = &*self.opts.diagnostics;
+        let filters = codecs::parse_filters_with_diagnostics(&dict, &(), diag)?;
         let codec_in = reader.deref_mut().take(len);
-        let mut codec_out = codecs::decode(codec_in, &filters);
+        let mut codec_out = codecs::decode_with_diagnostics(codec_in, &filters, diag);
         let mut read = |w| -> Result<u64, Error> {
             let mut dec_buf = [0; 8];
             codec_out.read_exact(&mut dec_buf[(8-w)..8])?;
@@ -297,7 +446,8 @@ impl<T: BufRead + Seek> FileParser<T> {
             for num in start..(start + len) {
                 let tpe = if w1 > 0 { read(w1)? } else { 1 };
                 let f2 = read(w2)?;
-                let f3 = read(w3)?.try_into().expect("Generation field larger than 16 bits.");
+                let f3 = if w3 > 0 { read(w3)? } else { 0 }
+                    .try_into().expect("Generation field larger than 16 bits.");
                 let rec = match tpe {
                     0 => Record::Free{gen: f3, next: f2},
                     1 => Record::Used{gen: f3, offset: f2},
@@ -306,7 +456,7 @@ impl<T: BufRead + Seek> FileParser<T> {
                 };
                 match map.entry(num) {
                     Entry::Vacant(entry) => { entry.insert(rec); },
-                    Entry::Occupied(_) => log::warn!("Duplicate object number {num} in xref stream")
+                    Entry::Occupied(_) => self.opts.diagnostics.report(Event::DuplicateXRefEntry(num))
                 };
             }
         }
@@ -317,6 +467,22 @@ impl<T: BufRead + Seek> FileParser<T> {
     }
 }
 
+#[cfg(feature = "memmap2")]
+impl FileParser<Cursor<memmap2::Mmap>> {
+    /// Creates a `FileParser` over a memory-mapped file.
+    ///
+    /// The map is owned by the `FileParser`, so readers returned by [`FileParser::read_raw()`] and 
+    /// friends borrow directly from the mapped region and no system calls are needed for seeking or 
+    /// reading. Note that the file must not be modified while mapped, otherwise the behaviour is 
+    /// undefined.
+    pub fn from_mmap(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the caller is responsible for the file not being modified (see doc comment).
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self::new(Cursor::new(map)))
+    }
+}
+
 
 struct StreamReader<'a, T: BufRead>(std::cell::RefMut<'a, T>);
 
@@ -368,6 +534,102 @@ mod tests {
         assert_eq!(s, b"xref");
     }
 
+    #[test]
+    fn test_multiple_eof() {
+        let mut data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let prev = FileParser::new(Cursor::new(&data)).entrypoint().unwrap();
+        let obj = data.len();
+        data.extend_from_slice(b"2 0 obj\n(update)\nendobj\n");
+        let start = data.len();
+        data.extend_from_slice(format!("xref\n2 1\n{obj:010} 00000 n \ntrailer\n\
+            << /Size 3 /Root 1 0 R /Prev {prev} >>\nstartxref\n{start}\n%%EOF\n").as_bytes());
+        assert_eq!(FileParser::new(Cursor::new(&data)).entrypoint().unwrap(), start as Offset);
+
+        data.extend_from_slice(b"startxref\n99999999\n%%EOF\n");
+        assert_eq!(FileParser::new(Cursor::new(&data)).entrypoint().unwrap(), start as Offset);
+
+        // Truncated startxref sections are skipped
+        data.extend_from_slice(b"startxref\n%%EOF\n");
+        assert_eq!(FileParser::new(Cursor::new(&data)).entrypoint().unwrap(), start as Offset);
+    }
+
+    #[test]
+    fn test_startxref_junk() {
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let start = FileParser::new(Cursor::new(&data)).entrypoint().unwrap();
+        let mut data = String::from_utf8(data).unwrap();
+        data = data.replace(&format!("startxref\n{start}\n"), &format!("startxref\n{start} garbage\n"));
+        let fp = FileParser::new(Cursor::new(&data));
+        assert_eq!(fp.entrypoint().unwrap(), start);
+        assert!(fp.read_xref_at(start).is_ok());
+
+        let fp = FileParser::new(Cursor::new(b"startxref\n1036 garbage\n%%EOF"));
+        assert_eq!(fp.entrypoint().unwrap(), 1036);
+        let fp = FileParser::new(Cursor::new(b"startxref\ngarbage 1036\n%%EOF"));
+        assert!(fp.entrypoint().is_err());
+    }
+
+    /// A large source consisting of zeros, except for the given chunks of data.
+    struct Sparse {
+        len: u64,
+        pos: u64,
+        chunks: Vec<(u64, Vec<u8>)>,
+    }
+
+    impl Read for Sparse {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let end = std::cmp::min(self.pos + buf.len() as u64, self.len);
+            let buf = &mut buf[..(end - self.pos) as usize];
+            buf.fill(0);
+            for (start, chunk) in &self.chunks {
+                let chunk_end = start + chunk.len() as u64;
+                if *start < end && chunk_end > self.pos {
+                    let from = std::cmp::max(*start, self.pos);
+                    let to = std::cmp::min(chunk_end, end);
+                    buf[((from - self.pos) as usize)..((to - self.pos) as usize)]
+                        .copy_from_slice(&chunk[((from - start) as usize)..((to - start) as usize)]);
+                }
+            }
+            self.pos = end;
+            Ok(buf.len())
+        }
+    }
+
+    impl Seek for Sparse {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.pos = match pos {
+                SeekFrom::Start(pos) => pos,
+                SeekFrom::End(off) => self.len.checked_add_signed(off).unwrap(),
+                SeekFrom::Current(off) => self.pos.checked_add_signed(off).unwrap(),
+            };
+            Ok(self.pos)
+        }
+    }
+
+    #[test]
+    fn test_large_file() {
+        let len = 5 << 30;
+        let xref = (1 << 32) + 12345;
+        let obj = xref - 100;
+        let tail = format!("startxref\n{xref}\n%%EOF\n");
+        let source = Sparse { len, pos: 0, chunks: vec![
+            (0, b"%PDF-1.7\n".to_vec()),
+            (obj, b"1 0 obj\n<< /Type /Catalog >>\nendobj\n".to_vec()),
+            (xref, format!("xref\n0 2\n0000000000 65535 f \n{obj:010} 00000 n \n\
+                trailer\n<< /Size 2 /Root 1 0 R >>\n").into_bytes()),
+            (len - tail.len() as u64, tail.into_bytes()),
+        ] };
+        let fp = FileParser::new(BufReader::new(source));
+        assert_eq!(fp.header().as_ref().unwrap().version, (1, 7));
+        assert_eq!(fp.file_len().unwrap(), len);
+        assert_eq!(fp.entrypoint().unwrap(), xref);
+        let xref = fp.read_xref_at(xref).unwrap();
+        assert_eq!(xref.map.get(&1), Some(&Record::Used { gen: 0, offset: obj }));
+        let (oref, obj) = fp.read_obj_at(obj).unwrap();
+        assert_eq!(oref, ObjRef { num: 1, gen: 0 });
+        assert!(obj.as_dict().unwrap().has_type(b"Catalog"));
+    }
+
     #[test]
     fn test_read_xref() {
         let fp = FileParser::new(BufReader::new(File::open("src/tests/hybrid.pdf").unwrap()));
@@ -398,6 +660,64 @@ mod tests {
         assert!(matches!(xref.tpe, XRefType::Table));
     }
 
+    #[cfg(feature = "memmap2")]
+    #[test]
+    fn test_from_mmap() {
+        let fp = FileParser::new(BufReader::new(File::open("src/tests/basic.pdf").unwrap()));
+        let fm = FileParser::from_mmap("src/tests/basic.pdf").unwrap();
+        assert_eq!(fm.header().as_ref().unwrap().version, (1, 4));
+        let entry = fm.entrypoint().unwrap();
+        assert_eq!(entry, fp.entrypoint().unwrap());
+        let xref = fm.read_xref_at(entry).unwrap();
+        for rec in xref.map.values() {
+            if let &Record::Used { offset, .. } = rec {
+                assert_eq!(fm.read_obj_at(offset).unwrap(), fp.read_obj_at(offset).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_xref_stream_widths() {
+        let xref_stream = |w: &str, data: &str| format!("1 0 obj\n<< /Type /XRef /Size 3 /W {w} \
+            /Filter /ASCIIHexDecode /Length {} >>\nstream\n{data}\nendstream\nendobj\n", data.len());
+
+        let source = xref_stream("[1 2 0]", "000000 010009 020005>");
+        let xref = FileParser::new(Cursor::new(source)).read_xref_at(0).unwrap();
+        assert_eq!(xref.map, BTreeMap::from([
+            (0, Record::Free { gen: 0, next: 0 }),
+            (1, Record::Used { gen: 0, offset: 9 }),
+            (2, Record::Compr { num_within: 5, index: 0 }),
+        ]));
+
+        let source = xref_stream("[0 2 1]", "000000 000901 000500>");
+        let xref = FileParser::new(Cursor::new(source)).read_xref_at(0).unwrap();
+        assert_eq!(xref.map, BTreeMap::from([
+            (0, Record::Used { gen: 0, offset: 0 }),
+            (1, Record::Used { gen: 1, offset: 9 }),
+            (2, Record::Used { gen: 0, offset: 5 }),
+        ]));
+
+        let source = xref_stream("[1 0 2]", "000000 010000 020000>");
+        assert!(FileParser::new(Cursor::new(source)).read_xref_at(0).is_err());
+    }
+
+    #[test]
+    fn test_xref_stream_no_size() {
+        let data = "000000 010009 010005>";
+        let source = format!("1 0 obj\n<< /Type /XRef /Index [0 1 4 2] /W [1 2 0] \
+            /Filter /ASCIIHexDecode /Length {} >>\nstream\n{data}\nendstream\nendobj\n", data.len());
+        let xref = FileParser::new(Cursor::new(&source)).read_xref_at(0).unwrap();
+        assert_eq!(xref.size, 6);
+        assert_eq!(xref.map, BTreeMap::from([
+            (0, Record::Free { gen: 0, next: 0 }),
+            (4, Record::Used { gen: 0, offset: 9 }),
+            (5, Record::Used { gen: 0, offset: 5 }),
+        ]));
+
+        let source = source.replace("/Index [0 1 4 2] ", "");
+        assert!(FileParser::new(Cursor::new(source)).read_xref_at(0).is_err());
+    }
+
     #[test]
     fn test_read_obj_at() {
         let fp = FileParser::new(BufReader::new(File::open("src/tests/basic.pdf").unwrap()));
@@ -406,9 +726,28 @@ mod tests {
         assert_eq!(oref, ObjRef { num: 4, gen: 0 });
         let stm = obj.as_stream().unwrap();
         assert_eq!(stm.data, 74);
+        assert_eq!(stm.data_offset(), Some(74));
+
+        assert!(fp.read_obj_at_checked(15, &ObjRef { num: 4, gen: 0 }).is_ok());
+        assert!(fp.read_obj_at_checked(15, &ObjRef { num: 4, gen: 1 }).is_err());
 
         // xref instead of object
         assert!(fp.read_obj_at(1036).is_err());
         assert!(fp.read_xref_at(1036).is_ok());
     }
+
+    #[test]
+    fn test_stream_lone_cr() {
+        let data = b"1 0 obj\n<< /Length 3 >>\nstream\rabc\nendstream\nendobj\n";
+        let mut fp = FileParser::new(Cursor::new(data));
+        assert!(fp.read_obj_at(0).is_err());
+        fp.set_lenient(true);
+        let (_, obj) = fp.read_obj_at(0).unwrap();
+        assert_eq!(obj.as_stream().unwrap().data, 31);
+
+        let data = b"1 0 obj\n<< /Length 3 >>\nstream\r\nabc\nendstream\nendobj\n";
+        let fp = FileParser::new(Cursor::new(data));
+        let (_, obj) = fp.read_obj_at(0).unwrap();
+        assert_eq!(obj.as_stream().unwrap().data, 32);
+    }
 }
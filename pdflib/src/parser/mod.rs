@@ -5,5 +5,7 @@ mod op;
 mod fp;
 
 pub use fp::FileParser;
+pub(crate) use fp::Structural;
 pub(crate) use tk::Tokenizer;
-pub use op::ObjParser;
+pub use op::{ObjParser, ParseOptions, MAX_NAME_LEN, MAX_DEPTH};
+pub(crate) use op::ContentToken;
@@ -1,4 +1,5 @@
 use std::io::{BufRead, Cursor};
+use std::sync::Arc;
 
 use crate::base::*;
 use crate::utils;
@@ -7,15 +8,54 @@ use super::bp::ByteProvider;
 use super::cc::CharClass;
 use super::tk::*;
 
+/// An item of a content stream, see [`ObjParser::read_content_token()`].
+pub(crate) enum ContentToken {
+    Operand(Object),
+    Operator(Vec<u8>)
+}
+
+/// The default maximum length of a name token, see [`ParseOptions::max_name_len`].
+pub const MAX_NAME_LEN: usize = 1 << 20;
+
+/// The default maximum nesting depth of arrays and dictionaries, see 
+/// [`ParseOptions::max_depth`].
+pub const MAX_DEPTH: usize = 256;
+
+/// Options controlling how objects are parsed, see [`ObjParser::read_obj_with()`].
+#[derive(Clone)]
+pub struct ParseOptions {
+    /// Tolerate malformations which do not prevent reading, see [`ObjParser::read_obj_with()`]. 
+    /// Defaults to `false`.
+    pub lenient: bool,
+    /// The maximum length of a name token, in bytes, before `#` escapes are decoded. The default, 
+    /// [`MAX_NAME_LEN`], is far above the limit recommended by the specification (127 bytes), but 
+    /// prevents unbounded allocation on corrupted input.
+    pub max_name_len: usize,
+    /// The maximum nesting depth of arrays and dictionaries. Deeper structures are rejected to 
+    /// prevent stack overflow on crafted input. Defaults to [`MAX_DEPTH`].
+    pub max_depth: usize,
+    /// The sink receiving reports of recoverable problems. Defaults to [`LogDiagnostics`].
+    pub diagnostics: Arc<dyn Diagnostics>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self { lenient: false, max_name_len: MAX_NAME_LEN, max_depth: MAX_DEPTH,
+            diagnostics: Arc::new(LogDiagnostics) }
+    }
+}
+
 /// Parses a single PDF object.
 pub struct ObjParser<T: BufRead> {
     reader: T,
-    stack: Vec<Token>
+    stack: Vec<Token>,
+    opts: ParseOptions,
+    depth: usize,
 }
 
 impl<T: BufRead> ObjParser<T> {
-    fn new(reader: T) -> Self {
-        Self { reader, stack: Vec::with_capacity(2) }
+    fn new(reader: T, opts: ParseOptions) -> Self {
+        Self { reader, stack: Vec::with_capacity(2), opts, depth: 0 }
     }
 
     fn next_token(&mut self) -> Result<Token, Error> {
@@ -26,18 +66,91 @@ impl<T: BufRead> ObjParser<T> {
         }
     }
 
+    /// Returns the next token without consuming it. The token is kept for the next read.
+    pub fn peek_token(&mut self) -> Result<&Token, Error> {
+        if self.stack.is_empty() {
+            let tk = self.reader.read_token()?;
+            self.stack.push(tk);
+        }
+        Ok(self.stack.last().unwrap()) // nonemptiness ensured above
+    }
+
     /// Parses a single PDF object.
     pub fn read_obj(reader: &mut T) -> Result<Object, Error> {
+        Self::read_obj_with(reader, &ParseOptions::default())
+    }
+
+    /// Like [`ObjParser::read_obj()`], but with the given [`ParseOptions`].
+    ///
+    /// If `opts.lenient` is set, integers not fitting into `i64` are read as [`Number::Real`] 
+    /// instead of producing an error, reporting [`Event::IntegerOutOfRange`]. Malformed dictionary 
+    /// entries are skipped up to the next key, reporting [`Event::MalformedDictEntry`]. Stray `R` 
+    /// keywords within arrays and dictionaries are skipped, reporting [`Event::UnexpectedR`].
+    pub fn read_obj_with(reader: &mut T, opts: &ParseOptions) -> Result<Object, Error> {
         let tk = reader.read_token()?;
         if matches!(tk[..], [b'0'..=b'9' | b'+' | b'-' | b'.', ..]) {
-            tk.try_into().map(Object::Number)
+            Self::parse_number(tk, opts).map(Object::Number)
         } else {
-            let mut op = ObjParser::new(reader);
+            let mut op = ObjParser::new(reader, opts.clone());
             op.stack.push(tk);
             op.read_obj_inner()
         }
     }
 
+    /// Parses a sequence of PDF objects separated by whitespace, like the body of an object 
+    /// stream, until the end of input.
+    ///
+    /// The iterator ends with `None` if the input ends cleanly between objects. If an object is 
+    /// malformed or truncated, the error is yielded and the iteration stops.
+    pub fn objects(mut reader: T) -> impl Iterator<Item = Result<Object, Error>> {
+        let mut done = false;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            if let Err(err) = reader.skip_ws() {
+                done = true;
+                return Some(Err(err.into()));
+            }
+            reader.peek()?;
+            let res = Self::read_obj(&mut reader);
+            done = res.is_err();
+            Some(res)
+        })
+    }
+
+    /// Reads the next operand or operator from a content stream. Returns `None` at the end of 
+    /// input.
+    pub(crate) fn read_content_token(reader: &mut T) -> Result<Option<ContentToken>, Error> {
+        reader.skip_ws()?;
+        if reader.peek().is_none() {
+            return Ok(None);
+        }
+        let tk = reader.read_token()?;
+        match &tk[..] {
+            [b'0'..=b'9' | b'+' | b'-' | b'.', ..]
+                => Self::parse_number(tk, &ParseOptions::default()).map(|num| Some(ContentToken::Operand(Object::Number(num)))),
+            b"true" | b"false" | b"null" | [b'(' | b'<' | b'/' | b'[', ..] => {
+                let mut op = ObjParser::new(reader, ParseOptions::default());
+                op.stack.push(tk);
+                op.read_obj_inner().map(|obj| Some(ContentToken::Operand(obj)))
+            },
+            [c, ..] if CharClass::of(*c) == CharClass::Reg => Ok(Some(ContentToken::Operator(tk))),
+            _ => Err(Error::Parse("unexcepted token"))
+        }
+    }
+
+    fn parse_number(tk: Token, opts: &ParseOptions) -> Result<Number, Error> {
+        let digits = tk.strip_prefix(b"+").or_else(|| tk.strip_prefix(b"-")).unwrap_or(&tk);
+        if opts.lenient && !digits.is_empty() && digits.iter().all(u8::is_ascii_digit)
+                && utils::parse_num::<i64>(&tk).is_none() {
+            // all ASCII, checked above
+            opts.diagnostics.report(Event::IntegerOutOfRange(String::from_utf8(tk.clone()).unwrap()));
+            return utils::parse_num(&tk).map(Number::Real).ok_or(Error::Parse("malformed number"));
+        }
+        tk.try_into()
+    }
+
     fn read_obj_inner(&mut self) -> Result<Object, Error> {
         let tk = self.next_token()?;
         match &tk[..] {
@@ -49,18 +162,28 @@ impl<T: BufRead> ObjParser<T> {
                 self.read_number_or_indirect()
             },
             [b'+' | b'-' | b'0' | b'.', ..]
-                => tk.try_into().map(Object::Number),
+                => Self::parse_number(tk, &self.opts).map(Object::Number),
             b"(" => self.read_lit_string(),
             b"<" => self.read_hex_string(),
             b"/" => self.read_name().map(Object::Name),
-            b"[" => self.read_array(),
-            b"<<" => self.read_dict(),
+            b"[" => self.nested(Self::read_array),
+            b"<<" => self.nested(Self::read_dict),
             _ => Err(Error::Parse("unexcepted token")),
         }
     }
 
+    fn nested(&mut self, func: fn(&mut Self) -> Result<Object, Error>) -> Result<Object, Error> {
+        if self.depth >= self.opts.max_depth {
+            return Err(Error::Parse("nesting too deep"));
+        }
+        self.depth += 1;
+        let ret = func(self);
+        self.depth -= 1;
+        ret
+    }
+
     fn read_number_or_indirect(&mut self) -> Result<Object, Error> {
-        let num = self.next_token()?.try_into()?;
+        let num = Self::parse_number(self.next_token()?, &self.opts)?;
         let Number::Int(num) = num else {
             return Ok(Object::Number(num))
         };
@@ -158,7 +281,22 @@ impl<T: BufRead> ObjParser<T> {
             None => return Ok(Name::from(b"")),
             _ => ()
         };
-        let tk = self.next_token()?;
+        let tk = match self.stack.pop() {
+            Some(tk) => tk,
+            None => {
+                let mut tk = Vec::new();
+                while let Some(c) = self.reader.next_if(|c| CharClass::of(c) == CharClass::Reg) {
+                    if tk.len() == self.opts.max_name_len {
+                        return Err(Error::Parse("name too long"));
+                    }
+                    tk.push(c);
+                }
+                tk
+            }
+        };
+        if tk.len() > self.opts.max_name_len {
+            return Err(Error::Parse("name too long"));
+        }
         if !tk.contains(&b'#') {
             return Ok(Name(tk));
         }
@@ -184,6 +322,10 @@ impl<T: BufRead> ObjParser<T> {
         loop {
             let tk = self.next_token()?;
             if tk == b"]" { break; }
+            if tk == b"R" && self.opts.lenient {
+                self.opts.diagnostics.report(Event::UnexpectedR);
+                continue;
+            }
             self.stack.push(tk);
             vec.push(self.read_obj_inner()?);
         }
@@ -193,16 +335,39 @@ impl<T: BufRead> ObjParser<T> {
     fn read_dict(&mut self) -> Result<Object, Error> {
         let mut dict = Vec::new();
         loop {
-            let key = match &self.next_token()?[..] {
+            let tk = self.next_token()?;
+            let key = match &tk[..] {
                 b">>" => break,
                 b"/" => self.read_name()?,
+                _ if self.opts.lenient => {
+                    self.opts.diagnostics.report(Event::MalformedDictEntry);
+                    self.stack.push(tk);
+                    self.skip_to_key()?;
+                    continue;
+                },
                 _ => return Err(Error::Parse("malformed dictionary"))
             };
+            if self.opts.lenient && self.peek_token()? == b"R" {
+                self.opts.diagnostics.report(Event::UnexpectedR);
+                self.next_token()?;
+            }
             let value = self.read_obj_inner()?;
             dict.push((key, value));
         }
         Ok(Object::Dict(Dict::from(dict)))
     }
+
+    /// Skips tokens until the next name or the end of the dictionary. Arrays, dictionaries and 
+    /// strings are skipped as whole objects, so that their contents are not mistaken for keys.
+    fn skip_to_key(&mut self) -> Result<(), Error> {
+        loop {
+            match &self.peek_token()?[..] {
+                b"/" | b">>" => return Ok(()),
+                b"[" | b"<<" | b"(" | b"<" => { self.read_obj_inner()?; },
+                _ => { self.next_token()?; }
+            }
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -210,7 +375,7 @@ impl<T: BufRead> ObjParser<T> {
 impl<T: BufRead> Drop for ObjParser<T> {
     fn drop(&mut self) {
         for tk in &self.stack {
-            log::error!("Dropping stacked token: {}", std::str::from_utf8(tk).unwrap())
+            log_error!("Dropping stacked token: {}", std::str::from_utf8(tk).unwrap())
         }
     }
 }
@@ -233,7 +398,7 @@ impl TryFrom<Token> for Number {
 
 impl From<&str> for ObjParser<Cursor<String>> {
     fn from(input: &str) -> Self {
-        ObjParser::new(Cursor::new(input.to_owned()))
+        ObjParser::new(Cursor::new(input.to_owned()), ParseOptions::default())
     }
 }
 
@@ -241,6 +406,28 @@ impl From<&str> for ObjParser<Cursor<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::Capture;
+
+    /// Lenient [`ParseOptions`] reporting to a new `Capture`.
+    fn lenient_capture() -> (ParseOptions, Arc<Capture>) {
+        let capture = Arc::new(Capture::default());
+        (ParseOptions { lenient: true, diagnostics: capture.clone(), ..Default::default() }, capture)
+    }
+
+    #[test]
+    fn test_peek_token() {
+        let mut parser = ObjParser::from("/Name [1 2]");
+        assert_eq!(parser.peek_token().unwrap(), b"/");
+        assert_eq!(parser.peek_token().unwrap(), b"/");
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::new_name(b"Name"));
+        assert_eq!(parser.peek_token().unwrap(), b"[");
+        assert_eq!(parser.next_token().unwrap(), b"[");
+        assert_eq!(parser.next_token().unwrap(), b"1");
+        parser.next_token().unwrap();
+        assert_eq!(parser.peek_token().unwrap(), b"]");
+        assert_eq!(parser.next_token().unwrap(), b"]");
+        assert!(parser.peek_token().is_err());
+    }
 
     #[test]
     fn test_read_primitives() {
@@ -265,6 +452,22 @@ mod tests {
         assert_eq!(parser.read_obj_inner().unwrap(), Object::Number(Number::Int(9223372036854775807)));
         assert!(parser.read_obj_inner().is_err());
 
+        let (opts, capture) = lenient_capture();
+        let mut parser = ObjParser::from("9223372036854775808 -99999999999999999999 [+9223372036854775808] 1e5");
+        parser.opts = opts;
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::Number(Number::Real(9223372036854775808.)));
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::Number(Number::Real(-99999999999999999999.)));
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::Array(vec![Object::Number(Number::Real(9223372036854775808.))]));
+        assert!(parser.read_obj_inner().is_err());
+        let events = capture.0.lock().unwrap().iter()
+            .map(|event| match event { Event::IntegerOutOfRange(num) => num.clone(), _ => panic!() })
+            .collect::<Vec<_>>();
+        assert_eq!(events, ["9223372036854775808", "-99999999999999999999", "+9223372036854775808"]);
+        assert_eq!(ObjParser::read_obj_with(&mut Cursor::new("18446744073709551616"),
+            &ParseOptions { lenient: true, ..Default::default() }).unwrap(),
+            Object::Number(Number::Real(18446744073709551616.)));
+        assert!(ObjParser::read_obj(&mut Cursor::new("18446744073709551616")).is_err());
+
         let mut parser = ObjParser::from("++1 1..0 .1. 1_ 1a 16#FFFE . 6.023E23 true");
         assert!(parser.read_obj_inner().is_err());
         assert!(parser.read_obj_inner().is_err());
@@ -351,6 +554,23 @@ are the same.) (These two strings are the same.)");
         assert!(parser.read_obj_inner().is_err());
         assert!(parser.read_obj_inner().is_err());
         assert_eq!(parser.read_obj_inner().unwrap(), Object::Bool(true));
+
+        // A raw null byte is white-space, so it can't be a part of a name.
+        let mut parser = ObjParser::from("/nul\x00/byte");
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::new_name(b"nul"));
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::new_name(b"byte"));
+
+        let long = "x".repeat(MAX_NAME_LEN);
+        let mut parser = ObjParser::from(format!("/{long} /{long}x").as_str());
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::new_name(long.as_bytes()));
+        assert!(matches!(parser.read_obj_inner(), Err(Error::Parse("name too long"))));
+
+        let opts = ParseOptions { max_name_len: 4, ..Default::default() };
+        assert_eq!(ObjParser::read_obj_with(&mut Cursor::new("/Name"), &opts).unwrap(), Object::new_name(b"Name"));
+        assert!(matches!(ObjParser::read_obj_with(&mut Cursor::new("/Names"), &opts),
+            Err(Error::Parse("name too long"))));
+        assert!(matches!(ObjParser::read_obj_with(&mut Cursor::new("<< /Type /Pages >>"), &opts),
+            Err(Error::Parse("name too long"))));
     }
 
     #[test]
@@ -366,6 +586,34 @@ are the same.) (These two strings are the same.)");
         ]));
         assert_eq!(parser.read_obj_inner().unwrap(), Object::Array(vec![]));
         assert!(parser.read_obj_inner().is_err());
+
+        assert!(ObjParser::from("[1 R 2]").read_obj_inner().is_err());
+        let (opts, capture) = lenient_capture();
+        let mut parser = ObjParser::from("[1 R 2] <</A R 3 /B 4 R>>");
+        parser.opts = opts;
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::Array(vec![
+            Object::Number(Number::Int(1)), Object::Number(Number::Int(2))]));
+        assert_eq!(parser.read_obj_inner().unwrap(), Object::Dict(Dict::from(vec![
+            (Name::from(b"A"), Object::Number(Number::Int(3))),
+            (Name::from(b"B"), Object::Number(Number::Int(4))),
+        ])));
+        // The R following a value is a malformed entry.
+        assert!(matches!(capture.0.lock().unwrap()[..],
+            [Event::UnexpectedR, Event::UnexpectedR, Event::MalformedDictEntry]));
+
+        let input = "[<</A ".repeat(MAX_DEPTH / 2) + "null" + &" >>]".repeat(MAX_DEPTH / 2);
+        assert!(ObjParser::from(input.as_str()).read_obj_inner().is_ok());
+        let input = "[".repeat(100_000);
+        assert!(matches!(ObjParser::from(input.as_str()).read_obj_inner(), Err(Error::Parse("nesting too deep"))));
+
+        let opts = ParseOptions { max_depth: 2, ..Default::default() };
+        assert!(ObjParser::read_obj_with(&mut Cursor::new("[<</A 1>>]"), &opts).is_ok());
+        assert!(matches!(ObjParser::read_obj_with(&mut Cursor::new("[<</A [1]>>]"), &opts),
+            Err(Error::Parse("nesting too deep"))));
+        let opts = ParseOptions { max_depth: 0, ..Default::default() };
+        assert!(ObjParser::read_obj_with(&mut Cursor::new("/A"), &opts).is_ok());
+        assert!(matches!(ObjParser::read_obj_with(&mut Cursor::new("[]"), &opts),
+            Err(Error::Parse("nesting too deep"))));
     }
 
     #[test]
@@ -398,6 +646,23 @@ are the same.) (These two strings are the same.)");
 
         let mut parser = ObjParser::from("<<>>");
         assert_eq!(parser.read_obj_inner().unwrap(), Object::Dict(Dict::from(vec![])));
+
+        let input = b"<< /A 1 junk /B (x) 5 >>";
+        assert!(ObjParser::read_obj_with(&mut &input[..], &ParseOptions::default()).is_err());
+        assert_eq!(ObjParser::read_obj_with(&mut &input[..], &ParseOptions { lenient: true, ..Default::default() }).unwrap(), Object::Dict(Dict::from(vec![
+            (Name::from(b"A"), Object::Number(Number::Int(1))),
+            (Name::from(b"B"), Object::new_string(b"x")),
+        ])));
+
+        // Nested objects within the junk are skipped as a whole.
+        let (opts, capture) = lenient_capture();
+        let input = b"<< /A 1 junk << /B 2 >> [/C (/D) 3] (/E) <00> /F 4 [5] /G 6 >>";
+        assert_eq!(ObjParser::read_obj_with(&mut &input[..], &opts).unwrap(), Object::Dict(Dict::from(vec![
+            (Name::from(b"A"), Object::Number(Number::Int(1))),
+            (Name::from(b"F"), Object::Number(Number::Int(4))),
+            (Name::from(b"G"), Object::Number(Number::Int(6))),
+        ])));
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::MalformedDictEntry, Event::MalformedDictEntry]));
     }
 
     #[test]
@@ -449,4 +714,22 @@ are the same.) (These two strings are the same.)");
 
         assert!(ObjParser::read_obj(&mut Cursor::new("R")).is_err());
     }
+    #[test]
+    fn test_objects() {
+        let objs = ObjParser::objects(&b"1 (two) << /Three 3 >> % comment\n"[..])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(objs, vec![
+            Object::Number(Number::Int(1)),
+            Object::new_string(b"two"),
+            Object::Dict(Dict::from(vec![(Name::from(b"Three"), Object::Number(Number::Int(3)))])),
+        ]);
+        assert_eq!(ObjParser::objects(&b""[..]).count(), 0);
+
+        let mut iter = ObjParser::objects(&b"[1 2] << /Key (val"[..]);
+        assert_eq!(iter.next().unwrap().unwrap(), Object::Array(vec![
+            Object::Number(Number::Int(1)), Object::Number(Number::Int(2))]));
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
 }
@@ -1,12 +1,12 @@
 use std::io::{Read, BufRead, Seek};
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::collections::btree_map::Entry;
 use std::cell::{RefCell, Ref};
 use std::ops::Deref;
 
 use crate::base::*;
 use crate::base::types::*;
-use crate::parser::{FileParser, ObjParser};
+use crate::parser::{FileParser, ObjParser, ParseOptions, Structural};
 use crate::codecs;
 use crate::utils;
 
@@ -14,17 +14,44 @@ use super::esr::EndstreamReader;
 
 pub struct BaseReader<T: BufRead + Seek> {
     pub parser: FileParser<T>,
+    /// Tolerate inconsistencies which do not prevent reading, e.g. a mismatched object number in 
+    /// an object stream header, with a warning.
+    pub lenient: bool,
+    /// Exclude a single end-of-line marker at the end of stream data whose `/Length` is given by 
+    /// an indirect reference.
+    pub trim_trailing_eol: bool,
     objstms: RefCell<BTreeMap<Offset, Result<ObjStm, Error>>>,
 }
 
 struct ObjStm {
     entries: Vec<(ObjNum, Offset)>,
-    source: Vec<u8>,
+    source: ObjStmSource,
 }
 
+enum ObjStmSource {
+    /// The decoded data following `/First`.
+    Buffered(Vec<u8>),
+    /// The data of members up to [`OBJSTM_MEMBER_LIMIT`] bytes long are kept, as long as their 
+    /// total stays within [`OBJSTM_LAZY_THRESHOLD`], indexed by their position in `entries`. Other 
+    /// members are decoded anew on each access, reading only up to their end.
+    Lazy { stm: RefStream, first: Offset, members: BTreeMap<usize, Vec<u8>> },
+}
+
+/// Object streams with more than this many bytes of decoded data following `/First` are not 
+/// buffered in memory as a whole.
+const OBJSTM_LAZY_THRESHOLD: Offset = 1 << 20;
+
+/// The maximum length of a member of a large object stream which is buffered individually.
+const OBJSTM_MEMBER_LIMIT: Offset = 1 << 16;
+
 impl<T: BufRead + Seek> BaseReader<T> {
     pub fn new(parser: FileParser<T>) -> Self {
-        Self { parser, objstms: Default::default() }
+        Self { parser, lenient: false, trim_trailing_eol: false, objstms: Default::default() }
+    }
+
+    /// Discards all cached object streams.
+    pub fn clear_cache(&mut self) {
+        self.objstms.get_mut().clear();
     }
 
     pub fn read_xref_chain(parser: &FileParser<T>, entry: Offset) -> impl Iterator<Item = (Offset, XRef)> + use<'_, T> {
@@ -32,20 +59,19 @@ impl<T: BufRead + Seek> BaseReader<T> {
     }
 
     pub fn resolve(&self, objref: &ObjRef, locator: &dyn Locator) -> Result<Object, Error> {
+        Ok(self.resolve_opt(objref, locator)?.unwrap_or(Object::Null))
+    }
+
+    pub fn resolve_opt(&self, objref: &ObjRef, locator: &dyn Locator) -> Result<Option<Object>, Error> {
         match locator.locate(objref) {
-            Some(Record::Used { offset, .. }) => self.read_uncompressed(offset, objref),
-            Some(Record::Compr { num_within, index }) => self.read_compressed(num_within, index, locator, objref),
-            _ => Ok(Object::Null)
+            Some(Record::Used { offset, .. }) => self.read_uncompressed(offset, objref).map(Some),
+            Some(Record::Compr { num_within, index }) => self.read_compressed(num_within, index, locator, objref).map(Some),
+            _ => Ok(None)
         }
     }
 
     pub fn read_uncompressed(&self, offset: Offset, oref_expd: &ObjRef) -> Result<Object, Error> {
-        let (oref, obj) = self.parser.read_obj_at(offset)?;
-        if &oref == oref_expd {
-            Ok(obj)
-        } else {
-            Err(Error::Parse("object number mismatch"))
-        }
+        self.parser.read_obj_at_checked(offset, oref_expd)
     }
 
     pub fn read_compressed(&self, num_within: ObjNum, index: ObjIndex, locator: &dyn Locator, oref_expd: &ObjRef) -> Result<Object, Error> {
@@ -59,13 +85,50 @@ impl<T: BufRead + Seek> BaseReader<T> {
             return Err(Error::Parse("out of bounds index requested from object stream"));
         };
         if &(ObjRef { num, gen: 0 }) != oref_expd {
-            return Err(Error::Parse("object number mismatch"));
+            if !self.lenient {
+                return Err(Error::Parse("object number mismatch"));
+            }
+            self.parser.diagnostics().report(Event::ObjStmMismatch {
+                objstm: num_within, index: index as ObjIndex, found: num, expected: *oref_expd });
+        }
+        let end_offset = objstm.entries.get(index + 1).map(|entry| entry.1);
+        match &objstm.source {
+            ObjStmSource::Buffered(data) => {
+                let end_offset = end_offset.map(|end| end.try_into().unwrap()).unwrap_or(data.len());
+                let mut source = &data[start_offset.try_into().unwrap()..end_offset];
+                ObjParser::read_obj_with(&mut source, &self.parse_options())
+            },
+            ObjStmSource::Lazy { members, .. } if members.contains_key(&index) => {
+                ObjParser::read_obj_with(&mut &members[&index][..], &self.parse_options())
+            },
+            ObjStmSource::Lazy { stm, first, .. } => {
+                // Resolving /Length may need access to the cache.
+                let (stm, skip) = (stm.clone(), first + start_offset);
+                drop(cache_ref);
+                let mut reader = self.read_stream_data(&stm, locator)?;
+                let skipped = std::io::copy(&mut reader.by_ref().take(skip), &mut std::io::sink())?;
+                if skipped < skip {
+                    return Err(Error::Parse("malformed object stream header (offsets)"));
+                }
+                let opts = self.parse_options();
+                match end_offset {
+                    Some(end) => ObjParser::read_obj_with(&mut reader.take(end - start_offset), &opts),
+                    None => ObjParser::read_obj_with(&mut reader, &opts)
+                }
+            }
+        }
+    }
+
+    /// The options of the parser, with `lenient` overridden by that of this `BaseReader`.
+    fn parse_options(&self) -> ParseOptions {
+        ParseOptions { lenient: self.lenient, ..self.parser.parse_options().clone() }
+    }
+
+    pub fn objstm_members(&self, ostm_num: ObjNum, locator: &dyn Locator) -> Result<Vec<ObjNum>, Error> {
+        match (*self.read_cache_objstm(ostm_num, locator)).deref() {
+            Ok(objstm) => Ok(objstm.entries.iter().map(|&(num, _)| num).collect()),
+            Err(err) => Err(err.clone())
         }
-        let end_offset = objstm.entries.get(index + 1)
-            .map(|entry| entry.1.try_into().unwrap())
-            .unwrap_or(objstm.source.len());
-        let mut source = &objstm.source[start_offset.try_into().unwrap()..end_offset];
-        ObjParser::read_obj(&mut source)
     }
 
     fn read_cache_objstm(&self, ostm_num: ObjNum, locator: &dyn Locator) -> Box<dyn Deref<Target =  Result<ObjStm, Error>> + '_> {
@@ -83,56 +146,180 @@ impl<T: BufRead + Seek> BaseReader<T> {
         let stm = self.read_uncompressed(ostm_offset, ostm_oref)?
             .into_stream()
             .ok_or(Error::Parse("object stream not found"))?;
-        // FIXME: /Type = /ObjStm
+        if !stm.is_objstm() {
+            return Err(Error::Parse("malformed object stream (/Type)"));
+        }
         let count = stm.dict.lookup(b"N").num_value()
             .ok_or(Error::Parse("malformed object stream (/N)"))?;
         let first = stm.dict.lookup(b"First").num_value()
             .ok_or(Error::Parse("malformed object stream (/First)"))?;
         let mut reader = self.read_stream_data(&stm, locator)?;
-        let mut header = (&mut reader).take(first);
+        let mut data = Vec::new();
+        reader.by_ref().take(first as u64).read_to_end(&mut data)?;
+        if first > data.len() {
+            return Err(Error::Parse("malformed object stream (/First out of range)"));
+        }
+        let mut header = &data[..];
         use crate::parser::Tokenizer;
-        let mut entries = Vec::with_capacity(count);
+        let mut entries = Vec::with_capacity(std::cmp::min(count, first));
         for _ in 0..count {
-            let num = utils::parse_num::<ObjNum>(&header.read_token()?)
-                .ok_or(Error::Parse("malformed object stream header"))?;
-            let offset = utils::parse_num::<Offset>(&header.read_token()?)
-                .ok_or(Error::Parse("malformed object stream header"))?;
+            let mut read = || header.read_token().ok()
+                .and_then(|tk| utils::parse_num::<u64>(&tk))
+                .ok_or(Error::Parse("malformed object stream header"));
+            let num: ObjNum = read()?;
+            let offset: Offset = read()?;
             entries.push((num, offset));
         }
-        // Drain the rest of header: https://stackoverflow.com/a/42247224
-        std::io::copy(&mut header, &mut std::io::sink())?;
+        if entries.windows(2).any(|w| w[0].1 > w[1].1) {
+            return Err(Error::Parse("malformed object stream header (offsets)"));
+        }
         let mut source = Vec::new();
-        std::io::copy(&mut reader, &mut source)?;
-        source.shrink_to_fit();
-        Ok(ObjStm { entries, source })
+        reader.by_ref().take(OBJSTM_LAZY_THRESHOLD + 1).read_to_end(&mut source)?;
+        if source.len() as Offset <= OBJSTM_LAZY_THRESHOLD {
+            if entries.last().is_some_and(|&(_, offset)| offset > source.len() as Offset) {
+                return Err(Error::Parse("malformed object stream header (offsets)"));
+            }
+            return Ok(ObjStm { entries, source: ObjStmSource::Buffered(source) });
+        }
+        let members = Self::split_objstm(&entries, &mut std::io::Cursor::new(source).chain(reader))?;
+        Ok(ObjStm { entries, source: ObjStmSource::Lazy { stm, first: first as Offset, members } })
+    }
+
+    /// Reads the data of a large object stream in a single pass, keeping the members which are 
+    /// small enough, see [`ObjStmSource::Lazy`].
+    fn split_objstm(entries: &[(ObjNum, Offset)], reader: &mut impl Read) -> Result<BTreeMap<usize, Vec<u8>>, Error> {
+        let mut members = BTreeMap::new();
+        let (mut pos, mut budget) = (0, OBJSTM_LAZY_THRESHOLD);
+        let skip = |reader: &mut dyn Read, len| -> Result<(), Error> {
+            if std::io::copy(&mut reader.take(len), &mut std::io::sink())? < len {
+                return Err(Error::Parse("malformed object stream header (offsets)"));
+            }
+            Ok(())
+        };
+        for (index, &(_, start)) in entries.iter().enumerate() {
+            skip(reader, start - pos)?;
+            pos = start;
+            let limit = OBJSTM_MEMBER_LIMIT.min(budget);
+            let mut data = Vec::new();
+            match entries.get(index + 1) {
+                Some(&(_, end)) if end - start <= limit => {
+                    reader.take(end - start).read_to_end(&mut data)?;
+                    pos += data.len() as Offset;
+                    budget -= data.len() as Offset;
+                    members.insert(index, data);
+                },
+                Some(_) => (),
+                None => {
+                    reader.take(limit + 1).read_to_end(&mut data)?;
+                    if data.len() as Offset <= limit {
+                        members.insert(index, data);
+                    }
+                }
+            }
+        }
+        Ok(members)
     }
 
     pub fn read_stream_data(&self, obj: &RefStream, locator: &dyn Locator) -> Result<Box<dyn BufRead + '_>, Error>
     {
         let res = BorrowedResolver { reader: self, locator };
-        let len = res.resolve_obj(obj.dict.lookup(b"Length").to_owned())?.num_value();
-        let filters = codecs::parse_filters(&obj.dict, &res)?;
+        let diag = &**self.parser.diagnostics();
+        let filters = codecs::parse_filters_with_diagnostics(&obj.dict, &res, diag)?;
+        let codec_in = self.read_stream_raw(obj, locator)?;
+        let codec_out = codecs::decode_with_diagnostics(codec_in, &filters, diag);
+        Ok(codec_out)
+    }
+
+    /// Like [`BaseReader::read_stream_data()`], but reads all the decoded data into a `Vec`, so 
+    /// that no borrow of the parser is held after returning.
+    pub fn read_stream_vec(&self, obj: &RefStream, locator: &dyn Locator) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::new();
+        self.read_stream_data(obj, locator)?.read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    pub fn read_stream_raw(&self, obj: &RefStream, locator: &dyn Locator) -> Result<Box<dyn BufRead + '_>, Error> {
+        let res = BorrowedResolver { reader: self, locator };
+        let len_obj = obj.dict.lookup(b"Length");
+        let mut non_integral = None;
+        let mut len = match res.resolve_obj(len_obj.to_owned())? {
+            // Some producers write an integral length as a real number.
+            Object::Number(Number::Real(len)) if len >= 0. && len.fract() == 0. && len <= u64::MAX as f64 =>
+                Some(len as u64),
+            Object::Number(Number::Real(len)) if len.fract() != 0. => {
+                non_integral = Some(len);
+                None
+            },
+            obj => obj.num_value::<u64>()
+        };
+        if let (Some(full), true, Object::Ref(_)) = (len, self.trim_trailing_eol, len_obj) {
+            let tail_len = full.min(2);
+            // A /Length reaching beyond the addressable range is left untrimmed.
+            if let Some(tail_pos) = obj.data.checked_add(full - tail_len) {
+                let mut tail = Vec::new();
+                self.parser.read_raw(tail_pos)?.take(tail_len).read_to_end(&mut tail)?;
+                len = Some(match tail[..] {
+                    [b'\r', b'\n'] => full - 2,
+                    [.., b'\r' | b'\n'] => full - 1,
+                    _ => full
+                });
+            }
+        }
         let reader = self.parser.read_raw(obj.data)?;
-        let codec_in: Box<dyn BufRead> = match len {
+        Ok(match len {
             Some(len) => Box::new(reader.take(len)),
             None => {
-                log::warn!("Stream with invalid or missing /Length found, reading until endstream.");
+                self.parser.diagnostics().report(match non_integral {
+                    Some(length) => Event::NonIntegralLength { offset: obj.data, length },
+                    None => Event::MissingLength(obj.data)
+                });
                 Box::new(EndstreamReader::new(reader))
             }
-        };
-        let codec_out = codecs::decode(codec_in, &filters);
-        Ok(codec_out)
+        })
+    }
+
+    pub fn stream_true_length(&self, obj: &RefStream) -> Result<u64, Error> {
+        let mut reader = EndstreamReader::new(self.parser.read_raw(obj.data)?);
+        let (mut len, mut tail) = (0, [0u8; 2]);
+        loop {
+            let buf = reader.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            for &byte in buf.iter().rev().take(2).rev() {
+                tail = [tail[1], byte];
+            }
+            let buf_len = buf.len();
+            reader.consume(buf_len);
+            len += buf_len as u64;
+        }
+        drop(reader);
+        let mut keyword = [0u8; 9];
+        self.parser.read_raw(obj.data + len)?.read_exact(&mut keyword)
+            .map_err(|_| Error::Parse("endstream not found"))?;
+        if &keyword != b"endstream" {
+            return Err(Error::Parse("endstream not found"));
+        }
+        // The end-of-line marker preceding `endstream` is not part of the data.
+        Ok(match tail {
+            [b'\r', b'\n'] if len >= 2 => len - 2,
+            [_, b'\r' | b'\n'] => len - 1,
+            _ => len
+        })
     }
 }
 
 struct XRefIterator<'a, T: BufRead + Seek> {
     parser: &'a FileParser<T>,
     queue: VecDeque<(Offset, bool)>,
+    visited: BTreeSet<Offset>,
+    /// The offset and `/Size` of the last section visited along the `/Prev` chain.
+    newer: Option<(Offset, ObjNum)>,
 }
 
 impl<'a, T: BufRead + Seek> XRefIterator<'a, T> {
     fn new(parser: &'a FileParser<T>, entry: Offset) -> Self {
-        Self { parser, queue: VecDeque::from([(entry, false)]) }
+        Self { parser, queue: VecDeque::from([(entry, false)]), visited: BTreeSet::new(), newer: None }
     }
 }
 
@@ -141,27 +328,54 @@ impl<T: BufRead + Seek> Iterator for XRefIterator<'_, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let (offset, is_aside) = self.queue.pop_front()?;
-        let xref = match self.parser.read_xref_at(offset) {
+        if !self.visited.insert(offset) {
+            self.parser.diagnostics().report(Event::BreakingXRefChain(offset));
+            self.queue.clear();
+            return None;
+        }
+        let res = if is_aside {
+            match self.parser.read_at(offset) {
+                Ok(Structural::Object(_, Object::Stream(stm))) if !stm.is_xref() => {
+                    self.parser.diagnostics().report(Event::XRefStmNotXRef(offset));
+                    return self.next();
+                },
+                Ok(Structural::Object(oref, obj)) => self.parser.read_xref_stream(oref, obj),
+                Ok(Structural::XRefSec(xref)) => Ok(xref),
+                Err(err) => Err(err)
+            }
+        } else {
+            self.parser.read_xref_at(offset)
+        };
+        let xref = match res {
             Ok(xref) => xref,
-            Err(err) => {
-                log::error!("Error reading xref at {offset}: {err}");
+            Err(error) => {
+                self.parser.diagnostics().report(Event::BrokenXRef { offset, error });
                 return None;
             }
         };
         if matches!(xref.tpe, XRefType::Table) {
-            if let Some(offset) = xref.dict.lookup(b"XRefStm").num_value() {
+            if let Some(stm_offset) = xref.dict.lookup(b"XRefStm").num_value() {
                 if !is_aside {
-                    self.queue.push_back((offset, true));
+                    self.queue.push_back((stm_offset, true));
                 } else {
-                    log::warn!("/XRefStm pointed to a classical section.");
+                    self.parser.diagnostics().report(Event::XRefStmNotStream(offset));
                 }
             }
         }
-        if let Some(offset) = xref.dict.lookup(b"Prev").num_value() {
+        if !is_aside {
+            if let Some((newer_offset, newer_size)) = self.newer {
+                if newer_size < xref.size {
+                    self.parser.diagnostics().report(Event::ShrinkingXRefSize {
+                        offset: newer_offset, size: newer_size, prev_size: xref.size });
+                }
+            }
+            self.newer = Some((offset, xref.size));
+        }
+        if let Some(prev) = xref.dict.lookup(b"Prev").num_value() {
             if !is_aside {
-                self.queue.push_back((offset, false));
+                self.queue.push_back((prev, false));
             } else {
-                log::warn!("Ignoring /Prev in a /XRefStm.");
+                self.parser.diagnostics().report(Event::PrevInXRefStm(offset));
             }
         }
         Some((offset, xref))
@@ -206,6 +420,27 @@ mod tests {
         assert_eq!(s, "test");
     }
 
+    #[test]
+    fn test_image_passthrough() {
+        let source = b"1 0 obj <</Length 8 /Filter /DCTDecode>> stream\n\xFF\xD8\xFF\xE0\x00\x10JF\nendstream endobj";
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(&source[..])));
+        let stm = rdr.read_uncompressed(0, &ObjRef { num: 1, gen: 0 })
+            .unwrap()
+            .into_stream()
+            .unwrap();
+        let fil = codecs::parse_filters(&stm.dict, &()).unwrap();
+        assert_eq!(codecs::image_format(&fil), Some(codecs::ImageFormat::Jpeg));
+        let mut data = rdr.read_stream_data(&stm, &()).unwrap();
+        let mut s = Vec::new();
+        data.read_to_end(&mut s).unwrap();
+        drop(data);
+        assert_eq!(s, b"\xFF\xD8\xFF\xE0\x00\x10JF");
+
+        let dict = Dict::from(vec![(Name::from(b"Filter"), Object::Array(vec![
+            Object::new_name(b"DCTDecode"), Object::new_name(b"FlateDecode")]))]);
+        assert!(codecs::parse_filters(&dict, &()).is_err());
+    }
+
     #[test]
     fn test_xref_chaining() {
         let fp = FileParser::new(BufReader::new(File::open("src/tests/hybrid.pdf").unwrap()));
@@ -230,7 +465,19 @@ mod tests {
         let fp = FileParser::new(BufReader::new(File::open("src/tests/circular.pdf").unwrap()));
         let mut iter = BaseReader::read_xref_chain(&fp, fp.entrypoint().unwrap());
         assert_eq!(iter.next().unwrap().0, 9);
-        assert_eq!(iter.next().unwrap().0, 9);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_xref_self_prev() {
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let startxref = FileParser::new(Cursor::new(&data)).entrypoint().unwrap();
+        let data = utils::make_pdf_with_trailer(&["<< /Type /Catalog >>"], &format!("/Prev {startxref}"));
+        let fp = FileParser::new(Cursor::new(&data));
+        let mut iter = BaseReader::read_xref_chain(&fp, fp.entrypoint().unwrap());
+        assert_eq!(iter.next().unwrap().0, startxref);
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
     }
 
     #[test]
@@ -248,7 +495,7 @@ mod tests {
         drop(data);
         assert_eq!(s, b"BT /F1 12 Tf 72 720 Td (Hello, PDF 1.5!) Tj ET");
 
-        // Indirect length - does not exclude the final EOL
+        // Indirect length - does not exclude the final EOL unless trim_trailing_eol is set
         let fp = FileParser::new(BufReader::new(File::open("src/tests/updates.pdf").unwrap()));
         let rdr = BaseReader::new(fp);
         let stm = rdr.read_uncompressed(9, &ObjRef { num: 1, gen: 0 })
@@ -262,6 +509,23 @@ mod tests {
         assert_eq!(s, b"Test 1\n");
     }
 
+    #[test]
+    fn test_read_stream_vec() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Length 3 >>\nstream\nabc\nendstream",
+            "(other)",
+        ]);
+        let fp = FileParser::new(Cursor::new(data));
+        let xref = fp.read_xref_at(fp.entrypoint().unwrap()).unwrap();
+        let rdr = BaseReader::new(fp);
+        let stm = rdr.resolve(&ObjRef { num: 2, gen: 0 }, &xref).unwrap().into_stream().unwrap();
+        let data = rdr.read_stream_vec(&stm, &xref).unwrap();
+        let obj = rdr.resolve(&ObjRef { num: 3, gen: 0 }, &xref).unwrap();
+        assert_eq!(data, b"abc");
+        assert_eq!(obj, Object::new_string(b"other"));
+    }
+
     #[test]
     fn test_objstm_caching() {
         use crate::parser::bp::ByteProvider;
@@ -280,7 +544,8 @@ mod tests {
 
         let objstms = rdr.objstms.borrow();
         assert!(!objstms.is_empty());
-        let line = objstms.get(&4973).unwrap().as_ref().unwrap().source.as_slice().read_line_excl().unwrap();
+        let ObjStmSource::Buffered(source) = &objstms.get(&4973).unwrap().as_ref().unwrap().source else { panic!() };
+        let line = source.as_slice().read_line_excl().unwrap();
         assert_eq!(line, b"<</Font<</F1 5 0 R>>/ProcSet[/PDF/Text/ImageC/ImageB/ImageI]>>");
         drop(objstms);
 
@@ -295,7 +560,7 @@ mod tests {
         let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
         let objstm = rdr.read_objstm(0, &ObjRef { num: 1, gen: 0 }, &()).unwrap();
         assert_eq!(objstm.entries, vec![(2, 0), (3, 1), (4, 2)]);
-        assert_eq!(objstm.source, b"614");
+        assert!(matches!(objstm.source, ObjStmSource::Buffered(ref source) if source == b"614"));
 
         struct MockLocator();
         impl Locator for MockLocator {
@@ -317,6 +582,135 @@ mod tests {
             Object::Number(Number::Int(4)));
     }
 
+    #[test]
+    fn test_objstm_lazy() {
+        let large = format!("({})", "x".repeat(16 * OBJSTM_LAZY_THRESHOLD as usize));
+        let data = crate::utils::make_pdf_objstm(&["<< /Type /Catalog >>"], &["[1 2]", &large, "42"]);
+        let fp = FileParser::new(Cursor::new(data));
+        let xref = fp.read_xref_at(fp.entrypoint().unwrap()).unwrap();
+        let rdr = BaseReader::new(fp);
+        let res = BorrowedResolver { reader: &rdr, locator: &xref };
+        assert_eq!(res.resolve_ref(&ObjRef { num: 4, gen: 0 }).unwrap(), Object::Number(Number::Int(42)));
+        assert_eq!(res.resolve_ref(&ObjRef { num: 2, gen: 0 }).unwrap(), Object::Array(vec![
+            Object::Number(Number::Int(1)), Object::Number(Number::Int(2))]));
+
+        // Only the header and the small members are kept in memory.
+        let objstms = rdr.objstms.borrow();
+        let objstm = objstms.values().next().unwrap().as_ref().unwrap();
+        assert_eq!(objstm.entries.len(), 3);
+        let ObjStmSource::Lazy { members, .. } = &objstm.source else { panic!() };
+        assert_eq!(members.keys().copied().collect::<Vec<_>>(), vec![0, 2]);
+        drop(objstms);
+
+        let Object::String(s) = res.resolve_ref(&ObjRef { num: 3, gen: 0 }).unwrap() else { panic!() };
+        assert_eq!(s.len(), 16 * OBJSTM_LAZY_THRESHOLD as usize);
+    }
+
+    #[test]
+    fn test_read_objstm_first() {
+        let source = "1 0 obj <</Type/ObjStm /N 3 /First 20 /Length 14>> stream
+2 0 3 1 4 2614endstream endobj";
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
+        assert!(matches!(rdr.read_objstm(0, &ObjRef { num: 1, gen: 0 }, &()),
+            Err(crate::Error::Parse("malformed object stream (/First out of range)"))));
+
+        let source = "1 0 obj <</Type/ObjStm /N 4 /First 11 /Length 14>> stream
+2 0 3 1 4 2614endstream endobj";
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
+        assert!(matches!(rdr.read_objstm(0, &ObjRef { num: 1, gen: 0 }, &()),
+            Err(crate::Error::Parse("malformed object stream header"))));
+    }
+
+    #[test]
+    fn test_read_objstm_offsets() {
+        let source = "1 0 obj <</Type/ObjStm /N 3 /First 11 /Length 14>> stream
+2 2 3 1 4 0614endstream endobj";
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
+        assert!(matches!(rdr.read_objstm(0, &ObjRef { num: 1, gen: 0 }, &()),
+            Err(crate::Error::Parse("malformed object stream header (offsets)"))));
+
+        let source = "1 0 obj <</Type/ObjStm /N 3 /First 11 /Length 14>> stream
+2 0 3 1 4 5614endstream endobj";
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
+        assert!(matches!(rdr.read_objstm(0, &ObjRef { num: 1, gen: 0 }, &()),
+            Err(crate::Error::Parse("malformed object stream header (offsets)"))));
+    }
+
+    #[test]
+    fn test_objstm_mismatch() {
+        let source = "1 0 obj <</Type/ObjStm /N 2 /First 8 /Length 11>> stream
+2 0 5 1 614endstream endobj";
+        struct MockLocator();
+        impl Locator for MockLocator {
+            fn locate(&self, objref: &ObjRef) -> Option<Record> {
+                match objref.num {
+                    1 => Some(Record::Used { gen: 0, offset: 0 }),
+                    2..=3 => Some(Record::Compr { num_within: 1, index: (objref.num as ObjIndex) - 2 }),
+                    _ => panic!()
+                }
+            }
+        }
+        let mut rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
+        assert!(rdr.resolve(&ObjRef { num: 3, gen: 0 }, &MockLocator()).is_err());
+        assert_eq!(rdr.resolve(&ObjRef { num: 2, gen: 0 }, &MockLocator()).unwrap(),
+            Object::Number(Number::Int(6)));
+        rdr.lenient = true;
+        assert_eq!(rdr.resolve(&ObjRef { num: 3, gen: 0 }, &MockLocator()).unwrap(),
+            Object::Number(Number::Int(14)));
+    }
+
+    #[test]
+    fn test_resolve_deep() {
+        let source = "1 0 obj (one) endobj\n2 0 obj /Two endobj";
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
+        struct MockLocator();
+        impl Locator for MockLocator {
+            fn locate(&self, objref: &ObjRef) -> Option<Record> {
+                match objref.num {
+                    1 => Some(Record::Used { gen: 0, offset: 0 }),
+                    2 => Some(Record::Used { gen: 0, offset: 21 }),
+                    _ => None
+                }
+            }
+        }
+        let res = BorrowedResolver { reader: &rdr, locator: &MockLocator() };
+        let dict = Object::Dict(Dict::from(vec![
+            (Name::from(b"A"), Object::Ref(ObjRef { num: 1, gen: 0 })),
+            (Name::from(b"B"), Object::Array(vec![Object::Ref(ObjRef { num: 2, gen: 0 })])),
+            (Name::from(b"C"), Object::Ref(ObjRef { num: 3, gen: 0 })),
+            (Name::from(b"D"), Object::Number(Number::Int(4))),
+        ]));
+        assert_eq!(res.resolve_deep(dict).unwrap(), Object::Dict(Dict::from(vec![
+            (Name::from(b"A"), Object::new_string(b"one")),
+            // only the first level is resolved
+            (Name::from(b"B"), Object::Array(vec![Object::Ref(ObjRef { num: 2, gen: 0 })])),
+            (Name::from(b"C"), Object::Null),
+            (Name::from(b"D"), Object::Number(Number::Int(4))),
+        ])));
+        assert_eq!(res.resolve_deep(Object::Array(vec![Object::Ref(ObjRef { num: 2, gen: 0 })])).unwrap(),
+            Object::Array(vec![Object::new_name(b"Two")]));
+    }
+
+    #[test]
+    fn test_read_stream_overflow_flate() {
+        use std::io::Write;
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"123").unwrap();
+        let data = enc.finish().unwrap();
+        let mut source = format!("1 0 obj <</Length {} /Filter /FlateDecode>> stream\n", data.len() + 20).into_bytes();
+        source.extend_from_slice(&data);
+        source.extend_from_slice(b"\nendstream endobj");
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
+        let stm = rdr.read_uncompressed(0, &ObjRef { num: 1, gen: 0 })
+            .unwrap()
+            .into_stream()
+            .unwrap();
+        let mut data = rdr.read_stream_data(&stm, &()).unwrap();
+        let mut s = Vec::new();
+        data.read_to_end(&mut s).unwrap();
+        assert_eq!(s, b"123");
+    }
+
     #[test]
     fn test_read_stream_overflow() {
         let source = "1 0 obj <</Length 10>> stream\n123\nendstream endobj";
@@ -355,7 +749,7 @@ mod tests {
         drop(data);
         assert_eq!(s, b"123");
 
-        let source = "1 0 obj <<>> stream\n123\n45endstream endobj";
+        let source = "1 0 obj <<>> stream\n123";
         let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
         let stm = rdr.read_uncompressed(0, &ObjRef { num: 1, gen: 0 })
             .unwrap()
@@ -365,9 +759,13 @@ mod tests {
         let mut s = Vec::new();
         data.read_to_end(&mut s).unwrap();
         drop(data);
-        assert_eq!(s, b"123\n45");
+        assert_eq!(s, b"123");
+    }
 
-        let source = "1 0 obj <<>> stream\n123";
+    #[test]
+    fn test_read_stream_no_eol() {
+        // endstream not preceded by an end-of-line marker is a part of the data
+        let source = "1 0 obj <<>> stream\n123\n45endstream endobj";
         let rdr = BaseReader::new(FileParser::new(Cursor::new(source)));
         let stm = rdr.read_uncompressed(0, &ObjRef { num: 1, gen: 0 })
             .unwrap()
@@ -377,6 +775,6 @@ mod tests {
         let mut s = Vec::new();
         data.read_to_end(&mut s).unwrap();
         drop(data);
-        assert_eq!(s, b"123");
+        assert_eq!(s, b"123\n45endstream endobj");
     }
 }
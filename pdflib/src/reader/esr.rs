@@ -1,15 +1,51 @@
 use std::io::BufRead;
 
+const ENDSTREAM: &[u8] = b"endstream";
+
+/// Bad character table for the Boyer-Moore-Horspool search for `endstream`.
+const SKIP: [usize; 256] = {
+    let mut table = [ENDSTREAM.len(); 256];
+    let mut i = 0;
+    while i < ENDSTREAM.len() - 1 {
+        table[ENDSTREAM[i] as usize] = ENDSTREAM.len() - 1 - i;
+        i += 1;
+    }
+    table
+};
+
 pub struct EndstreamReader<T: BufRead> {
     inner: T,
     buf: Vec<u8>,
     cur_index: usize,
-    endstream: Option<usize>,
+    // End of data known not to be part of `endstream`, which can be released to the caller.
+    avail: usize,
+    // The byte preceding `buf`, if any data have been dropped from it.
+    before: Option<u8>,
+    done: bool,
 }
 
 impl<T: BufRead> EndstreamReader<T> {
     pub fn new(inner: T) -> Self {
-        Self { inner, buf: Vec::new(), cur_index: 0, endstream: None }
+        Self { inner, buf: Vec::new(), cur_index: 0, avail: 0, before: None, done: false }
+    }
+
+    /// Searches `buf` for `endstream`, starting from `self.avail`. If not found, returns `Err`
+    /// with the first position which could not be tested yet.
+    ///
+    /// Only an occurrence at the start of the data or following an end-of-line marker counts.
+    fn search(&self) -> Result<usize, usize> {
+        let last = ENDSTREAM.len() - 1;
+        let mut pos = self.avail;
+        while let Some(&byte) = self.buf.get(pos + last) {
+            if byte == ENDSTREAM[last] && self.buf[pos..(pos + last)] == ENDSTREAM[..last] {
+                let prev = if pos == 0 { self.before } else { Some(self.buf[pos - 1]) };
+                if matches!(prev, None | Some(b'\n' | b'\r')) {
+                    return Ok(pos);
+                }
+            }
+            pos += SKIP[byte as usize];
+        }
+        Err(pos)
     }
 }
 
@@ -30,29 +66,105 @@ impl<T: BufRead> std::io::Read for EndstreamReader<T> {
 
 impl<T: BufRead> BufRead for EndstreamReader<T> {
     fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
-        const ENDSTREAM: &[u8] = b"endstream";
-        if self.cur_index < self.buf.len() {
-            match self.endstream {
-                Some(end_index) => Ok(&self.buf[self.cur_index..end_index]),
-                None => Ok(&self.buf[self.cur_index..])
+        while self.cur_index == self.avail && !self.done {
+            if self.cur_index > 0 {
+                self.before = Some(self.buf[self.cur_index - 1]);
             }
-        } else {
-            use crate::parser::bp::ByteProvider;
+            self.buf.drain(0..self.cur_index);
+            self.avail = 0;
             self.cur_index = 0;
-            self.buf = match self.inner.read_line_incl() {
-                Ok(buf) => buf,
-                Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(&[]),
-                Err(err) => return Err(err)
-            };
-            self.endstream = self.buf.windows(ENDSTREAM.len()).position(|w| w == ENDSTREAM);
-            match self.endstream {
-                Some(end_index) => Ok(&self.buf[0..end_index]),
-                None => Ok(&self.buf[..])
+            let chunk = self.inner.fill_buf()?;
+            if chunk.is_empty() {
+                self.avail = self.buf.len();
+                self.done = true;
+                break;
+            }
+            let chunk_len = chunk.len();
+            self.buf.extend_from_slice(chunk);
+            self.inner.consume(chunk_len);
+            match self.search() {
+                Ok(end_index) => {
+                    self.avail = end_index;
+                    self.done = true;
+                },
+                Err(next) => self.avail = next
             }
         }
+        Ok(&self.buf[self.cur_index..self.avail])
     }
 
     fn consume(&mut self, amt: usize) {
-        self.cur_index += amt;
+        self.cur_index = std::cmp::min(self.cur_index + amt, self.avail);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Read};
+
+    fn naive(data: &[u8]) -> &[u8] {
+        let end = (0..data.len())
+            .find(|&pos| data[pos..].starts_with(ENDSTREAM) && (pos == 0 || matches!(data[pos - 1], b'\n' | b'\r')))
+            .unwrap_or(data.len());
+        &data[..end]
+    }
+
+    fn read_all(data: &[u8], capacity: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        EndstreamReader::new(BufReader::with_capacity(capacity, data))
+            .read_to_end(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_endstream() {
+        let cases: &[&[u8]] = &[
+            b"",
+            b"endstream",
+            b"data\nendstream\nendobj",
+            b"data\r\nendstream",
+            b"data\rendstream more endstream",
+            b"dataendstream\nxendstream\nendstream\nrest",
+            b"endstreaendstream\nendstream",
+            b"no terminator",
+            b"\nendstrea",
+            b"dataendstream",
+            b"dataendstream\rendstream",
+            b"x\nendstreamendstream",
+        ];
+        for &data in cases {
+            for capacity in [1, 2, 3, 5, 8, 9, 10, 64] {
+                assert_eq!(read_all(data, capacity), naive(data), "{data:?} / {capacity}");
+            }
+        }
+
+        // Not preceded by an end-of-line marker: not the terminator.
+        for capacity in [1, 4, 5, 64] {
+            assert_eq!(read_all(b"dataendstream", capacity), b"dataendstream");
+            assert_eq!(read_all(b"dataendstream\nendstream", capacity), b"dataendstream\n");
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_endstream() {
+        let mut data = Vec::with_capacity(8 << 20);
+        let mut state = 1u32;
+        while data.len() < 8 << 20 {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            data.push((state >> 16) as u8);
+        }
+        data.extend_from_slice(b"\nendstream\nendobj\n");
+
+        let start = std::time::Instant::now();
+        let expected = naive(&data).len();
+        let naive_time = start.elapsed();
+        let start = std::time::Instant::now();
+        let actual = read_all(&data, 8192).len();
+        let bmh_time = start.elapsed();
+        assert_eq!(actual, expected);
+        println!("naive: {naive_time:?}, Boyer-Moore-Horspool: {bmh_time:?}");
     }
 }
@@ -6,7 +6,7 @@ use crate::base::*;
 use crate::base::types::*;
 use crate::parser::FileParser;
 
-use super::base::BaseReader;
+use super::base::{BaseReader, BorrowedResolver};
 
 /// Work in progress.
 pub struct FullReader<T: BufRead + Seek> {
@@ -26,7 +26,7 @@ impl<T: BufRead + Seek> FullReader<T> {
         let entry = match parser.entrypoint() {
             Ok(offset) => Some(offset),
             Err(err) => {
-                log::error!("Entrypoint not found: {err}");
+                log_error!("Entrypoint not found: {err}");
                 None
             }
         };
@@ -43,10 +43,6 @@ impl<T: BufRead + Seek> FullReader<T> {
         let mut order = Vec::new();
         let mut next_rc = None;
         for (offset, xref) in BaseReader::read_xref_chain(&self.base.parser, entry) {
-            if order.iter().any(|(o, _)| o == &offset) {
-                log::warn!("Breaking xref chain detected at {offset}.");
-                break;
-            }
             if let Some(rc) = self.xrefs.get(&offset) {
                 next_rc = Some(rc.clone());
                 break;
@@ -61,8 +57,8 @@ impl<T: BufRead + Seek> FullReader<T> {
     }
 
     pub fn objects(&self) -> impl Iterator<Item = (ObjRef, Result<(Object, impl Locator), Error>)> + '_ {
-        self.xrefs.iter()
-            .flat_map(|(_, rc)| rc.curr.map.iter().map(move |(num, rec)| (num, rec, Rc::clone(rc))))
+        self.xrefs.values()
+            .flat_map(|rc| rc.curr.map.iter().map(move |(num, rec)| (num, rec, Rc::clone(rc))))
             // all used objects in all xrefs + back-reference to section
             .flat_map(move |(&num, rec, link)| match *rec {
                 Record::Used{gen, offset} => {
@@ -86,11 +82,67 @@ impl<T: BufRead + Seek> FullReader<T> {
             .unwrap_or(&() as &dyn Locator)
     }
 
+    /// The trailer dictionary of the newest cross-reference section, i.e., the one pointed to by 
+    /// `startxref`. This is empty if the section could not be read.
+    pub fn trailer(&self) -> &Dict {
+        static EMPTY: Dict = Dict::new();
+        self.entry
+            .and_then(|offset| self.xrefs.get(&offset))
+            .map(|rc| &rc.curr.dict)
+            .unwrap_or(&EMPTY)
+    }
+
+    /// Resolves an object reference using [`FullReader::base_locator()`], i.e., according to the 
+    /// newest revision of the file.
+    pub fn resolve(&self, objref: &ObjRef) -> Result<Object, Error> {
+        self.base.resolve(objref, self.base_locator())
+    }
+
     pub fn read_stream_data(&self, obj: &RefStream, locator: &dyn Locator) -> Result<Box<dyn BufRead + '_>, Error> {
         self.base.read_stream_data(obj, locator)
     }
 }
 
+/// A [`Resolver`] for the objects of a [`FullReader`], looking up object references through a 
+/// given [`Locator`], like [`FullReader::base_locator()`] or one obtained from 
+/// [`FullReader::objects()`].
+///
+/// ```
+/// use std::io::Cursor;
+/// use pdflib::{ObjRef, Resolver};
+/// use pdflib::codecs::{self, Filter};
+/// use pdflib::reader::{FullReader, RefResolver};
+///
+/// let mut data = b"%PDF-1.7\n".to_vec();
+/// let off1 = data.len();
+/// data.extend_from_slice(b"1 0 obj\n<< /Length 3 /Filter 2 0 R >>\nstream\n41>\nendstream\nendobj\n");
+/// let off2 = data.len();
+/// data.extend_from_slice(b"2 0 obj\n/ASCIIHexDecode\nendobj\n");
+/// let start = data.len();
+/// data.extend_from_slice(format!("xref\n0 3\n0000000000 65535 f \n{off1:010} 00000 n \n\
+///     {off2:010} 00000 n \ntrailer\n<< /Size 3 >>\nstartxref\n{start}\n%%EOF\n").as_bytes());
+///
+/// let rdr = FullReader::new(Cursor::new(data));
+/// let res = RefResolver::new(&rdr, rdr.base_locator());
+/// let stm = res.resolve_ref(&ObjRef { num: 1, gen: 0 }).unwrap().into_stream().unwrap();
+/// let filters = codecs::parse_filters(&stm.dict, &res).unwrap();
+/// assert!(matches!(filters[..], [Filter::AsciiHex]));
+/// ```
+pub struct RefResolver<'a, T: BufRead + Seek>(BorrowedResolver<'a, T>);
+
+impl<'a, T: BufRead + Seek> RefResolver<'a, T> {
+    /// Creates a `RefResolver` for `reader` using `locator`.
+    pub fn new(reader: &'a FullReader<T>, locator: &'a dyn Locator) -> Self {
+        Self(BorrowedResolver { reader: &reader.base, locator })
+    }
+}
+
+impl<T: BufRead + Seek> Resolver for RefResolver<'_, T> {
+    fn resolve_ref(&self, objref: &ObjRef) -> Result<Object, Error> {
+        self.0.resolve_ref(objref)
+    }
+}
+
 #[doc(hidden)]
 impl Locator for Rc<XRefLink> {
     fn locate(&self, objref: &ObjRef) -> Option<Record> {
@@ -104,7 +156,6 @@ mod tests {
     use super::*;
     use std::io::*;
     use std::fs::*;
-    use crate::reader::base::BorrowedResolver;
     use crate::parser::bp::ByteProvider;
 
     #[test]
@@ -141,6 +192,29 @@ mod tests {
         //etc.
     }
 
+    #[test]
+    fn test_resolve() {
+        let data = crate::utils::make_pdf(&["<< /Type /Catalog /Pages 2 0 R >>", "<< /Type /Pages /Kids [] /Count 0 >>"]);
+        let rdr = FullReader::new(Cursor::new(data));
+        let oref = ObjRef { num: 2, gen: 0 };
+        let obj = rdr.resolve(&oref).unwrap();
+        assert_eq!(obj, BorrowedResolver { reader: &rdr.base, locator: rdr.base_locator() }.resolve_ref(&oref).unwrap());
+        assert!(obj.as_dict().unwrap().has_type(b"Pages"));
+        assert_eq!(rdr.resolve(&ObjRef { num: 5, gen: 0 }).unwrap(), Object::Null);
+    }
+
+    #[test]
+    fn test_trailer() {
+        let data = crate::utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let full = FullReader::new(Cursor::new(&data));
+        let simple = crate::reader::SimpleReader::new(Cursor::new(&data)).unwrap();
+        assert_eq!(full.trailer().lookup(b"Root"), &Object::Ref(ObjRef { num: 1, gen: 0 }));
+        assert_eq!(full.trailer().lookup(b"Root"), simple.trailer().lookup(b"Root"));
+
+        let full = FullReader::new(Cursor::new(b"%PDF-1.7\n"));
+        assert_eq!(full.trailer(), &Dict::default());
+    }
+
     #[test]
     fn test_xref_chaining() {
         let rdr = FullReader::new(BufReader::new(File::open("src/tests/hybrid.pdf").unwrap()));
@@ -151,9 +225,9 @@ mod tests {
         assert_eq!(rdr.base_locator() as *const dyn Locator as *const (),
             x912 as &dyn Locator as *const dyn Locator as *const ());
         // main table's /XRefStm
-        assert_eq!(Rc::as_ptr(x912.next.as_ref().unwrap()), Rc::as_ptr(&x759));
+        assert_eq!(Rc::as_ptr(x912.next.as_ref().unwrap()), Rc::as_ptr(x759));
         // main table's /Prev
-        assert_eq!(Rc::as_ptr(x759.next.as_ref().unwrap()), Rc::as_ptr(&x417));
+        assert_eq!(Rc::as_ptr(x759.next.as_ref().unwrap()), Rc::as_ptr(x417));
         assert!(x417.next.is_none());
 
         // 912 itself does not define 4 0
@@ -180,9 +254,9 @@ mod tests {
         let x322 = rdr.xrefs.get(&322).unwrap();
         let x87 = rdr.xrefs.get(&87).unwrap();
         // main table's /Prev
-        assert_eq!(Rc::as_ptr(x510.next.as_ref().unwrap()), Rc::as_ptr(&x322));
+        assert_eq!(Rc::as_ptr(x510.next.as_ref().unwrap()), Rc::as_ptr(x322));
         // /Prev's /Prev
-        assert_eq!(Rc::as_ptr(x322.next.as_ref().unwrap()), Rc::as_ptr(&x87));
+        assert_eq!(Rc::as_ptr(x322.next.as_ref().unwrap()), Rc::as_ptr(x87));
         assert!(x87.next.is_none());
 
         let stm = BorrowedResolver { reader: &rdr.base, locator: x87 }
@@ -2,6 +2,8 @@ mod base;
 mod simple;
 mod full;
 mod esr;
+mod streaming;
 
 pub use simple::SimpleReader;
-pub use full::FullReader;
+pub use full::{FullReader, RefResolver};
+pub use streaming::{StreamingReader, StreamingEvent};
@@ -1,8 +1,12 @@
-use std::io::{BufRead, Seek};
+use std::io::{Read, BufRead, Seek, Write};
+use std::sync::Arc;
+#[cfg(feature = "rayon")]
+use std::collections::BTreeMap;
 
 use crate::base::*;
 use crate::base::types::*;
 use crate::parser::FileParser;
+use crate::codecs::{self, Filter};
 
 use super::base::BaseReader;
 
@@ -15,6 +19,7 @@ use super::base::BaseReader;
 pub struct SimpleReader<T: BufRead + Seek> {
     base: BaseReader<T>,
     pub xref: XRef,
+    sections: Vec<(Offset, Dict)>,
 }
 
 impl<T: BufRead + Seek> SimpleReader<T> {
@@ -22,26 +27,132 @@ impl<T: BufRead + Seek> SimpleReader<T> {
     ///
     /// Returns with an error if the cross-reference table is not found or damaged.
     pub fn new(source: T) -> Result<Self, Error> {
-        let parser = FileParser::new(source);
+        Self::with_parser(FileParser::new(source))
+    }
+
+    /// Like [`SimpleReader::new()`], but reports recoverable problems to the given 
+    /// [`Diagnostics`] sink instead of the `log` crate.
+    pub fn with_diagnostics(source: T, diag: Arc<dyn Diagnostics>) -> Result<Self, Error> {
+        let mut parser = FileParser::new(source);
+        parser.set_diagnostics(diag);
+        Self::with_parser(parser)
+    }
+
+    fn with_parser(parser: FileParser<T>) -> Result<Self, Error> {
         let entry = parser.entrypoint()?;
-        let xref = Self::build_xref(&parser, entry)?;
+        let (xref, sections) = Self::build_xref(&parser, entry)?;
         let base = BaseReader::new(parser);
-        Ok(Self { base, xref })
+        Ok(Self { base, xref, sections })
     }
 
-    fn build_xref(parser: &FileParser<T>, entry: Offset) -> Result<XRef, Error> {
+    fn build_xref(parser: &FileParser<T>, entry: Offset) -> Result<(XRef, Vec<(Offset, Dict)>), Error> {
         let mut iter = BaseReader::read_xref_chain(parser, entry);
-        let mut order = vec![entry];
-        let mut xref = iter.next().ok_or(Error::Parse("could not parse xref table"))?.1;
+        let (offset, mut xref) = iter.next().ok_or(Error::Parse("could not parse xref table"))?;
+        let mut sections = vec![(offset, xref.dict.clone())];
+        let stm_offset = xref.dict.lookup(b"XRefStm").num_value::<Offset>();
+        let mut had_table = false;
         for (offset, next_xref) in iter {
-            if order.contains(&offset) {
-                log::warn!("Breaking xref chain detected at {offset}.");
-                break;
+            sections.push((offset, next_xref.dict.clone()));
+            // In hybrid-reference files, the classical trailer may rely on the stream for /Root.
+            if Some(offset) == stm_offset && xref.dict.lookup(b"Root") == &Object::Null {
+                if let root @ Object::Ref(_) = next_xref.dict.lookup(b"Root") {
+                    xref.dict = Dict::from(xref.dict.into_iter()
+                        .chain([(Name::from(b"Root"), root.clone())])
+                        .collect::<Vec<_>>());
+                }
             }
+            had_table |= matches!(next_xref.tpe, XRefType::Table);
             xref.merge_prev(next_xref);
-            order.push(offset);
         }
-        Ok(xref)
+        // A stream-based update on top of a classical file must carry its own /Root.
+        if had_table && matches!(xref.tpe, XRefType::Stream(_)) && xref.dict.lookup(b"Root") == &Object::Null {
+            return Err(Error::Parse("xref stream trailer lacks /Root"));
+        }
+        Ok((xref, sections))
+    }
+
+    /// Rebuilds the cross-reference table from the current contents of the source and discards 
+    /// all cached object streams. This allows picking up incremental updates appended to the file 
+    /// after this `SimpleReader` was created.
+    ///
+    /// On error, `self` is left unchanged.
+    pub fn reload(&mut self) -> Result<(), Error> {
+        let entry = self.base.parser.entrypoint()?;
+        (self.xref, self.sections) = Self::build_xref(&self.base.parser, entry)?;
+        self.base.clear_cache();
+        Ok(())
+    }
+
+    /// Sets whether inconsistencies which do not prevent reading, like a mismatched object number 
+    /// in an object stream header, an integer out of range or a malformed dictionary entry, should 
+    /// only produce a warning. Defaults to `false`.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.base.lenient = lenient;
+        self.base.parser.set_lenient(lenient);
+    }
+
+    /// Sets the maximum length of name tokens, see 
+    /// [`ParseOptions::max_name_len`](crate::parser::ParseOptions::max_name_len). Longer names 
+    /// produce an error. Defaults to [`MAX_NAME_LEN`](crate::parser::MAX_NAME_LEN).
+    pub fn set_max_name_len(&mut self, len: usize) {
+        self.base.parser.set_max_name_len(len);
+    }
+
+    /// Sets the maximum nesting depth of arrays and dictionaries, see 
+    /// [`ParseOptions::max_depth`](crate::parser::ParseOptions::max_depth). Deeper objects produce 
+    /// an error. Defaults to [`MAX_DEPTH`](crate::parser::MAX_DEPTH).
+    pub fn set_max_depth(&mut self, depth: usize) {
+        self.base.parser.set_max_depth(depth);
+    }
+
+    /// Sets whether a single end-of-line marker at the end of stream data should be excluded if 
+    /// the stream's `/Length` is given by an indirect reference. Such a marker may be included in 
+    /// the length by mistake when it is computed after writing the data. Defaults to `false`.
+    pub fn set_trim_trailing_eol(&mut self, trim: bool) {
+        self.base.trim_trailing_eol = trim;
+    }
+
+    /// Returns the offset and the trailer dictionary (or the cross-reference stream dictionary) of 
+    /// each cross-reference section, in the order they were discovered, starting from the newest.
+    pub fn section_trailers(&self) -> Vec<(Offset, Dict)> {
+        self.sections.clone()
+    }
+
+    /// The trailer dictionary, i.e., `self.xref.dict`.
+    pub fn trailer(&self) -> &Dict {
+        &self.xref.dict
+    }
+
+    /// The byte offset of the `%PDF` header from the start of the source. This is zero if the 
+    /// header is not found. See [`FileParser::header()`].
+    pub fn header_offset(&self) -> Offset {
+        self.base.parser.header().as_ref().map_or(0, |header| header.start)
+    }
+
+    /// The underlying [`FileParser`].
+    pub fn parser(&self) -> &FileParser<T> {
+        &self.base.parser
+    }
+
+    /// Returns the file identifiers from the `/ID` entry of the trailer: the original one, 
+    /// assigned when the file was first created, and the current one.
+    ///
+    /// If only one identifier is present, it is returned as both. Returns `None` if the entry is 
+    /// missing or malformed.
+    pub fn file_id(&self) -> Option<(Vec<u8>, Vec<u8>)> {
+        match self.xref.dict.lookup(b"ID").as_array()?.as_slice() {
+            [Object::String(orig), Object::String(curr), ..] => Some((orig.clone(), curr.clone())),
+            [Object::String(id)] => Some((id.clone(), id.clone())),
+            _ => None
+        }
+    }
+
+    /// Reads the object physically present at the given offset (relative to `%PDF`), bypassing the 
+    /// cross-reference table. This allows accessing objects superseded by incremental updates.
+    ///
+    /// Fails if the object found at the offset is not `oref`.
+    pub fn read_object_at(&self, offset: Offset, oref: &ObjRef) -> Result<Object, Error> {
+        self.base.read_uncompressed(offset, oref)
     }
 
     /// Iterates over all object numbers marked as used, in increasing number.
@@ -64,6 +175,178 @@ impl<T: BufRead + Seek> SimpleReader<T> {
             })
     }
 
+    /// Iterates over the references of all objects marked as used, including those compressed in 
+    /// object streams, in increasing number.
+    ///
+    /// Unlike [`SimpleReader::objects()`], the objects are not read or parsed.
+    pub fn object_refs(&self) -> impl Iterator<Item = ObjRef> + '_ {
+        self.xref.map.iter()
+            .flat_map(|(&num, rec)| match *rec {
+                Record::Used{gen, ..} => Some(ObjRef{num, gen}),
+                Record::Compr{..} => Some(ObjRef{num, gen: 0}),
+                Record::Free{..} => None
+            })
+    }
+
+    /// Iterates over the records of the merged cross-reference table, i.e., for each object number 
+    /// the record from the newest revision which mentions it, in increasing number.
+    pub fn effective_records(&self) -> impl Iterator<Item = (ObjNum, Record)> + '_ {
+        self.xref.map.iter().map(|(&num, &rec)| (num, rec))
+    }
+
+    /// Writes the records given by [`SimpleReader::effective_records()`] in a human-readable 
+    /// form, one per line.
+    pub fn write_effective_records<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        for (num, rec) in self.effective_records() {
+            writeln!(w, "{num}: {rec}")?;
+        }
+        Ok(())
+    }
+
+    /// The number of objects marked as used, including those compressed in object streams.
+    pub fn len(&self) -> usize {
+        self.xref.map.values()
+            .filter(|rec| !matches!(rec, Record::Free{..}))
+            .count()
+    }
+
+    /// Returns `true` if no objects are marked as used.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parses all objects like [`SimpleReader::objects()`], distributing the work over a `rayon` 
+    /// thread pool. The results are collected by [`ObjRef`].
+    ///
+    /// Since `SimpleReader` can not be shared between threads, each worker parses from its own 
+    /// instance of the source, obtained by calling `open`. This must produce a fresh handle to the 
+    /// same data, e.g., by reopening the same file. The cross-reference table of `self` is shared. 
+    /// Objects compressed in the same object stream are processed together, so that each object 
+    /// stream is only decoded once.
+    #[cfg(feature = "rayon")]
+    pub fn par_objects<F>(&self, open: F) -> BTreeMap<ObjRef, Result<Object, Error>>
+        where F: Fn() -> std::io::Result<T> + Sync + Send
+    {
+        use rayon::prelude::*;
+
+        enum Unit {
+            Single(ObjRef, Offset),
+            ObjStm(ObjNum, Vec<(ObjRef, ObjIndex)>)
+        }
+
+        let mut units = Vec::new();
+        let mut objstms = BTreeMap::<ObjNum, Vec<_>>::new();
+        for (&num, rec) in &self.xref.map {
+            match *rec {
+                Record::Used{gen, offset} => units.push(Unit::Single(ObjRef{num, gen}, offset)),
+                Record::Compr{num_within, index} =>
+                    objstms.entry(num_within).or_default().push((ObjRef{num, gen: 0}, index)),
+                Record::Free{..} => ()
+            }
+        }
+        units.extend(objstms.into_iter().map(|(num_within, members)| Unit::ObjStm(num_within, members)));
+
+        let xref = &self.xref;
+        let lenient = self.base.lenient;
+        let opts = self.base.parser.parse_options();
+        units.into_par_iter()
+            .map_init(
+                || open()
+                    .map(|source| {
+                        let mut parser = FileParser::new(source);
+                        parser.set_parse_options(opts.clone());
+                        let mut base = BaseReader::new(parser);
+                        base.lenient = lenient;
+                        base
+                    })
+                    .map_err(Error::from),
+                |base, unit| -> Vec<(ObjRef, Result<Object, Error>)> {
+                    let base = match base {
+                        Ok(base) => base,
+                        Err(err) => return match unit {
+                            Unit::Single(objref, _) => vec![(objref, Err(err.clone()))],
+                            Unit::ObjStm(_, members) => members.into_iter()
+                                .map(|(objref, _)| (objref, Err(err.clone())))
+                                .collect()
+                        }
+                    };
+                    match unit {
+                        Unit::Single(objref, offset) =>
+                            vec![(objref, base.read_uncompressed(offset, &objref))],
+                        Unit::ObjStm(num_within, members) => members.into_iter()
+                            .map(|(objref, index)| (objref, base.read_compressed(num_within, index, xref, &objref)))
+                            .collect()
+                    }
+                })
+            .flatten_iter()
+            .collect()
+    }
+
+    /// Like [`Resolver::resolve_ref()`], but returns `None` if the object is free or has no record 
+    /// in the cross-reference table, rather than [`Object::Null`].
+    ///
+    /// This allows detecting dangling references, which `resolve_ref()` treats as `null` in 
+    /// accordance with the specification.
+    pub fn resolve_ref_opt(&self, objref: &ObjRef) -> Result<Option<Object>, Error> {
+        self.base.resolve_opt(objref, &self.xref)
+    }
+
+    /// Returns the effective cross-reference [`Record`] of `objref`, telling whether the object 
+    /// is stored at a file offset, compressed in an object stream, or free. The object is not 
+    /// read. Returns `None` if there is no record for `objref`.
+    pub fn locate(&self, objref: &ObjRef) -> Option<Record> {
+        self.xref.locate(objref)
+    }
+
+    /// Returns the number of the object stream containing `objref`, or `None` if it is not a 
+    /// compressed object (including free objects and objects without a record).
+    pub fn container_of(&self, objref: &ObjRef) -> Option<ObjNum> {
+        match self.locate(objref)? {
+            Record::Compr { num_within, .. } => Some(num_within),
+            _ => None
+        }
+    }
+
+    /// Lists the numbers of the objects stored in the object stream `objstm_num`, in the order 
+    /// given by its header. The member objects themselves are not parsed.
+    ///
+    /// The object stream is cached in the same way as when resolving its members.
+    pub fn objstm_members(&self, objstm_num: ObjNum) -> Result<Vec<ObjNum>, Error> {
+        self.base.objstm_members(objstm_num, &self.xref)
+    }
+
+    /// Resolves `obj` using [`Resolver::resolve_obj()`] and returns the result if it is a 
+    /// [`Dict`]. Otherwise, an error is returned.
+    pub fn resolve_dict(&self, obj: &Object) -> Result<Dict, Error> {
+        self.resolve_obj(obj.to_owned())?
+            .into_dict()
+            .ok_or(Error::Parse("expected a dictionary"))
+    }
+
+    /// Resolves `obj` using [`Resolver::resolve_obj()`] and returns the result if it is a 
+    /// [`RefStream`]. Otherwise, an error is returned.
+    pub fn resolve_stream(&self, obj: &Object) -> Result<RefStream, Error> {
+        self.resolve_obj(obj.to_owned())?
+            .into_stream()
+            .ok_or(Error::Parse("expected a stream"))
+    }
+
+    /// Resolves `obj` using [`Resolver::resolve_obj()`] and returns the result if it is a 
+    /// [`Name`]. Otherwise, an error is returned.
+    pub fn resolve_name(&self, obj: &Object) -> Result<Name, Error> {
+        self.resolve_obj(obj.to_owned())?
+            .into_name()
+            .ok_or(Error::Parse("expected a name"))
+    }
+
+    /// Resolves `obj` using [`Resolver::resolve_obj()`] and returns the result if it is an 
+    /// array. Otherwise, an error is returned.
+    pub fn resolve_array(&self, obj: &Object) -> Result<Vec<Object>, Error> {
+        self.resolve_obj(obj.to_owned())?
+            .into_array()
+            .ok_or(Error::Parse("expected an array"))
+    }
+
     /// Creates a `BufRead` reading stream data for a [`RefStream`], after decoding using the 
     /// values of `/Filter` and `/DecodeParms` from the stream dictionary.
     ///
@@ -80,6 +363,141 @@ impl<T: BufRead + Seek> SimpleReader<T> {
     pub fn read_stream_data(&self, obj: &RefStream) -> Result<Box<dyn BufRead + '_>, Error> {
         self.base.read_stream_data(obj, &self.xref)
     }
+
+    /// Like [`SimpleReader::read_stream_data()`], but reads all the decoded data into a `Vec`. 
+    /// Unlike the `BufRead` returned by that method, this does not hold any borrow after 
+    /// returning, so other methods of this `SimpleReader` can be called freely.
+    pub fn read_stream_vec(&self, obj: &RefStream) -> Result<Vec<u8>, Error> {
+        self.base.read_stream_vec(obj, &self.xref)
+    }
+
+    /// Resolves `objref` and, if it refers to a stream, returns its decoded data as in 
+    /// [`SimpleReader::read_stream_data()`]. Otherwise, an error is returned.
+    pub fn read_stream_by_ref(&self, objref: &ObjRef) -> Result<Box<dyn BufRead + '_>, Error> {
+        let stm = self.resolve_stream(&Object::Ref(*objref))?;
+        self.base.read_stream_data(&stm, &self.xref)
+    }
+
+    /// Reads the decoded data of a [`RefStream`] into an [`OwnedObject`], which can be modified 
+    /// and serialized, e.g. using [`BaseObject::write_to()`].
+    ///
+    /// The `/Filter` and `/DecodeParms` entries are removed from the dictionary, except if the 
+    /// last filter is an image filter passed through undecoded, in which case only that one is 
+    /// kept.
+    pub fn load_stream(&self, obj: &RefStream) -> Result<OwnedObject, Error> {
+        let filters = self.stream_filters(obj)?;
+        let mut dict = obj.dict.iter()
+            .filter(|(key, _)| key != b"Filter" && key != b"DecodeParms" && key != b"DP" && key != b"DL")
+            .cloned()
+            .collect::<Vec<_>>();
+        if codecs::image_format(&filters).is_some() {
+            let params = match obj.dict.lookup(b"DecodeParms") {
+                Object::Null => obj.dict.lookup(b"DP"),
+                params => params
+            };
+            for (key, entry) in [(&b"Filter"[..], obj.dict.lookup(b"Filter")), (b"DecodeParms", params)] {
+                let last = match self.resolve_deep(entry.to_owned())? {
+                    Object::Array(mut arr) => arr.pop().unwrap_or(Object::Null),
+                    obj => obj
+                };
+                if last != Object::Null {
+                    dict.push((Name::from(key), last));
+                }
+            }
+        }
+        let dict = Dict::from(dict).map_streams(&mut |stm| self.read_raw_vec(stm))?;
+        let data = self.read_stream_vec(obj)?;
+        Ok(OwnedObject::Stream(Stream { dict, data }))
+    }
+
+    /// Converts an [`Object`] into an [`OwnedObject`], copying the raw data of streams at any 
+    /// depth. Unlike [`SimpleReader::load_stream()`], the data are not decoded, so the stream 
+    /// dictionaries are kept unchanged.
+    pub fn detach(&self, obj: Object) -> Result<OwnedObject, Error> {
+        obj.map_streams(&mut |stm| self.read_raw_vec(stm))
+    }
+
+    fn read_raw_vec(&self, stm: &RefStream) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::new();
+        self.base.read_stream_raw(stm, &self.xref)?.read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    /// Resolves all indirect references in `obj` recursively, producing a self-contained tree 
+    /// which does not depend on this `SimpleReader`. The raw data of streams are copied like in 
+    /// [`SimpleReader::detach()`].
+    ///
+    /// A reference to an object which is being materialized higher up in the tree (a cycle) is 
+    /// replaced by `null`, reporting [`Event::ReferenceCycle`]. An error is returned if the nesting 
+    /// exceeds `max_depth` levels of arrays, dictionaries and references.
+    pub fn materialize(&self, obj: Object, max_depth: usize) -> Result<OwnedObject, Error> {
+        let obj = self.materialize_inner(obj, max_depth, &mut Vec::new())?;
+        self.detach(obj)
+    }
+
+    fn materialize_inner(&self, obj: Object, depth: usize, path: &mut Vec<ObjRef>) -> Result<Object, Error> {
+        let nested = |depth: usize| depth.checked_sub(1).ok_or(Error::Parse("maximum depth exceeded"));
+        Ok(match obj {
+            Object::Ref(objref) => {
+                if path.contains(&objref) {
+                    self.base.parser.diagnostics().report(Event::ReferenceCycle(objref));
+                    return Ok(Object::Null);
+                }
+                let target = self.resolve_ref(&objref)?;
+                path.push(objref);
+                let ret = self.materialize_inner(target, nested(depth)?, path);
+                path.pop();
+                ret?
+            },
+            Object::Array(arr) => Object::Array(arr.into_iter()
+                .map(|obj| self.materialize_inner(obj, nested(depth)?, path))
+                .collect::<Result<_, _>>()?),
+            Object::Dict(dict) => Object::Dict(self.materialize_dict(dict, nested(depth)?, path)?),
+            Object::Stream(stm) => Object::Stream(Stream {
+                dict: self.materialize_dict(stm.dict, nested(depth)?, path)?,
+                data: stm.data
+            }),
+            obj => obj
+        })
+    }
+
+    fn materialize_dict(&self, dict: Dict, depth: usize, path: &mut Vec<ObjRef>) -> Result<Dict, Error> {
+        Ok(Dict::from(dict.into_iter()
+            .map(|(key, obj)| Ok((key, self.materialize_inner(obj, depth, path)?)))
+            .collect::<Result<Vec<_>, Error>>()?))
+    }
+
+    /// Determines the actual length of the raw data of a [`RefStream`] by searching for the 
+    /// `endstream` keyword following an end-of-line marker, disregarding its `/Length`. The 
+    /// end-of-line marker is not counted.
+    ///
+    /// This can be used to correct a wrong `/Length`. Returns an error if `endstream` is not found.
+    pub fn stream_true_length(&self, obj: &RefStream) -> Result<u64, Error> {
+        self.base.stream_true_length(obj)
+    }
+
+    /// Returns the chain of filters applied to the data of a [`RefStream`], as given by its 
+    /// `/Filter` and `/DecodeParms` entries, without decoding the data.
+    pub fn stream_filters(&self, obj: &RefStream) -> Result<Vec<Filter>, Error> {
+        codecs::parse_filters_with_diagnostics(&obj.dict, self, &**self.base.parser.diagnostics())
+    }
+
+    /// Returns `true` if the last filter applied to the data of a [`RefStream`] is an image 
+    /// compression filter: `/DCTDecode`, `/JPXDecode` or `/CCITTFaxDecode`.
+    pub fn is_image_stream(&self, obj: &RefStream) -> Result<bool, Error> {
+        Ok(matches!(self.stream_filters(obj)?.last(), Some(Filter::Dct(_) | Filter::Jpx | Filter::CcittFax(_))))
+    }
+
+    /// Like [`SimpleReader::read_stream_data()`], but yields only the `len` bytes of decoded data 
+    /// starting at `start`. Fewer bytes are returned if the data end sooner.
+    ///
+    /// As the filters do not support seeking, the data preceding `start` are decoded and 
+    /// discarded.
+    pub fn read_stream_range(&self, obj: &RefStream, start: u64, len: u64) -> Result<Box<dyn BufRead + '_>, Error> {
+        let mut reader = self.read_stream_data(obj)?;
+        std::io::copy(&mut reader.by_ref().take(start), &mut std::io::sink())?;
+        Ok(Box::new(reader.take(len)))
+    }
 }
 
 impl<T: BufRead + Seek> Resolver for SimpleReader<T> {
@@ -95,6 +513,7 @@ mod tests {
     use std::io::*;
     use std::fs::*;
     use crate::parser::bp::ByteProvider;
+    use crate::utils::{self, Capture};
 
     #[test]
     fn test_objects_iter() {
@@ -128,6 +547,260 @@ mod tests {
         //etc.
     }
 
+    #[test]
+    fn test_resolve_typed() {
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/basic.pdf").unwrap())).unwrap();
+        let pages = Object::Ref(ObjRef { num: 1, gen: 0 });
+        let dict = rdr.resolve_dict(&pages).unwrap();
+        assert_eq!(rdr.resolve_name(dict.lookup(b"Type")).unwrap(), Name::from(b"Pages"));
+        assert_eq!(rdr.resolve_array(dict.lookup(b"Kids")).unwrap(),
+            vec![Object::Ref(ObjRef { num: 2, gen: 0 })]);
+        assert!(rdr.resolve_stream(&pages).is_err());
+        assert!(rdr.resolve_array(&pages).is_err());
+        assert!(rdr.resolve_name(dict.lookup(b"Count")).is_err());
+        assert!(rdr.resolve_dict(dict.lookup(b"Missing")).is_err());
+
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 4, gen: 0 })).unwrap();
+        assert!(rdr.resolve_dict(&Object::Stream(stm)).is_err());
+    }
+
+    #[test]
+    fn test_read_stream_by_ref() {
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/basic.pdf").unwrap())).unwrap();
+        let mut data = rdr.read_stream_by_ref(&ObjRef { num: 4, gen: 0 }).unwrap();
+        assert_eq!(data.read_line_excl().unwrap(), b"1 0 0 -1 0 841.889771 cm");
+        drop(data);
+        assert!(rdr.read_stream_by_ref(&ObjRef { num: 1, gen: 0 }).is_err());
+    }
+
+    #[test]
+    fn test_header_offset() {
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let rdr = SimpleReader::new(Cursor::new(&data)).unwrap();
+        assert_eq!(rdr.header_offset(), 0);
+        let rdr = SimpleReader::new(Cursor::new([&b"junk\n"[..], &data].concat())).unwrap();
+        assert_eq!(rdr.header_offset(), 5);
+
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/offset.pdf").unwrap())).unwrap();
+        assert_eq!(rdr.header_offset(), 656);
+    }
+
+    #[test]
+    fn test_file_id() {
+        let data = utils::make_pdf_with_trailer(&["<< /Type /Catalog >>"], "/ID [<0123ABCD> <FFFF>]");
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.file_id(), Some((vec![0x01, 0x23, 0xAB, 0xCD], vec![0xFF, 0xFF])));
+
+        let data = utils::make_pdf_with_trailer(&["<< /Type /Catalog >>"], "/ID [(abc)]");
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.file_id(), Some((b"abc".to_vec(), b"abc".to_vec())));
+
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.file_id(), None);
+    }
+
+    #[test]
+    fn test_resolve_ref_opt() {
+        let data = utils::make_pdf(&["<< /Type /Catalog >>", "null"]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.resolve_ref_opt(&ObjRef { num: 1, gen: 0 }).unwrap(),
+            Some(Object::Dict(Dict::from(vec![(Name::from(b"Type"), Object::new_name(b"Catalog"))]))));
+        // Explicit null is still an object
+        assert_eq!(rdr.resolve_ref_opt(&ObjRef { num: 2, gen: 0 }).unwrap(), Some(Object::Null));
+        // Free
+        assert_eq!(rdr.resolve_ref_opt(&ObjRef { num: 0, gen: 65535 }).unwrap(), None);
+        // Never defined
+        assert_eq!(rdr.resolve_ref_opt(&ObjRef { num: 10, gen: 0 }).unwrap(), None);
+        assert_eq!(rdr.resolve_ref(&ObjRef { num: 10, gen: 0 }).unwrap(), Object::Null);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let data = utils::make_pdf(&["<< /Type /Catalog >>"]);
+        let startxref = SimpleReader::new(Cursor::new(&data)).unwrap().parser().entrypoint().unwrap();
+        let data = utils::make_pdf_with_trailer(&["<< /Type /Catalog >>"], &format!("/Prev {startxref}"));
+        let capture = Arc::new(Capture::default());
+        let rdr = SimpleReader::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        assert_eq!(rdr.len(), 1);
+        let events = capture.0.lock().unwrap();
+        assert!(matches!(events[..], [Event::BreakingXRefChain(offset)] if offset == startxref));
+    }
+
+    #[test]
+    fn test_shrinking_size() {
+        let mut data = utils::make_pdf(&["<< /Type /Catalog >>", "(two)", "(three)"]);
+        let prev = SimpleReader::new(Cursor::new(&data)).unwrap().parser().entrypoint().unwrap();
+        let start = data.len();
+        data.extend_from_slice(format!("xref\n0 1\n0000000000 65535 f \ntrailer\n\
+            << /Size 2 /Root 1 0 R /Prev {prev} >>\nstartxref\n{start}\n%%EOF\n").as_bytes());
+        let capture = Arc::new(Capture::default());
+        SimpleReader::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let events = capture.0.lock().unwrap();
+        assert!(matches!(events[..], [Event::ShrinkingXRefSize { offset, size: 2, prev_size: 4 }]
+            if offset == start as Offset));
+    }
+
+    #[test]
+    fn test_reload() {
+        use std::cell::RefCell;
+        use std::io::SeekFrom;
+        use std::rc::Rc;
+
+        // A source which can be appended to while being read.
+        struct Growing(Rc<RefCell<Vec<u8>>>, u64);
+
+        impl Read for Growing {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let data = self.0.borrow();
+                let len = (&data[(self.1 as usize).min(data.len())..]).read(buf)?;
+                self.1 += len as u64;
+                Ok(len)
+            }
+        }
+
+        impl Seek for Growing {
+            fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+                self.1 = match pos {
+                    SeekFrom::Start(pos) => pos,
+                    SeekFrom::End(diff) => self.0.borrow().len().checked_add_signed(diff as isize).unwrap() as u64,
+                    SeekFrom::Current(diff) => self.1.checked_add_signed(diff).unwrap(),
+                };
+                Ok(self.1)
+            }
+        }
+
+        let data = Rc::new(RefCell::new(utils::make_pdf(&["<< /Type /Catalog >>"])));
+        let mut rdr = SimpleReader::new(BufReader::new(Growing(data.clone(), 0))).unwrap();
+        let prev = rdr.parser().entrypoint().unwrap();
+        let oref = ObjRef { num: 2, gen: 0 };
+        assert_eq!(rdr.resolve_ref(&oref).unwrap(), Object::Null);
+
+        let mut data = data.borrow_mut();
+        let off = data.len();
+        data.extend_from_slice(b"2 0 obj\n(new)\nendobj\n");
+        let start = data.len();
+        data.extend_from_slice(format!("xref\n2 1\n{off:010} 00000 n \ntrailer\n\
+            << /Size 3 /Root 1 0 R /Prev {prev} >>\nstartxref\n{start}\n%%EOF\n").as_bytes());
+        drop(data);
+        assert_eq!(rdr.resolve_ref(&oref).unwrap(), Object::Null);
+        rdr.reload().unwrap();
+        assert_eq!(rdr.resolve_ref(&oref).unwrap(), Object::new_string(b"new"));
+        assert_eq!(rdr.len(), 2);
+    }
+
+    #[test]
+    fn test_real_length() {
+        let body = "x".repeat(842);
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            &format!("<< /Length 842.0 >>\nstream\n{body}\nendstream"),
+            &format!("<< /Length 841.5 >>\nstream\n{body}\nendstream"),
+        ]);
+        let capture = Arc::new(Capture::default());
+        let rdr = SimpleReader::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 2, gen: 0 })).unwrap();
+        assert_eq!(rdr.read_stream_vec(&stm).unwrap(), body.as_bytes());
+        assert!(capture.0.lock().unwrap().is_empty());
+
+        // Not integral: read until endstream, including the EOL.
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 3, gen: 0 })).unwrap();
+        assert_eq!(rdr.read_stream_vec(&stm).unwrap(), format!("{body}\n").as_bytes());
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::NonIntegralLength { length: 841.5, .. }]));
+    }
+
+    #[test]
+    fn test_lenient_numbers() {
+        let data = utils::make_pdf(&["<< /Type /Catalog /Big 9223372036854775808 >>"]);
+        let mut rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let oref = ObjRef { num: 1, gen: 0 };
+        assert!(rdr.resolve_ref(&oref).is_err());
+        rdr.set_lenient(true);
+        let dict = rdr.resolve_ref(&oref).unwrap().into_dict().unwrap();
+        assert_eq!(dict.lookup(b"Big"), &Object::Number(Number::Real(9223372036854775808.)));
+    }
+
+    #[test]
+    fn test_read_stream_range() {
+        let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"0123456789abcdef").unwrap();
+        let hex = enc.finish().unwrap().iter().map(|b| format!("{b:02X}")).collect::<String>() + ">";
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            &format!("<< /Filter [/ASCIIHexDecode /FlateDecode] /Length {} >>\nstream\n{hex}\nendstream", hex.len()),
+        ]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 2, gen: 0 })).unwrap();
+        let mut full = Vec::new();
+        rdr.read_stream_data(&stm).unwrap().read_to_end(&mut full).unwrap();
+        let mut range = Vec::new();
+        rdr.read_stream_range(&stm, 4, 4).unwrap().read_to_end(&mut range).unwrap();
+        assert_eq!(range, &full[4..8]);
+        assert_eq!(range, b"4567");
+
+        let mut range = Vec::new();
+        rdr.read_stream_range(&stm, 12, 10).unwrap().read_to_end(&mut range).unwrap();
+        assert_eq!(range, b"cdef");
+    }
+
+    #[test]
+    fn test_stream_filters() {
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/indirect-filters.pdf").unwrap())).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 4, gen: 0 })).unwrap();
+        assert_eq!(rdr.stream_filters(&stm).unwrap(), vec![Filter::AsciiHex, Filter::Flate(Dict::default())]);
+        assert!(!rdr.is_image_stream(&stm).unwrap());
+    }
+
+    #[test]
+    fn test_is_image_stream() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Filter 3 0 R /DecodeParms [null 4 0 R] /Length 0 >>\nstream\n\nendstream",
+            "[/ASCIIHexDecode /DCTDecode]",
+            "<< /ColorTransform 0 >>",
+        ]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 2, gen: 0 })).unwrap();
+        assert_eq!(rdr.stream_filters(&stm).unwrap(), vec![Filter::AsciiHex,
+            Filter::Dct(Dict::from(vec![(Name::from(b"ColorTransform"), Object::Number(Number::Int(0)))]))]);
+        assert!(rdr.is_image_stream(&stm).unwrap());
+    }
+
+    #[test]
+    fn test_len() {
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/basic.pdf").unwrap())).unwrap();
+        assert!(!rdr.is_empty());
+        assert_eq!(rdr.len(), rdr.objects().count());
+        assert_eq!(rdr.len(), rdr.object_refs().count());
+
+        let data = utils::make_pdf_objstm(&["<< /Type /Catalog >>"], &["1", "2"]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.len(), 5);
+    }
+
+    #[test]
+    fn test_object_refs() {
+        let data = utils::make_pdf_objstm(&["<< /Type /Catalog /Pages 3 0 R >>", "(uncompressed)"],
+            &["<< /Type /Pages /Kids [] /Count 0 >>", "42"]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.xref.locate(&ObjRef { num: 4, gen: 0 }), Some(Record::Compr { num_within: 5, index: 1 }));
+        assert_eq!(rdr.object_refs().collect::<Vec<_>>(), (1..=6).map(|num| ObjRef { num, gen: 0 }).collect::<Vec<_>>());
+        assert_eq!(rdr.resolve_ref(&ObjRef { num: 4, gen: 0 }).unwrap(), Object::Number(Number::Int(42)));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_objects() {
+        let open = || File::open("src/tests/objstm.pdf").map(BufReader::new);
+        let rdr = SimpleReader::new(open().unwrap()).unwrap();
+        let mut par = rdr.par_objects(open);
+        for (oref, res) in rdr.objects() {
+            let par_res = par.remove(&oref).unwrap();
+            assert_eq!(res.ok(), par_res.ok());
+        }
+        assert!(par.is_empty());
+    }
+
     #[test]
     fn test_xref_chaining() {
         let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/hybrid.pdf").unwrap())).unwrap();
@@ -153,4 +826,244 @@ mod tests {
         let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/circular.pdf").unwrap())).unwrap();
         assert!(rdr.xref.map.is_empty());
     }
+
+    #[test]
+    fn test_section_trailers() {
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/updates.pdf").unwrap())).unwrap();
+        let prevs = rdr.section_trailers().into_iter()
+            .map(|(offset, dict)| (offset, dict.lookup(b"Prev").num_value::<Offset>()))
+            .collect::<Vec<_>>();
+        assert_eq!(prevs, vec![(510, Some(322)), (322, Some(87)), (87, None)]);
+    }
+
+    #[test]
+    fn test_hybrid_root() {
+        let mut data = b"%PDF-1.5\n".to_vec();
+        let off1 = data.len();
+        data.extend_from_slice(b"1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+        let off2 = data.len();
+        data.extend_from_slice(b"2 0 obj\n<< /Type /Pages /Kids [] /Count 0 >>\nendobj\n");
+        let off3 = data.len();
+        let hex = format!("01{off3:04X}00>");
+        data.extend_from_slice(format!("3 0 obj\n<< /Type /XRef /Size 4 /Index [3 1] /W [1 2 1] /Root 1 0 R \
+            /Filter /ASCIIHexDecode /Length {} >>\nstream\n{hex}\nendstream\nendobj\n", hex.len()).as_bytes());
+        let start = data.len();
+        data.extend_from_slice(format!("xref\n0 3\n0000000000 65535 f \n{off1:010} 00000 n \n{off2:010} 00000 n \n\
+            trailer\n<< /Size 4 /XRefStm {off3} >>\nstartxref\n{start}\n%%EOF\n").as_bytes());
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.xref.dict.lookup(b"Root"), &Object::Ref(ObjRef { num: 1, gen: 0 }));
+        assert_eq!(rdr.xref.dict.lookup(b"XRefStm").num_value(), Some(off3));
+        assert!(rdr.resolve_dict(rdr.xref.dict.lookup(b"Root")).unwrap().has_type(b"Catalog"));
+    }
+
+    #[test]
+    fn test_xrefstm_not_xref() {
+        let mut data = b"%PDF-1.5\n".to_vec();
+        let off1 = data.len();
+        data.extend_from_slice(b"1 0 obj\n<< /Type /Catalog >>\nendobj\n");
+        let off2 = data.len();
+        data.extend_from_slice(b"2 0 obj\n<< /Length 3 >>\nstream\nabc\nendstream\nendobj\n");
+        let start = data.len();
+        data.extend_from_slice(format!("xref\n0 3\n0000000000 65535 f \n{off1:010} 00000 n \n{off2:010} 00000 n \n\
+            trailer\n<< /Size 3 /Root 1 0 R /XRefStm {off2} >>\nstartxref\n{start}\n%%EOF\n").as_bytes());
+        let capture = Arc::new(Capture::default());
+        let rdr = SimpleReader::with_diagnostics(Cursor::new(&data), capture.clone()).unwrap();
+        assert_eq!(rdr.len(), 2);
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::XRefStmNotXRef(offset)] if offset == off2 as Offset));
+
+        // A stream-based update lacking /Root on top of a classical section is rejected.
+        let off3 = data.len();
+        let hex = format!("01{off3:04X}00>");
+        data.extend_from_slice(format!("3 0 obj\n<< /Type /XRef /Size 4 /Index [3 1] /W [1 2 1] /Prev {start} \
+            /Filter /ASCIIHexDecode /Length {} >>\nstream\n{hex}\nendstream\nendobj\nstartxref\n{off3}\n%%EOF\n",
+            hex.len()).as_bytes());
+        assert!(SimpleReader::new(Cursor::new(&data)).is_err());
+    }
+
+    #[test]
+    fn test_objstm_members() {
+        let data = utils::make_pdf_objstm(&["<< /Type /Catalog >>"], &["(a)", "[1 2]", "<< >>"]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.objstm_members(5).unwrap(), vec![2, 3, 4]);
+        assert!(rdr.objstm_members(1).is_err());
+        assert!(rdr.objstm_members(10).is_err());
+    }
+
+    #[test]
+    fn test_container_of() {
+        let data = utils::make_pdf_objstm(&["<< /Type /Catalog >>"], &["(a)", "[1 2]"]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(rdr.container_of(&ObjRef { num: 2, gen: 0 }), Some(4));
+        assert_eq!(rdr.container_of(&ObjRef { num: 3, gen: 0 }), Some(4));
+        assert_eq!(rdr.container_of(&ObjRef { num: 1, gen: 0 }), None);
+        assert_eq!(rdr.container_of(&ObjRef { num: 4, gen: 0 }), None);
+        assert_eq!(rdr.container_of(&ObjRef { num: 0, gen: 65535 }), None);
+        assert_eq!(rdr.container_of(&ObjRef { num: 10, gen: 0 }), None);
+    }
+
+    #[test]
+    fn test_locate() {
+        let data = utils::make_pdf_objstm(&["<< /Type /Catalog >>"], &["(a)"]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        assert!(matches!(rdr.locate(&ObjRef { num: 1, gen: 0 }), Some(Record::Used { gen: 0, .. })));
+        assert_eq!(rdr.locate(&ObjRef { num: 2, gen: 0 }), Some(Record::Compr { num_within: 3, index: 0 }));
+        assert!(matches!(rdr.locate(&ObjRef { num: 0, gen: 65535 }), Some(Record::Free { .. })));
+
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/objstm.pdf").unwrap())).unwrap();
+        assert_eq!(rdr.locate(&ObjRef { num: 1, gen: 0 }), Some(Record::Compr { num_within: 8, index: 4 }));
+    }
+
+    #[test]
+    fn test_trim_trailing_eol() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Length 3 0 R >>\nstream\nabc\r\n\nendstream",
+            "5",
+            "<< /Length 4 >>\nstream\nabc\n\nendstream",
+            "<< /Length 6 0 R >>\nstream\nabc\nendstream",
+            "18446744073709551616.0",
+        ]);
+        let mut rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let read = |rdr: &SimpleReader<_>, num| {
+            let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num, gen: 0 })).unwrap();
+            let mut s = Vec::new();
+            rdr.read_stream_data(&stm).unwrap().read_to_end(&mut s).unwrap();
+            s
+        };
+        assert_eq!(read(&rdr, 2), b"abc\r\n");
+        assert_eq!(read(&rdr, 4), b"abc\n");
+        rdr.set_trim_trailing_eol(true);
+        assert_eq!(read(&rdr, 2), b"abc");
+        assert_eq!(read(&rdr, 4), b"abc\n");
+        // A /Length overflowing the offset is not trimmed.
+        assert!(read(&rdr, 5).starts_with(b"abc\nendstream"));
+    }
+
+    #[test]
+    fn test_load_stream() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Filter [/ASCIIHexDecode] /DecodeParms [null] /Length 11 >>\nstream\n68656C6C6F>\nendstream",
+            "[1 2 0 R]",
+            "<< /Filter /ASCIIHexDecode /DP << /Predictor 1 >> /Length 11 >>\nstream\n68656C6C6F>\nendstream",
+            "<< /Filter [/ASCIIHexDecode /DCTDecode] /DP [null << /ColorTransform 0 >>] /Length 7 >>\nstream\n616263>\nendstream",
+        ]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 2, gen: 0 })).unwrap();
+        let mut out = Vec::new();
+        rdr.load_stream(&stm).unwrap().write_to(&mut out).unwrap();
+        assert_eq!(out, b"<< /Length 5 >>\nstream\nhello\nendstream");
+
+        let obj = rdr.detach(Object::Stream(stm)).unwrap();
+        let mut out = Vec::new();
+        obj.write_to(&mut out).unwrap();
+        assert_eq!(out, b"<< /Filter [ /ASCIIHexDecode ] /DecodeParms [ null ] /Length 11 >>\nstream\n68656C6C6F>\nendstream");
+
+        let arr = rdr.resolve_ref(&ObjRef { num: 3, gen: 0 }).unwrap();
+        assert_eq!(rdr.detach(arr).unwrap(), OwnedObject::Array(vec![
+            OwnedObject::Number(Number::Int(1)), OwnedObject::Ref(ObjRef { num: 2, gen: 0 })]));
+
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 4, gen: 0 })).unwrap();
+        let mut out = Vec::new();
+        rdr.load_stream(&stm).unwrap().write_to(&mut out).unwrap();
+        assert_eq!(out, b"<< /Length 5 >>\nstream\nhello\nendstream");
+
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: 5, gen: 0 })).unwrap();
+        let mut out = Vec::new();
+        rdr.load_stream(&stm).unwrap().write_to(&mut out).unwrap();
+        assert_eq!(out, b"<< /Filter /DCTDecode /DecodeParms << /ColorTransform 0 >> /Length 3 >>\nstream\nabc\nendstream");
+    }
+
+    #[test]
+    fn test_materialize() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog /Pages 2 0 R >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /MediaBox 4 0 R /Contents 5 0 R /Resources << /Font << /F1 6 0 R >> >> >>",
+            "[0 0 612 792]",
+            "<< /Length 7 0 R >>\nstream\nBT ET\nendstream",
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>",
+            "5",
+        ]);
+        let capture = Arc::new(Capture::default());
+        let rdr = SimpleReader::with_diagnostics(Cursor::new(data), capture.clone()).unwrap();
+
+        fn has_refs(obj: &OwnedObject) -> bool {
+            match obj {
+                OwnedObject::Ref(_) => true,
+                OwnedObject::Array(arr) => arr.iter().any(has_refs),
+                OwnedObject::Dict(dict) => dict.iter().any(|(_, obj)| has_refs(obj)),
+                OwnedObject::Stream(stm) => stm.dict.iter().any(|(_, obj)| has_refs(obj)),
+                _ => false
+            }
+        }
+
+        let page = Object::Ref(ObjRef { num: 3, gen: 0 });
+        let obj = rdr.materialize(page.clone(), 10).unwrap();
+        assert!(!has_refs(&obj));
+        let dict = obj.as_dict().unwrap();
+        // The cycle back to the page through /Parent /Kids is broken.
+        assert_eq!(dict.lookup(b"Parent").as_dict().unwrap().lookup(b"Kids"), &OwnedObject::Array(vec![OwnedObject::Null]));
+        assert!(matches!(capture.0.lock().unwrap()[..], [Event::ReferenceCycle(ObjRef { num: 3, gen: 0 })]));
+        assert_eq!(dict.lookup(b"MediaBox").as_array().unwrap().len(), 4);
+        let stream = OwnedObject::Stream(Stream {
+            dict: OwnedDict::from(vec![(Name::from(b"Length"), OwnedObject::Number(Number::Int(5)))]),
+            data: b"BT ET".to_vec() });
+        assert_eq!(dict.lookup(b"Contents"), &stream);
+        assert!(rdr.materialize(page, 2).is_err());
+
+        let contents = Object::Array(vec![Object::Ref(ObjRef { num: 5, gen: 0 })]);
+        assert_eq!(rdr.materialize(contents, 5).unwrap(), OwnedObject::Array(vec![stream]));
+    }
+
+    #[test]
+    fn test_stream_true_length() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Length 10 >>\nstream\n123\nendstream",
+            "<< /Length 2 >>\nstream\nabcdef\r\nendstream",
+            "<< /Length 3 >>\nstream\nxyzendstream",
+        ]);
+        let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let len = |num| rdr.stream_true_length(&rdr.resolve_stream(&Object::Ref(ObjRef { num, gen: 0 })).unwrap());
+        assert_eq!(len(2).unwrap(), 3);
+        assert_eq!(len(3).unwrap(), 6);
+        // endstream must follow an end-of-line marker
+        assert!(len(4).is_err());
+
+        let data = b"%PDF-1.7\n1 0 obj\n<< /Length 3 >>\nstream\n123";
+        let rdr = BaseReader::new(FileParser::new(Cursor::new(&data[..])));
+        let stm = rdr.read_uncompressed(9, &ObjRef { num: 1, gen: 0 }).unwrap().into_stream().unwrap();
+        assert!(rdr.stream_true_length(&stm).is_err());
+    }
+
+    #[test]
+    fn test_effective_records() {
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/updates.pdf").unwrap())).unwrap();
+        let newest = rdr.parser().read_xref_at(510).unwrap();
+        let (num, rec) = rdr.effective_records().nth(1).unwrap();
+        assert_eq!(num, 1);
+        assert_eq!(rec, newest.map[&1]);
+        assert!(matches!(rec, Record::Used { gen: 0, offset } if offset != 9));
+
+        let mut out = Vec::new();
+        rdr.write_effective_records(&mut out).unwrap();
+        assert_eq!(out.split(|&c| c == b'\n').filter(|line| !line.is_empty()).count(), rdr.xref.map.len());
+        let line = format!("\n1: {rec}\n");
+        assert!(out.windows(line.len()).any(|w| w == line.as_bytes()));
+    }
+
+    #[test]
+    fn test_read_object_at() {
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/updates.pdf").unwrap())).unwrap();
+        let stm = rdr.read_object_at(9, &ObjRef { num: 1, gen: 0 })
+            .unwrap()
+            .into_stream()
+            .unwrap();
+        let mut data = rdr.read_stream_data(&stm).unwrap();
+        let mut s = Vec::new();
+        data.read_to_end(&mut s).unwrap();
+        assert_eq!(s, b"Test 1\n");
+        assert!(rdr.read_object_at(9, &ObjRef { num: 2, gen: 0 }).is_err());
+    }
 }
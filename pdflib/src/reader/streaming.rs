@@ -0,0 +1,145 @@
+use std::io::{BufRead, Seek};
+use std::collections::VecDeque;
+
+use crate::base::*;
+use crate::base::types::*;
+use crate::parser::{FileParser, Structural};
+
+use super::esr::EndstreamReader;
+
+/// An event emitted by [`StreamingReader`].
+#[derive(Debug, PartialEq)]
+pub enum StreamingEvent {
+    /// An indirect object starts. Followed by a `Value` event.
+    ObjectStart(ObjRef),
+    /// The value of the indirect object announced by the preceding `ObjectStart`.
+    Value(Object),
+    /// The data of a stream object, given by its offset and length. Follows the `Value` event 
+    /// containing the stream dictionary.
+    StreamData(Offset, u64),
+    /// A classical cross-reference section was skipped. Carries its trailer dictionary.
+    XRefSection(Dict),
+}
+
+/// Reads a PDF file sequentially from the start, emitting a flat sequence of 
+/// [`StreamingEvent`]s.
+///
+/// Unlike [`SimpleReader`](super::SimpleReader) and [`FullReader`](super::FullReader), 
+/// `StreamingReader` does not build any cross-reference structure, so it uses a constant amount 
+/// of memory regardless of the file size. Consequently, references are not resolved, and stream 
+/// lengths given by an indirect object are found by scanning for `endstream`. Cross-reference 
+/// streams and object streams are reported as ordinary objects.
+///
+/// Iteration stops at the end of input or after the first error.
+pub struct StreamingReader<T: BufRead + Seek> {
+    parser: FileParser<T>,
+    pos: Option<Offset>,
+    queue: VecDeque<StreamingEvent>,
+}
+
+impl<T: BufRead + Seek> StreamingReader<T> {
+    /// Creates a `StreamingReader` instance around a `BufRead + Seek` source.
+    pub fn new(source: T) -> Self {
+        Self { parser: FileParser::new(source), pos: Some(0), queue: VecDeque::new() }
+    }
+
+    fn read_next(&mut self, pos: Offset) -> Result<Option<Offset>, Error> {
+        let Some(pos) = self.parser.skip_to_next(pos)? else { return Ok(None) };
+        match self.parser.read_at(pos)? {
+            Structural::XRefSec(xref) => {
+                self.queue.push_back(StreamingEvent::XRefSection(xref.dict));
+                Ok(Some(self.parser.position()?))
+            },
+            Structural::Object(oref, Object::Stream(stm)) => {
+                let offset = stm.data;
+                let (len, next) = self.stream_len(offset, stm.dict.lookup(b"Length").num_value())?;
+                self.queue.push_back(StreamingEvent::ObjectStart(oref));
+                self.queue.push_back(StreamingEvent::Value(Object::Stream(stm)));
+                self.queue.push_back(StreamingEvent::StreamData(offset, len));
+                Ok(Some(next))
+            },
+            Structural::Object(oref, obj) => {
+                self.queue.push_back(StreamingEvent::ObjectStart(oref));
+                self.queue.push_back(StreamingEvent::Value(obj));
+                Ok(Some(self.parser.position()?))
+            }
+        }
+    }
+
+    fn stream_len(&self, offset: Offset, len: Option<u64>) -> Result<(u64, Offset), Error> {
+        if let Some((len, end)) = len.and_then(|len| Some((len, offset.checked_add(len)?))) {
+            if let Ok(next) = self.parser.read_stream_end(end) {
+                return Ok((len, next));
+            }
+        }
+        let mut reader = EndstreamReader::new(self.parser.read_raw(offset)?);
+        let len = std::io::copy(&mut reader, &mut std::io::sink())?;
+        drop(reader);
+        Ok((len, self.parser.read_stream_end(offset + len)?))
+    }
+}
+
+impl<T: BufRead + Seek> Iterator for StreamingReader<T> {
+    type Item = Result<StreamingEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.queue.pop_front() {
+            return Some(Ok(event));
+        }
+        let pos = self.pos.take()?;
+        match self.read_next(pos) {
+            Ok(next) => {
+                self.pos = next;
+                self.queue.pop_front().map(Ok)
+            },
+            Err(err) => Some(Err(err))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::SimpleReader;
+    use crate::utils;
+    use std::io::{BufReader, Cursor};
+    use std::fs::File;
+
+    #[test]
+    fn test_basic() {
+        let events = StreamingReader::new(BufReader::new(File::open("src/tests/basic.pdf").unwrap()))
+            .collect::<Result<Vec<_>, _>>().unwrap();
+        let rdr = SimpleReader::new(BufReader::new(File::open("src/tests/basic.pdf").unwrap())).unwrap();
+        let count = events.iter().filter(|ev| matches!(ev, StreamingEvent::ObjectStart(_))).count();
+        assert_eq!(count, rdr.len());
+        assert_eq!(events[0], StreamingEvent::ObjectStart(ObjRef { num: 4, gen: 0 }));
+        assert!(matches!(events[2], StreamingEvent::StreamData(74, _)));
+        assert!(matches!(events.last(), Some(StreamingEvent::XRefSection(_))));
+    }
+
+    #[test]
+    fn test_streaming() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Length 3 >>\nstream\nabc\nendstream",
+            "<< /Length 1 0 R >>\nstream\nabcd\nendstream",
+        ]);
+        let events = StreamingReader::new(Cursor::new(data)).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(events.len(), 9);
+        assert_eq!(events[0], StreamingEvent::ObjectStart(ObjRef { num: 1, gen: 0 }));
+        assert!(matches!(events[4], StreamingEvent::StreamData(_, 3)));
+        assert!(matches!(events[7], StreamingEvent::StreamData(_, 5)));
+        assert!(matches!(events[8], StreamingEvent::XRefSection(_)));
+    }
+
+    #[test]
+    fn test_streaming_huge_length() {
+        let data = utils::make_pdf(&[
+            "<< /Type /Catalog >>",
+            "<< /Length 9223372036854775807 >>\nstream\nabc\nendstream",
+        ]);
+        let events = StreamingReader::new(Cursor::new(data)).collect::<Result<Vec<_>, _>>().unwrap();
+        assert!(matches!(events[4], StreamingEvent::StreamData(_, 4)));
+    }
+}
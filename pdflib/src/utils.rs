@@ -16,11 +16,134 @@ pub fn parse_int_strict<T>(bstr: &[u8]) -> Option<T>
     }
 }
 
+/// Lookup table for [`hex_value`], with `0xFF` marking invalid characters.
+const HEX_VALUES: [u8; 256] = {
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < 10 {
+        table[b'0' as usize + i] = i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < 6 {
+        table[b'a' as usize + i] = 10 + i as u8;
+        table[b'A' as usize + i] = 10 + i as u8;
+        i += 1;
+    }
+    table
+};
+
 pub fn hex_value(c: u8) -> Option<u8> {
-    match c {
-        b'0'..=b'9' => Some(c - b'0'),
-        b'a'..=b'f' => Some(c - b'a' + 10),
-        b'A'..=b'F' => Some(c - b'A' + 10),
-        _ => None
+    match HEX_VALUES[c as usize] {
+        0xFF => None,
+        val => Some(val)
+    }
+}
+
+/// A [`Diagnostics`](crate::base::Diagnostics) sink collecting all reported events.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct Capture(pub(crate) std::sync::Mutex<Vec<crate::base::Event>>);
+
+#[cfg(test)]
+impl crate::base::Diagnostics for Capture {
+    fn report(&self, event: crate::base::Event) {
+        self.0.lock().unwrap().push(event);
+    }
+}
+
+/// Assembles a complete PDF file from a list of object bodies, numbered from 1, adding a valid 
+/// cross-reference table. The trailer refers to object 1 as `/Root`.
+#[cfg(test)]
+pub(crate) fn make_pdf(objects: &[&str]) -> Vec<u8> {
+    make_pdf_with_trailer(objects, "")
+}
+
+/// Like [`make_pdf`], but adds `extra` to the trailer dictionary.
+#[cfg(test)]
+pub(crate) fn make_pdf_with_trailer(objects: &[&str], extra: &str) -> Vec<u8> {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    let mut offsets = Vec::new();
+    for (ix, obj) in objects.iter().enumerate() {
+        offsets.push(data.len());
+        data.extend_from_slice(format!("{} 0 obj\n{obj}\nendobj\n", ix + 1).as_bytes());
+    }
+    let start = data.len();
+    let size = objects.len() + 1;
+    data.extend_from_slice(format!("xref\n0 {size}\n0000000000 65535 f \n").as_bytes());
+    for offset in offsets {
+        data.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    data.extend_from_slice(format!("trailer\n<< /Size {size} /Root 1 0 R {extra}>>\nstartxref\n{start}\n%%EOF\n").as_bytes());
+    data
+}
+
+/// Like [`make_pdf`], but additionally stores the `compressed` objects in an object stream, and 
+/// uses a cross-reference stream. The objects are numbered from 1 in the order: `objects`, 
+/// `compressed`, the object stream, the cross-reference stream.
+#[cfg(test)]
+pub(crate) fn make_pdf_objstm(objects: &[&str], compressed: &[&str]) -> Vec<u8> {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    // (type, field 2, field 3)
+    let mut records = vec![(0, 0, 65535)];
+    for (ix, obj) in objects.iter().enumerate() {
+        records.push((1, data.len(), 0));
+        data.extend_from_slice(format!("{} 0 obj\n{obj}\nendobj\n", ix + 1).as_bytes());
+    }
+    let objstm_num = objects.len() + compressed.len() + 1;
+    let mut header = String::new();
+    let mut body = String::new();
+    for (ix, obj) in compressed.iter().enumerate() {
+        records.push((2, objstm_num, ix));
+        header += &format!("{} {} ", objects.len() + ix + 1, body.len());
+        body += obj;
+        body += "\n";
+    }
+    records.push((1, data.len(), 0));
+    data.extend_from_slice(format!("{objstm_num} 0 obj\n<< /Type /ObjStm /N {} /First {} /Length {} >>\n\
+        stream\n{header}{body}\nendstream\nendobj\n",
+        compressed.len(), header.len(), header.len() + body.len()).as_bytes());
+    let start = data.len();
+    records.push((1, start, 0));
+    let size = records.len();
+    let hex = records.iter()
+        .map(|&(tpe, f2, f3)| format!("{tpe:02X}{f2:08X}{f3:04X}"))
+        .collect::<String>() + ">";
+    data.extend_from_slice(format!("{} 0 obj\n<< /Type /XRef /Size {size} /W [1 4 2] /Root 1 0 R \
+        /Filter /ASCIIHexDecode /Length {} >>\nstream\n{hex}\nendstream\nendobj\nstartxref\n{start}\n%%EOF\n",
+        size - 1, hex.len()).as_bytes());
+    data
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::types::*;
+
+    #[test]
+    fn test_parse_int_strict() {
+        assert_eq!(parse_int_strict::<ObjNum>(b"0"), Some(0));
+        assert_eq!(parse_int_strict::<ObjNum>(b"10"), Some(10));
+        assert_eq!(parse_int_strict::<ObjGen>(b"65535"), Some(65535));
+        assert_eq!(parse_int_strict::<ObjNum>(b"01"), None);
+        assert_eq!(parse_int_strict::<ObjNum>(b"00"), None);
+        assert_eq!(parse_int_strict::<ObjNum>(b"+1"), None);
+        assert_eq!(parse_int_strict::<ObjNum>(b"-1"), None);
+        assert_eq!(parse_int_strict::<ObjNum>(b""), None);
+        assert_eq!(parse_int_strict::<ObjGen>(b"65536"), None);
+    }
+
+    #[test]
+    fn test_hex_value() {
+        for c in 0..=255u8 {
+            let expected = match c {
+                b'0'..=b'9' => Some(c - b'0'),
+                b'a'..=b'f' => Some(c - b'a' + 10),
+                b'A'..=b'F' => Some(c - b'A' + 10),
+                _ => None
+            };
+            assert_eq!(hex_value(c), expected);
+        }
     }
 }
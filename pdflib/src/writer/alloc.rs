@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+
+use crate::base::*;
+use crate::base::types::*;
+
+/// Hands out object numbers for new objects during a write session, making sure that they do not 
+/// collide with existing objects or with each other.
+///
+/// Numbers marked as free in the cross-reference table are reused first, with the generation 
+/// numbers given by their records. After that, new numbers are allocated past the highest number 
+/// in use.
+#[derive(Debug)]
+pub struct ObjectAllocator {
+    free: BTreeSet<ObjRef>,
+    next: ObjNum,
+}
+
+impl ObjectAllocator {
+    /// Creates an `ObjectAllocator` for a new file, starting at object number 1.
+    pub fn new() -> Self {
+        Self { free: BTreeSet::new(), next: 1 }
+    }
+
+    /// Creates an `ObjectAllocator` for updating a file with the given cross-reference table.
+    pub fn from_xref(xref: &XRef) -> Self {
+        let free = xref.map.iter()
+            .filter_map(|(&num, rec)| match *rec {
+                Record::Free { gen, .. } if num != 0 && gen < 65535 => Some(ObjRef { num, gen }),
+                _ => None
+            })
+            .collect();
+        Self { free, next: xref.end() }
+    }
+
+    /// Returns a reference for a new object.
+    pub fn allocate(&mut self) -> ObjRef {
+        self.free.pop_first().unwrap_or_else(|| {
+            let num = self.next;
+            self.next += 1;
+            ObjRef { num, gen: 0 }
+        })
+    }
+
+    /// The value of `/Size` covering all the allocated object numbers.
+    pub fn size(&self) -> ObjNum {
+        self.next
+    }
+}
+
+impl Default for ObjectAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::reader::SimpleReader;
+    use crate::utils;
+
+    #[test]
+    fn test_allocate() {
+        let data = utils::make_pdf(&["<< /Type /Catalog >>", "null", "null"]);
+        let mut rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+        let mut alloc = ObjectAllocator::from_xref(&rdr.xref);
+        let refs = (0..3).map(|_| alloc.allocate()).collect::<Vec<_>>();
+        assert_eq!(refs, (4..7).map(|num| ObjRef { num, gen: 0 }).collect::<Vec<_>>());
+        assert!(refs.iter().all(|objref| rdr.xref.locate(objref) == Some(Record::default())));
+        assert_eq!(alloc.size(), 7);
+
+        rdr.xref.map.insert(2, Record::Free { gen: 1, next: 0 });
+        let mut alloc = ObjectAllocator::from_xref(&rdr.xref);
+        assert_eq!(alloc.allocate(), ObjRef { num: 2, gen: 1 });
+        assert_eq!(alloc.allocate(), ObjRef { num: 4, gen: 0 });
+
+        let mut alloc = ObjectAllocator::new();
+        assert_eq!(alloc.allocate(), ObjRef { num: 1, gen: 0 });
+        assert_eq!(alloc.size(), 2);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::base::*;
+use crate::base::types::*;
+
+use super::ObjectAllocator;
+
+/// Creates a new PDF file from scratch.
+///
+/// Objects are added using [`PdfBuilder::add()`], or using [`PdfBuilder::reserve()`] followed by 
+/// [`PdfBuilder::set()`] when their references are needed in advance. Pages are added using 
+/// [`PdfBuilder::add_page()`]. The document catalog and the page tree are maintained by the 
+/// builder.
+pub struct PdfBuilder {
+    alloc: ObjectAllocator,
+    objects: BTreeMap<ObjRef, OwnedObject>,
+    catalog_ref: ObjRef,
+    catalog: Vec<(Name, OwnedObject)>,
+    pages_ref: ObjRef,
+    pages: Vec<ObjRef>,
+}
+
+impl PdfBuilder {
+    /// Creates an empty `PdfBuilder`.
+    pub fn new() -> Self {
+        let mut alloc = ObjectAllocator::new();
+        let catalog_ref = alloc.allocate();
+        let pages_ref = alloc.allocate();
+        Self { alloc, objects: BTreeMap::new(), catalog_ref, catalog: Vec::new(), pages_ref, pages: Vec::new() }
+    }
+
+    /// Adds an object, returning its newly allocated reference.
+    pub fn add(&mut self, obj: OwnedObject) -> ObjRef {
+        let objref = self.reserve();
+        self.objects.insert(objref, obj);
+        objref
+    }
+
+    /// Allocates a reference for an object to be provided later using [`PdfBuilder::set()`]. If 
+    /// that does not happen, a `null` object is written in its place.
+    pub fn reserve(&mut self) -> ObjRef {
+        self.alloc.allocate()
+    }
+
+    /// Sets the object for a reference obtained from [`PdfBuilder::reserve()`].
+    ///
+    /// A reference which was not allocated by this builder, or which belongs to the document 
+    /// catalog or the page tree, results in an error of the kind 
+    /// [`std::io::ErrorKind::InvalidInput`].
+    pub fn set(&mut self, objref: ObjRef, obj: OwnedObject) -> std::io::Result<()> {
+        if objref.num == 0 || objref.num >= self.alloc.size() || objref.gen != 0
+            || objref == self.catalog_ref || objref == self.pages_ref
+        {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "object number not reserved"));
+        }
+        self.objects.insert(objref, obj);
+        Ok(())
+    }
+
+    /// Adds a page given by its page dictionary, returning its reference. The `/Type` and 
+    /// `/Parent` entries are filled in by the builder.
+    pub fn add_page(&mut self, page: OwnedDict) -> ObjRef {
+        let dict = [
+                (Name::from(b"Type"), OwnedObject::new_name(b"Page")),
+                (Name::from(b"Parent"), OwnedObject::Ref(self.pages_ref)),
+            ].into_iter()
+            .chain(page.into_iter().filter(|(key, _)| key != b"Type" && key != b"Parent"))
+            .collect::<Vec<_>>();
+        let objref = self.add(OwnedObject::Dict(OwnedDict::from(dict)));
+        self.pages.push(objref);
+        objref
+    }
+
+    /// Adds an entry to the document catalog. The `/Type` and `/Pages` entries are filled in by 
+    /// the builder.
+    pub fn set_catalog_entry(&mut self, key: &[u8], val: OwnedObject) {
+        self.catalog.retain(|(name, _)| name != key);
+        self.catalog.push((Name::from(key), val));
+    }
+
+    /// The reference to the document catalog.
+    pub fn catalog_ref(&self) -> ObjRef {
+        self.catalog_ref
+    }
+
+    /// Serializes the document into a complete PDF file.
+    pub fn finish(mut self) -> Result<Vec<u8>, Error> {
+        let kids = self.pages.iter().map(|&objref| OwnedObject::Ref(objref)).collect();
+        self.objects.insert(self.pages_ref, OwnedObject::Dict(OwnedDict::from(vec![
+            (Name::from(b"Type"), OwnedObject::new_name(b"Pages")),
+            (Name::from(b"Kids"), OwnedObject::Array(kids)),
+            (Name::from(b"Count"), OwnedObject::Number(Number::Int(self.pages.len() as i64))),
+        ])));
+        let catalog = [
+                (Name::from(b"Type"), OwnedObject::new_name(b"Catalog")),
+                (Name::from(b"Pages"), OwnedObject::Ref(self.pages_ref)),
+            ].into_iter()
+            .chain(self.catalog.into_iter().filter(|(key, _)| key != b"Type" && key != b"Pages"))
+            .collect::<Vec<_>>();
+        self.objects.insert(self.catalog_ref, OwnedObject::Dict(OwnedDict::from(catalog)));
+
+        let mut out = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let size = self.alloc.size();
+        let mut map = BTreeMap::from([(0, Record::default())]);
+        for num in 1..size {
+            let objref = ObjRef { num, gen: 0 };
+            map.insert(num, Record::Used { gen: 0, offset: out.len() as Offset });
+            writeln!(out, "{num} 0 obj")?;
+            match self.objects.get(&objref) {
+                Some(obj) => obj.write_to(&mut out)?,
+                None => out.extend_from_slice(b"null")
+            }
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_pos = out.len();
+        let dict = Dict::from(vec![
+            (Name::from(b"Size"), Object::Number(Number::Int(size as i64))),
+            (Name::from(b"Root"), Object::Ref(self.catalog_ref)),
+        ]);
+        let xref = XRef { tpe: XRefType::Table, map, dict, size };
+        xref.write_table(&mut out)?;
+        write!(out, "startxref\n{xref_pos}\n%%EOF\n")?;
+        Ok(out)
+    }
+}
+
+impl Default for PdfBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use crate::document::Document;
+
+    #[test]
+    fn test_builder() {
+        let mut builder = PdfBuilder::new();
+        let content = builder.add(OwnedObject::Stream(Stream {
+            dict: OwnedDict::default(),
+            data: b"BT /F1 12 Tf 72 720 Td (Hello) Tj ET".to_vec()
+        }));
+        let font = builder.reserve();
+        let page = builder.add_page(OwnedDict::from(vec![
+            (Name::from(b"MediaBox"), OwnedObject::Array([0, 0, 612, 792].into_iter()
+                .map(|x| OwnedObject::Number(Number::Int(x))).collect())),
+            (Name::from(b"Contents"), OwnedObject::Ref(content)),
+            (Name::from(b"Resources"), OwnedObject::Dict(OwnedDict::from(vec![
+                (Name::from(b"Font"), OwnedObject::Dict(OwnedDict::from(vec![(Name::from(b"F1"), OwnedObject::Ref(font))])))
+            ]))),
+        ]));
+        builder.set(font, OwnedObject::Dict(OwnedDict::from(vec![
+            (Name::from(b"Type"), OwnedObject::new_name(b"Font")),
+            (Name::from(b"Subtype"), OwnedObject::new_name(b"Type1")),
+            (Name::from(b"BaseFont"), OwnedObject::new_name(b"Helvetica")),
+        ]))).unwrap();
+        assert!(builder.set(ObjRef { num: 100, gen: 0 }, OwnedObject::Null).is_err());
+        assert!(builder.set(builder.catalog_ref(), OwnedObject::Null).is_err());
+        builder.set_catalog_entry(b"PageMode", OwnedObject::new_name(b"UseNone"));
+        let data = builder.finish().unwrap();
+
+        let doc = Document::new(Cursor::new(data)).unwrap();
+        assert_eq!(doc.catalog().lookup(b"PageMode"), &Object::new_name(b"UseNone"));
+        let pages = doc.pages().unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].objref, page);
+        let rdr = doc.reader();
+        let stm = rdr.resolve_stream(pages[0].dict.lookup(b"Contents")).unwrap();
+        let mut s = Vec::new();
+        rdr.read_stream_data(&stm).unwrap().read_to_end(&mut s).unwrap();
+        assert_eq!(s, b"BT /F1 12 Tf 72 720 Td (Hello) Tj ET");
+        let font = rdr.resolve_dict(&Object::Ref(font)).unwrap();
+        assert_eq!(font.lookup(b"BaseFont"), &Object::new_name(b"Helvetica"));
+    }
+}
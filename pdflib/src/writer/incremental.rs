@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::io::{Cursor, Write};
+
+use crate::base::*;
+use crate::base::types::*;
+use crate::parser::FileParser;
+
+/// Appends an incremental update to an existing PDF file.
+///
+/// The new or replaced objects are collected using [`IncrementalWriter::add()`]. Upon writing, 
+/// the original file is reproduced unchanged, followed by the objects, a new cross-reference 
+/// table section linked to the original one via `/Prev`, and a new trailer.
+pub struct IncrementalWriter<'a> {
+    original: &'a [u8],
+    objects: BTreeMap<ObjRef, OwnedObject>,
+}
+
+impl<'a> IncrementalWriter<'a> {
+    /// Creates an `IncrementalWriter` updating the file given by its contents.
+    pub fn new(original: &'a [u8]) -> Self {
+        Self { original, objects: BTreeMap::new() }
+    }
+
+    /// Adds a new object or replaces an existing one. If `objref` has been added before, the 
+    /// previous object is discarded.
+    pub fn add(&mut self, objref: ObjRef, obj: OwnedObject) {
+        self.objects.insert(objref, obj);
+    }
+
+    /// Writes the original file followed by the update into `w`.
+    ///
+    /// Fails if the cross-reference table of the original file can not be located, as the new 
+    /// section needs to refer to it.
+    pub fn write_to(&self, w: &mut dyn Write) -> Result<(), Error> {
+        let parser = FileParser::new(Cursor::new(self.original));
+        let start = match parser.header() {
+            Ok(header) => header.start,
+            Err(err) => return Err(err.clone())
+        };
+        let prev = parser.entrypoint()?;
+        let prev_xref = parser.read_xref_at(prev)?;
+        // Offsets are relative to %PDF
+        let base = self.original.len() as Offset - start;
+
+        let mut out = Vec::new();
+        if !matches!(self.original.last(), Some(b'\n' | b'\r')) {
+            out.push(b'\n');
+        }
+        let mut map = BTreeMap::new();
+        for (objref, obj) in &self.objects {
+            map.insert(objref.num, Record::Used { gen: objref.gen, offset: base + out.len() as Offset });
+            writeln!(out, "{} {} obj", objref.num, objref.gen)?;
+            obj.write_to(&mut out)?;
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_pos = base + out.len() as Offset;
+        let size = map.last_key_value()
+            .map(|(num, _)| std::cmp::max(num + 1, prev_xref.size))
+            .unwrap_or(prev_xref.size);
+        let mut dict = prev_xref.dict.into_iter()
+            .filter(|(key, _)| [&b"Root"[..], b"Info", b"ID", b"Encrypt"].iter().any(|k| key == k))
+            .collect::<Vec<_>>();
+        dict.push((Name::from(b"Size"), Object::Number(Number::Int(size as i64))));
+        dict.push((Name::from(b"Prev"), Object::Number(Number::Int(prev as i64))));
+        let xref = XRef { tpe: XRefType::Table, map, dict: Dict::from(dict), size };
+        xref.write_table(&mut out)?;
+        write!(out, "startxref\n{xref_pos}\n%%EOF\n")?;
+
+        w.write_all(self.original)?;
+        w.write_all(&out)?;
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::SimpleReader;
+    use crate::base::Resolver;
+
+    #[test]
+    fn test_incremental() {
+        let original = std::fs::read("src/tests/basic.pdf").unwrap();
+        let rdr = SimpleReader::new(Cursor::new(&original[..])).unwrap();
+        let size = rdr.xref.size;
+        let kids = rdr.resolve_ref(&ObjRef { num: 2, gen: 0 }).unwrap();
+        drop(rdr);
+
+        let mut writer = IncrementalWriter::new(&original);
+        let pages = OwnedObject::Dict(OwnedDict::from(vec![
+            (Name::from(b"Type"), OwnedObject::new_name(b"Pages")),
+            (Name::from(b"Kids"), OwnedObject::Array(vec![OwnedObject::Ref(ObjRef { num: 2, gen: 0 })])),
+            (Name::from(b"Count"), OwnedObject::Number(Number::Int(1))),
+            (Name::from(b"Rotate"), OwnedObject::Number(Number::Int(90))),
+        ]));
+        writer.add(ObjRef { num: 1, gen: 0 }, pages);
+        writer.add(ObjRef { num: size, gen: 0 }, OwnedObject::Stream(Stream {
+            dict: OwnedDict::default(), data: b"new data".to_vec() }));
+        let mut updated = Vec::new();
+        writer.write_to(&mut updated).unwrap();
+        assert!(updated.starts_with(&original));
+
+        let rdr = SimpleReader::new(Cursor::new(updated)).unwrap();
+        assert_eq!(rdr.xref.size, size + 1);
+        let pages = rdr.resolve_dict(&Object::Ref(ObjRef { num: 1, gen: 0 })).unwrap();
+        assert_eq!(pages.lookup(b"Rotate"), &Object::Number(Number::Int(90)));
+        assert_eq!(rdr.resolve_ref(&ObjRef { num: 2, gen: 0 }).unwrap(), kids);
+        let stm = rdr.resolve_stream(&Object::Ref(ObjRef { num: size, gen: 0 })).unwrap();
+        let mut data = Vec::new();
+        std::io::Read::read_to_end(&mut rdr.read_stream_data(&stm).unwrap(), &mut data).unwrap();
+        assert_eq!(data, b"new data");
+    }
+}
@@ -0,0 +1,10 @@
+//! Writing of PDF files.
+
+mod alloc;
+pub use alloc::ObjectAllocator;
+
+mod incremental;
+pub use incremental::IncrementalWriter;
+
+mod builder;
+pub use builder::PdfBuilder;
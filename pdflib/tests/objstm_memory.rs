@@ -0,0 +1,91 @@
+//! Checks that resolving small members of a large object stream does not load the whole stream
+//! into memory. This needs its own test binary as it replaces the global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::Cursor;
+
+use pdflib::*;
+use pdflib::reader::SimpleReader;
+
+/// Tracks the memory allocated by each thread, to assert that it stays bounded.
+struct PeakAlloc;
+
+thread_local! {
+    /// The current and peak allocated size.
+    static ALLOCATED: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATED.try_with(|cell| {
+            let (curr, peak) = cell.get();
+            cell.set((curr + layout.size(), peak.max(curr + layout.size())));
+        });
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = ALLOCATED.try_with(|cell| {
+            let (curr, peak) = cell.get();
+            cell.set((curr.saturating_sub(layout.size()), peak));
+        });
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAlloc = PeakAlloc;
+
+/// Returns the peak of memory allocated by this thread while running `func`.
+fn peak_allocation(func: impl FnOnce()) -> usize {
+    ALLOCATED.with(|cell| cell.set((0, 0)));
+    func();
+    ALLOCATED.with(|cell| cell.get().1)
+}
+
+/// Assembles a PDF file with a catalog (object 1) followed by `compressed` stored in an object
+/// stream, using a cross-reference stream.
+fn make_pdf_objstm(compressed: &[&str]) -> Vec<u8> {
+    let mut data = b"%PDF-1.7\n".to_vec();
+    // (type, field 2, field 3)
+    let mut records = vec![(0, 0, 65535), (1, data.len(), 0)];
+    data.extend_from_slice(b"1 0 obj\n<< /Type /Catalog >>\nendobj\n");
+    let objstm_num = compressed.len() + 2;
+    let mut header = String::new();
+    let mut body = String::new();
+    for (ix, obj) in compressed.iter().enumerate() {
+        records.push((2, objstm_num, ix));
+        header += &format!("{} {} ", ix + 2, body.len());
+        body += obj;
+        body += "\n";
+    }
+    records.push((1, data.len(), 0));
+    data.extend_from_slice(format!("{objstm_num} 0 obj\n<< /Type /ObjStm /N {} /First {} /Length {} >>\n\
+        stream\n{header}{body}\nendstream\nendobj\n",
+        compressed.len(), header.len(), header.len() + body.len()).as_bytes());
+    let start = data.len();
+    records.push((1, start, 0));
+    let size = records.len();
+    let hex = records.iter()
+        .map(|&(tpe, f2, f3)| format!("{tpe:02X}{f2:08X}{f3:04X}"))
+        .collect::<String>() + ">";
+    data.extend_from_slice(format!("{} 0 obj\n<< /Type /XRef /Size {size} /W [1 4 2] /Root 1 0 R \
+        /Filter /ASCIIHexDecode /Length {} >>\nstream\n{hex}\nendstream\nendobj\nstartxref\n{start}\n%%EOF\n",
+        size - 1, hex.len()).as_bytes());
+    data
+}
+
+#[test]
+fn test_objstm_peak_allocation() {
+    const MIB: usize = 1 << 20;
+    let large = format!("({})", "x".repeat(16 * MIB));
+    let data = make_pdf_objstm(&["[1 2]", &large, "42"]);
+    let rdr = SimpleReader::new(Cursor::new(data)).unwrap();
+    let peak = peak_allocation(|| {
+        assert_eq!(rdr.resolve_ref(&ObjRef { num: 4, gen: 0 }).unwrap(), Object::Number(Number::Int(42)));
+        assert_eq!(rdr.resolve_ref(&ObjRef { num: 2, gen: 0 }).unwrap(), Object::Array(vec![
+            Object::Number(Number::Int(1)), Object::Number(Number::Int(2))]));
+    });
+    assert!(peak < 4 * MIB, "peak allocation {peak}");
+}
@@ -29,19 +29,17 @@ fn main() -> Result<(), pdf::Error> {
     };
 
     let file = File::open(fname)?;
-    let reader = pdf::reader::SimpleReader::new(BufReader::new(file))?;
+    let doc = pdf::document::Document::new(BufReader::new(file))?;
+    let reader = doc.reader();
     let xref = &reader.xref;
     let trailer = || pdf::Object::Dict(xref.dict.clone());
     let mut history = vec![];
     let mut curr_obj = trailer();
-    curr_obj.print_indented(0);
+    curr_obj.print_indented(0, &mut std::io::stdout())?;
 
     let root_ref = xref.dict.lookup(b"Root")
         .as_objref()
         .ok_or(pdf::Error::Parse("Could not find /Root."))?;
-    let root = reader.resolve_ref(root_ref)?
-        .into_dict()
-        .ok_or(pdf::Error::Parse("Could not find /Root."))?;
 
     'main: for line in std::io::stdin().lines() {
         let line = line?;
@@ -88,14 +86,18 @@ fn main() -> Result<(), pdf::Error> {
                     log::error!("Malformed page number.");
                     continue;
                 };
-                let objref = try_or_continue!(find_page(&reader, &root, page_num));
+                let Some(index) = page_num.checked_sub(1) else {
+                    log::error!("Page number out of range.");
+                    continue;
+                };
+                let objref = try_or_continue!(doc.find_page(index)).objref;
                 println!("{}", objref);
                 curr_obj = try_or_continue!(reader.resolve_ref(&objref));
                 history.push(objref);
             },
             [part, ref rest @ ..] if part.starts_with('/') || part.starts_with('[') => {
                 let mut subobj = &curr_obj;
-                for spec in std::iter::once(part).chain(rest.into_iter().map(std::ops::Deref::deref)) {
+                for spec in std::iter::once(part).chain(rest.iter().map(std::ops::Deref::deref)) {
                     let bs = spec.as_bytes();
                     match bs[0] {
                         b'/' => {
@@ -142,7 +144,7 @@ fn main() -> Result<(), pdf::Error> {
                     curr_obj = try_or_continue!(reader.resolve_ref(&objref));
                     history.push(objref);
                 } else {
-                    subobj.print_indented(0);
+                    subobj.print_indented(0, &mut std::io::stdout())?;
                     continue;
                 }
             },
@@ -157,101 +159,8 @@ fn main() -> Result<(), pdf::Error> {
             },
             _ => log::error!("Unknown command.")
         }
-        curr_obj.print_indented(0);
+        curr_obj.print_indented(0, &mut std::io::stdout())?;
     }
 
     Ok(())
 }
-
-trait PrettyPrint {
-    const SPACES: &str = "  ";
-
-    fn print_indented(&self, indent: usize);
-}
-
-impl PrettyPrint for pdf::Object {
-    fn print_indented(&self, indent: usize) {
-        let ind = Self::SPACES.repeat(indent);
-        match self {
-            pdf::Object::Array(arr) => arr.print_indented(indent),
-            pdf::Object::Dict(dict) => dict.print_indented(indent),
-            pdf::Object::Stream(stm) => {
-                stm.dict.print_indented(indent);
-                println!("{ind}[stream]");
-            },
-            obj => println!("{obj}")
-        }
-    }
-}
-
-impl PrettyPrint for Vec<pdf::Object> {
-    fn print_indented(&self, indent: usize) {
-        let ind = Self::SPACES.repeat(indent);
-        println!("[");
-        for (index, item) in self.iter().enumerate() {
-            print!("{ind}{}[{}] ", Self::SPACES, index + 1);
-            item.print_indented(indent + 1);
-        }
-        println!("{ind}]");
-    }
-}
-
-impl PrettyPrint for pdf::Dict {
-    fn print_indented(&self, indent: usize) {
-        let ind = Self::SPACES.repeat(indent);
-        println!("<<");
-        for (key, val) in self.iter() {
-            print!("{ind}{}{key} ", Self::SPACES);
-            val.print_indented(indent + 1);
-        }
-        println!("{ind}>>");
-   }
-}
-
-fn find_page(reader: &pdf::reader::SimpleReader<BufReader<File>>, root: &pdf::Dict,
-    page_num: usize) -> Result<pdf::ObjRef, pdf::Error> {
-    let Some(mut num) = page_num.checked_sub(1) else {
-        return Err(pdf::Error::Parse("Page number out of range."));
-    };
-    let mut curr_ref = *root.lookup(b"Pages").as_objref()
-        .ok_or(pdf::Error::Parse("Could not find /Pages."))?;
-    let mut curr_node = reader.resolve_ref(&curr_ref)?.into_dict()
-        .ok_or(pdf::Error::Parse("Could not find /Pages."))?;
-    let mut count = curr_node.lookup(b"Count").num_value()
-        .ok_or(pdf::Error::Parse("Could not read page tree."))?;
-    let err = || pdf::Error::Parse("Could not read page tree.");
-    'a: loop {
-        if num >= count {
-            return Err(pdf::Error::Parse("Page number out of range."));
-        }
-        let kids = reader.resolve_obj(curr_node.lookup(b"Kids").to_owned())?.into_array().ok_or(err())?;
-        if kids.len() == count {
-            return Ok(*kids[num].as_objref().ok_or(err())?);
-        }
-        for kid in kids {
-            let objref = *kid.as_objref().ok_or(err())?;
-            let node = reader.resolve_ref(&objref)?.into_dict().ok_or(err())?;
-            if node.lookup(b"Parent") != &pdf::Object::Ref(curr_ref) {
-                return Err(pdf::Error::Parse("malformed page tree (/Parent)"));
-            }
-            let this_count = match node.lookup(b"Type")
-                .as_name()
-                .ok_or(pdf::Error::Parse("malformed page tree"))?
-                .as_slice() {
-                    b"Pages" =>
-                        node.lookup(b"Count").num_value()
-                            .ok_or(pdf::Error::Parse("Could not read page tree."))?,
-                    b"Page" => 1,
-                    _ => return Err(pdf::Error::Parse("malformed page tree (/Type)"))
-            };
-            if this_count > num {
-                count = this_count;
-                curr_ref = objref;
-                curr_node = node;
-                continue 'a;
-            } else {
-                num -= this_count;
-            }
-        }
-    }
-}